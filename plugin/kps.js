@@ -322,20 +322,22 @@ dmn.plugin.defineElement({
     uid: "preview",
   },
 
-  onMount: ({
-    setState,
-    onHook,
-    getSettings,
-    expose,
-    setAnchor,
-    onSettingsChange,
-  }) => {
-    const timestamps = [];
-    let max = 0;
-    let kpsSum = 0;
-    let kpsCount = 0;
+  onMount: ({ setState, getSettings, expose, setAnchor, onSettingsChange }) => {
+    // KPS / AVG / MAX는 앱의 통계 서비스 값 사용 (오버레이가 숨겨지거나 새로고침돼도 유지)
+    let latest = { kps: 0, avg: 0, max: 0 };
     let maxval = 1;
 
+    const applyReport = (report) => {
+      const stats = report && report.modes[report.mode];
+      latest = stats
+        ? {
+            kps: stats.kps,
+            avg: Math.round(stats.avgKps),
+            max: stats.peakKps,
+          }
+        : { kps: 0, avg: 0, max: 0 };
+    };
+
     // 초기 설정으로 historyBuffer 크기 결정
     const initialSettings = getSettings();
     const GRAPH_UPDATE_MS = 100; // 그래프 히스토리 업데이트 주기
@@ -359,16 +361,24 @@ dmn.plugin.defineElement({
       }
     });
 
+    const unsubscribeKps = dmn.stats.onKps(applyReport);
+    dmn.stats
+      .getKps()
+      .then(applyReport)
+      .catch(() => {});
+
     const resetStats = () => {
       const currentSettings = getSettings();
       const targetSize = Math.ceil(
         (currentSettings.graphSpeed || 1000) / GRAPH_UPDATE_MS
       );
 
-      timestamps.length = 0;
-      max = 0;
-      kpsSum = 0;
-      kpsCount = 0;
+      dmn.stats
+        .getKps()
+        .then((report) => dmn.stats.resetKps(report.mode))
+        .then(applyReport)
+        .catch(() => {});
+      latest = { kps: 0, avg: 0, max: 0 };
       maxval = 1;
       historyBuffer = new Array(targetSize).fill(0);
 
@@ -385,46 +395,9 @@ dmn.plugin.defineElement({
       reset: resetStats,
     });
 
-    // 현재 눌린 키를 추적하기 위한 Set
-    const pressedKeys = new Set();
-
-    // 키 입력 감지
-    onHook("key", ({ key, state }) => {
-      if (typeof state === "string") {
-        const keyState = state.toLowerCase();
-
-        if (keyState === "down") {
-          // 키가 이미 눌려있지 않은 경우에만 카운팅 (홀드 방지)
-          if (!pressedKeys.has(key)) {
-            pressedKeys.add(key);
-            const now = Date.now();
-            timestamps.push(now);
-          }
-        } else if (keyState === "up") {
-          pressedKeys.delete(key);
-        }
-      }
-    });
-
-    // KPS 계산 루프 (50ms마다)
+    // 그래프 갱신 루프 (50ms마다)
     const interval = setInterval(() => {
-      const now = Date.now();
-      // 1초 지난 기록 제거
-      while (timestamps.length > 0 && timestamps[0] < now - 1000) {
-        timestamps.shift();
-      }
-
-      const kps = timestamps.length;
-
-      // 최대값 업데이트
-      if (kps > max) max = kps;
-
-      // 평균값 업데이트
-      if (kps > 0) {
-        kpsSum += kps;
-        kpsCount++;
-      }
-      const avg = kpsCount > 0 ? Math.round(kpsSum / kpsCount) : 0;
+      const { kps, avg, max } = latest;
 
       // 그래프 스케일링용 최대값 업데이트
       if (kps > maxval) maxval = kps;
@@ -453,6 +426,7 @@ dmn.plugin.defineElement({
 
     return () => {
       clearInterval(interval);
+      unsubscribeKps();
     };
   },
 });
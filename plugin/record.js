@@ -243,7 +243,10 @@ async function saveRecording() {
       duration: Date.now() - recordingStartTime,
       totalEvents: recordedData.length,
       events: recordedData.map((event) => {
-        const relativeMs = event.timestamp - recordingStartTime;
+        const relativeMs = Math.max(
+          0,
+          Math.round(event.timestamp - recordingStartTime)
+        );
         return {
           key: event.key,
           action: event.action,
@@ -285,13 +288,19 @@ async function saveRecording() {
 }
 
 // 키 입력 이벤트 핸들러
-function onKeyState({ key, state }) {
+function onKeyState({ key, state, timestampUs, wallClockOffsetUs }) {
   if (!isRecording) return;
 
+  // 데몬 캡처 시각이 있으면 사용 (IPC/이벤트 루프 지연 제외)
+  const timestamp =
+    typeof timestampUs === "number" && typeof wallClockOffsetUs === "number"
+      ? (timestampUs + wallClockOffsetUs) / 1000
+      : Date.now();
+
   const event = {
     key,
     action: state, // 'DOWN' or 'UP'
-    timestamp: Date.now(),
+    timestamp,
   };
 
   recordedData.push(event);
@@ -1,18 +1,19 @@
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     io::{BufRead, BufReader},
-    process::{Child, Command, Stdio},
+    process::{Child, ChildStdin, Command, Stdio},
     sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+        mpsc, Arc,
     },
     thread::{self, JoinHandle},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anyhow::{anyhow, Context, Result};
+use chrono::NaiveDate;
 use log::{error, warn};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use serde_json::json;
 use tauri::{
     AppHandle, Emitter, Manager, Monitor, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
@@ -21,16 +22,41 @@ use tauri::{
 use tauri_runtime_wry::wry::dpi::{LogicalPosition, LogicalSize};
 
 use crate::{
+    clock::{monotonic_us, wall_clock_offset_us},
+    ime::ImeState,
+    ipc::{CaptureFilters, CaptureState, ControlMessage},
     keyboard::KeyboardManager,
+    protocol::{self, Frame},
+    replay::ReplaySource,
     models::{
         overlay_resize_anchor_from_str, BootstrapOverlayState, BootstrapPayload, KeyCounters,
-        KeyMappings, OverlayBounds, OverlayResizeAnchor, SettingsDiff, SettingsState,
+        InjectedInputPolicy, InputSettings, KeyMappings, OverlayBounds, OverlayResizeAnchor,
+        CounterResetSchedule, SettingsDiff, SettingsState, ShortcutBinding, ShortcutsState,
+    },
+    services::{
+        chatter_filter::{ChatterFilter, KeyChatterStats},
+        counter_flush::CounterFlusher,
+        counter_history::{CounterHistory, CounterHistoryRange, HistoryGranularity},
+        counter_snapshots::{
+            restore_counters, scoped_counters, CounterSnapshot, CounterSnapshots, SnapshotReason,
+        },
+        css_watcher::CssWatcher,
+        key_gestures::{KeyGestures, KeyHold},
+        key_timing::{KeyTimingReport, KeyTimingStats},
+        play_sessions::{PlaySession, PlaySessions},
+        kps_stats::{self, KpsReport, KpsStats},
+        event_batcher::{BatchEntry, EventBatcher},
+        latency::{LatencyReport, LatencyStats},
+        daemon_supervisor::{CrashAction, DaemonHealth, DaemonSupervisor},
+        settings::SettingsService,
+        wheel_pulse::{is_wheel_label, WheelPress, WheelPulses},
     },
-    services::{css_watcher::CssWatcher, settings::SettingsService},
     store::AppStore,
 };
 
 const OVERLAY_LABEL: &str = "overlay";
+/// 기본 키 모드 (전환 순서)
+const BUILTIN_KEY_MODES: [&str; 4] = ["4key", "5key", "6key", "8key"];
 const DEFAULT_OVERLAY_WIDTH: f64 = 860.0;
 const DEFAULT_OVERLAY_HEIGHT: f64 = 320.0;
 const OVERLAY_MARGIN: f64 = 40.0;
@@ -44,24 +70,91 @@ pub struct AppState {
     keyboard_task: RwLock<Option<KeyboardDaemonTask>>,
     key_counters: Arc<RwLock<KeyCounters>>,
     key_counter_enabled: Arc<AtomicBool>,
+    /// 카운터 증가분 주기 저장 (스레드 생성 실패 시 종료·초기화 때만 저장)
+    counter_flusher: Option<CounterFlusher>,
+    /// 시간·하루 단위 카운터 기록 (`counter_history.json`)
+    counter_history: Arc<CounterHistory>,
+    /// 초기화 전 카운터 스냅샷 (`counter_snapshots.json`)
+    counter_snapshots: Arc<CounterSnapshots>,
+    input_settings: Arc<RwLock<InputSettings>>,
+    /// 키 채터링 필터 (디바운스)
+    chatter: ChatterFilter,
+    /// 캡처 → 이벤트 발생 / 오버레이 렌더 지연 시간
+    latency: LatencyStats,
+    /// 모드별 KPS / APM 통계
+    kps: KpsStats,
+    /// `stats:kps` 갱신·세션 종료 감지 스레드 실행 여부 (종료 시 내림)
+    stats_ticker_running: Arc<AtomicBool>,
+    /// 키별 누름 시간·간격 분포
+    key_timing: KeyTimingStats,
+    /// 자동 감지한 플레이 세션 (`play_sessions.json`)
+    play_sessions: PlaySessions,
     active_keys: Arc<RwLock<HashSet<String>>>,
     /// Raw input stream subscriber count - emit only when > 0
     raw_input_subscribers: Arc<std::sync::atomic::AtomicU32>,
+    /// 원시 입력 허용 수 (> 0이면 `mapped_keys_only`여도 데몬이 모든 키 전달)
+    raw_input_grants: AtomicU32,
     /// CSS 파일 핫리로딩 워처
     css_watcher: RwLock<Option<CssWatcher>>,
+    /// 데몬 입력 전달 일시정지 여부 (데몬 재시작 시 다시 전송)
+    capture_paused: AtomicBool,
+    /// 데몬 입력 장치 필터 (데몬 재시작 시 다시 전송)
+    capture_filters: RwLock<CaptureFilters>,
+    /// 포커스된 앱의 IME·입력 언어 상태 (데몬이 바뀔 때마다 보고, 보고 전에는 `None`)
+    ime_state: RwLock<Option<ImeState>>,
+    /// 응답 대기 중인 ping (nonce → 대기자)
+    daemon_pings: Mutex<HashMap<u64, mpsc::Sender<()>>>,
+    next_ping_nonce: AtomicU64,
+    /// 데몬 크래시 감지 및 재시작 상태
+    daemon_supervisor: Arc<DaemonSupervisor>,
+    /// 데몬 실행 세대 (시작/중지마다 증가, 이전 세대의 재시작 예약을 무효화)
+    daemon_generation: AtomicU64,
+    /// 실제 입력 대신 재생할 녹화 파일 (없으면 하드웨어 입력)
+    replay_source: RwLock<Option<ReplaySource>>,
 }
 
 impl AppState {
     pub fn initialize(store: AppStore) -> Result<Self> {
         let store = Arc::new(store);
         let snapshot = store.snapshot();
-        let keyboard =
-            KeyboardManager::new(snapshot.keys.clone(), snapshot.selected_key_type.clone());
+        let keyboard = KeyboardManager::new(
+            snapshot.keys.clone(),
+            snapshot.key_mode_devices.clone(),
+            snapshot.selected_key_type.clone(),
+        );
         let settings = SettingsService::new(store.clone());
 
         let key_counters = Arc::new(RwLock::new(snapshot.key_counters.clone()));
         Self::sync_counters_with_keys_impl(&key_counters, &snapshot.keys);
+        let input_settings = Arc::new(RwLock::new(snapshot.input_settings.clone()));
+        let counter_history = Arc::new(CounterHistory::load(
+            store.data_dir(),
+            snapshot.input_settings.history_retention_days,
+        ));
+        let play_sessions = PlaySessions::load(store.data_dir());
+        let counter_snapshots = Arc::new(CounterSnapshots::load(store.data_dir()));
+        let counter_flusher = {
+            let store = store.clone();
+            let key_counters = key_counters.clone();
+            let counter_history = counter_history.clone();
+            let counter_snapshots = counter_snapshots.clone();
+            let input_settings = input_settings.clone();
+            let flush = move || {
+                let snapshot = key_counters.read().clone();
+                store.set_key_counters(snapshot)?;
+                counter_history.save(input_settings.read().history_retention_days)?;
+                counter_snapshots.save()
+            };
+            match CounterFlusher::spawn(flush) {
+                Ok(flusher) => Some(flusher),
+                Err(err) => {
+                    log::warn!("periodic key counter persistence disabled: {err}");
+                    None
+                }
+            }
+        };
         let key_counter_enabled = Arc::new(AtomicBool::new(snapshot.key_counter_enabled));
+        let chatter = ChatterFilter::new(snapshot.input_settings.debounce_ms);
         let active_keys = Arc::new(RwLock::new(HashSet::new()));
 
         Ok(Self {
@@ -73,9 +166,28 @@ impl AppState {
             keyboard_task: RwLock::new(None),
             key_counters,
             key_counter_enabled,
+            counter_flusher,
+            counter_history,
+            counter_snapshots,
+            input_settings,
+            chatter,
+            latency: LatencyStats::new(),
+            kps: KpsStats::new(),
+            stats_ticker_running: Arc::new(AtomicBool::new(false)),
+            key_timing: KeyTimingStats::new(),
+            play_sessions,
             active_keys,
             raw_input_subscribers: Arc::new(std::sync::atomic::AtomicU32::new(0)),
+            raw_input_grants: AtomicU32::new(0),
             css_watcher: RwLock::new(None),
+            capture_paused: AtomicBool::new(false),
+            capture_filters: RwLock::new(CaptureFilters::default()),
+            ime_state: RwLock::new(None),
+            daemon_pings: Mutex::new(HashMap::new()),
+            next_ping_nonce: AtomicU64::new(1),
+            daemon_supervisor: Arc::new(DaemonSupervisor::new()),
+            daemon_generation: AtomicU64::new(0),
+            replay_source: RwLock::new(ReplaySource::from_env()),
         })
     }
 
@@ -92,7 +204,11 @@ impl AppState {
                 let _ = overlay.open_devtools();
             }
         }
+        if self.input_settings.read().counter_reset_schedule == CounterResetSchedule::AppStart {
+            self.reset_counters_on_schedule(app);
+        }
         self.start_keyboard_hook(app.clone())?;
+        self.start_stats_ticker(app);
         // CSS 핫리로딩 워처 초기화
         self.initialize_css_watcher(app);
         Ok(())
@@ -145,6 +261,7 @@ impl AppState {
                 overlay_resize_anchor: state.overlay_resize_anchor.clone(),
                 key_counter_enabled: state.key_counter_enabled,
                 grid_settings: state.grid_settings.clone(),
+                input_settings: state.input_settings.clone(),
                 shortcuts: state.shortcuts.clone(),
             },
             keys: state.keys.clone(),
@@ -176,6 +293,11 @@ impl AppState {
         if let Some(value) = diff.changed.key_counter_enabled {
             self.key_counter_enabled.store(value, Ordering::SeqCst);
         }
+        if let Some(value) = diff.changed.input_settings.as_ref() {
+            self.chatter.set_threshold_ms(value.debounce_ms);
+            *self.input_settings.write() = value.clone();
+            self.sync_key_allowlist();
+        }
         app.emit("settings:changed", diff)?;
         Ok(())
     }
@@ -229,9 +351,18 @@ impl AppState {
         if let Err(err) = self.persist_key_counters() {
             log::warn!("failed to persist key counters during shutdown: {err}");
         }
-        if let Some(task) = self.keyboard_task.write().take() {
-            drop(task);
+        let retention_days = self.input_settings.read().history_retention_days;
+        if let Err(err) = self.counter_history.save(retention_days) {
+            log::warn!("failed to persist counter history during shutdown: {err}");
+        }
+        if let Err(err) = self.counter_snapshots.save() {
+            log::warn!("failed to persist counter snapshots during shutdown: {err}");
         }
+        if let Err(err) = self.play_sessions.end_now() {
+            log::warn!("failed to persist play session during shutdown: {err}");
+        }
+        self.stop_keyboard_hook();
+        self.stats_ticker_running.store(false, Ordering::SeqCst);
         // CSS 워처 정리
         if let Some(watcher) = self.css_watcher.write().take() {
             watcher.shutdown();
@@ -353,12 +484,11 @@ impl AppState {
         if task_guard.is_some() {
             return Ok(());
         }
+        let generation = self.daemon_generation.fetch_add(1, Ordering::SeqCst) + 1;
 
         self.clear_active_keys();
 
         let current_exe = std::env::current_exe().context("failed to locate dm-note executable")?;
-        let shortcuts_json = serde_json::to_string(&self.store.settings_snapshot().shortcuts)
-            .unwrap_or_else(|_| "{}".to_string());
 
         // Prepare Named Pipe server asynchronously to avoid blocking before spawning the daemon.
         #[cfg(target_os = "windows")]
@@ -375,12 +505,16 @@ impl AppState {
         };
         #[cfg(not(target_os = "windows"))]
         let _pipe_receiver: Option<std::sync::mpsc::Receiver<Option<std::fs::File>>> = None;
-        let mut child = Command::new(current_exe)
+        let mut command = Command::new(current_exe);
+        command
             .arg("--keyboard-daemon")
-            .env("DMNOTE_HOTKEYS_V1", shortcuts_json)
-            .stdin(Stdio::null())
+            .stdin(Stdio::piped())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(source) = self.replay_source.read().as_ref() {
+            command.args(source.to_args());
+        }
+        let mut child = command
             .spawn()
             .context("failed to spawn keyboard daemon process")?;
 
@@ -389,16 +523,46 @@ impl AppState {
             .take()
             .context("keyboard daemon stdout unavailable")?;
         let stderr = child.stderr.take();
+        // Control link to the daemon; closing it makes the daemon exit.
+        let mut control = child
+            .stdin
+            .take()
+            .context("keyboard daemon stdin unavailable")?;
+        // The hello must be the first frame on the link, followed by the hotkeys the daemon
+        // starts with, so send them (and the current capture state) before anyone else can
+        // reach the control writer.
+        protocol::send_hello(&mut control).context("failed to greet keyboard daemon")?;
+        let mut initial = vec![
+            ControlMessage::SetHotkeys {
+                shortcuts: Box::new(self.store.settings_snapshot().shortcuts),
+            },
+            ControlMessage::SetFilters {
+                filters: *self.capture_filters.read(),
+            },
+        ];
+        if self.capture_paused.load(Ordering::SeqCst) {
+            initial.push(ControlMessage::Pause);
+        }
+        if let Some(keys) = self.key_allowlist() {
+            initial.push(ControlMessage::SetKeyAllowlist { keys: Some(keys) });
+        }
+        for message in initial {
+            protocol::write_frame(&mut control, &Frame::Control(message))
+                .context("failed to send initial state to keyboard daemon")?;
+        }
+        let control = Arc::new(Mutex::new(control));
+        let reader_control = control.clone();
 
         let running = Arc::new(AtomicBool::new(true));
         let running_reader = running.clone();
         let keyboard = self.keyboard.clone();
         let app_handle = app.clone();
+        let supervisor = self.daemon_supervisor.clone();
+        let stderr_supervisor = self.daemon_supervisor.clone();
 
         let reader_handle = thread::Builder::new()
             .name("keyboard-daemon-reader".into())
             .spawn(move || {
-            let mut keys_state_emit_count: u64 = 0;
                 // Prefer Named Pipe if available; otherwise, use stdout
                 #[allow(unused_mut)]
                 let mut reader: BufReader<Box<dyn std::io::Read + Send>> = {
@@ -419,7 +583,42 @@ impl AppState {
                         BufReader::new(Box::new(stdout))
                     }
                 };
-                let mut overlay_window = app_handle.get_webview_window(OVERLAY_LABEL);
+                let gestures = {
+                    let hold_handle = app_handle.clone();
+                    match KeyGestures::spawn(move |hold| emit_key_hold(&hold_handle, &hold)) {
+                        Ok(gestures) => Some(gestures),
+                        Err(err) => {
+                            warn!("key gestures disabled: {err}");
+                            None
+                        }
+                    }
+                };
+                let batcher = {
+                    let batch_handle = app_handle.clone();
+                    let emit_batch = move |entries| emit_key_batch(&batch_handle, entries);
+                    match EventBatcher::spawn(emit_batch) {
+                        Ok(batcher) => Some(Arc::new(batcher)),
+                        Err(err) => {
+                            warn!("batched key events disabled: {err}");
+                            None
+                        }
+                    }
+                };
+                let mut dispatcher = KeyEventDispatcher::new(app_handle.clone(), keyboard.clone())
+                    .with_gestures(gestures)
+                    .with_batcher(batcher.clone());
+                let wheel_pulses = {
+                    let mut release_dispatcher =
+                        KeyEventDispatcher::new(app_handle.clone(), keyboard.clone())
+                            .with_batcher(batcher);
+                    match WheelPulses::spawn(move |release| release_dispatcher.dispatch(&release)) {
+                        Ok(pulses) => Some(pulses),
+                        Err(err) => {
+                            warn!("wheel keys disabled: {err}");
+                            None
+                        }
+                    }
+                };
                 // Elevate reader thread priority slightly on Windows
                 #[cfg(target_os = "windows")]
                 unsafe {
@@ -427,213 +626,83 @@ impl AppState {
                     let _ = SetThreadPriority(GetCurrentThread(), THREAD_PRIORITY_ABOVE_NORMAL);
                 }
 
-                while running_reader.load(Ordering::SeqCst) {
-                    let mut line = String::new();
-                    match reader.read_line(&mut line) {
-                        Ok(0) => break,
-                        Ok(_) => {
-                            let s = line.trim();
-                            if s.is_empty() {
-                                continue;
-                            }
-
-                            // First, try to parse as DaemonCommand (global hotkeys)
-                            if let Ok(command) = serde_json::from_str::<crate::ipc::DaemonCommand>(s) {
-                                match command {
-                                    crate::ipc::DaemonCommand::ToggleOverlay => {
-                                        log::info!("[AppState] received ToggleOverlay command from daemon");
-                                        let app_state = app_handle.state::<AppState>();
-                                        let is_visible = *app_state.overlay_visible.read();
-                                        if let Err(err) = app_state.set_overlay_visibility(&app_handle, !is_visible) {
-                                            log::error!("failed to toggle overlay visibility: {err}");
-                                        }
-                                    }
-                                    crate::ipc::DaemonCommand::ToggleOverlayLock => {
-                                        log::info!("[AppState] received ToggleOverlayLock command from daemon");
-                                        let app_state = app_handle.state::<AppState>();
-                                        let current = app_state.store.snapshot().overlay_locked;
-                                        match app_state.settings.apply_patch(crate::models::SettingsPatchInput {
-                                            overlay_locked: Some(!current),
-                                            ..Default::default()
-                                        }) {
-                                            Ok(diff) => {
-                                                if let Err(err) = app_state.emit_settings_changed(&diff, &app_handle) {
-                                                    log::error!("failed to apply overlay lock toggle: {err}");
-                                                }
-                                            }
-                                            Err(err) => log::error!("failed to toggle overlay lock: {err}"),
-                                        }
-                                    }
-                                    crate::ipc::DaemonCommand::ToggleAlwaysOnTop => {
-                                        log::info!("[AppState] received ToggleAlwaysOnTop command from daemon");
-                                        let app_state = app_handle.state::<AppState>();
-                                        let current = app_state.store.snapshot().always_on_top;
-                                        match app_state.settings.apply_patch(crate::models::SettingsPatchInput {
-                                            always_on_top: Some(!current),
-                                            ..Default::default()
-                                        }) {
-                                            Ok(diff) => {
-                                                if let Err(err) = app_state.emit_settings_changed(&diff, &app_handle) {
-                                                    log::error!("failed to apply always-on-top toggle: {err}");
-                                                }
-                                            }
-                                            Err(err) => log::error!("failed to toggle always-on-top: {err}"),
-                                        }
-                                    }
-                                }
-                                continue;
-                            }
-
-                            // Preferred: JSON encoded HookMessage (with device).
-                            let parsed: Option<crate::ipc::HookMessage> =
-                                serde_json::from_str(s).ok();
-
-                            let message = if let Some(msg) = parsed {
-                                if msg.labels.is_empty() {
-                                    continue;
-                                }
-                                msg
-                            } else {
-                                // Legacy compact format: "D:<label>" / "U:<label>"
-                                if s.len() < 3
-                                    || !s.as_bytes().get(1).map(|c| *c == b':').unwrap_or(false)
-                                {
-                                    continue;
-                                }
-                                let (state_ch, rest) = s.split_at(1);
-                                let key = &rest[1..];
-                                if key.is_empty() {
-                                    continue;
-                                }
-                                crate::ipc::HookMessage {
-                                    device: crate::ipc::InputDeviceKind::Keyboard,
-                                    labels: vec![key.to_string()],
-                                    state: if state_ch == "D" {
-                                        crate::ipc::HookKeyState::Down
-                                    } else {
-                                        crate::ipc::HookKeyState::Up
-                                    },
-                                    vk_code: None,
-                                    scan_code: None,
-                                    flags: None,
-                                }
-                            };
-
-                            let device_str = match message.device {
-                                crate::ipc::InputDeviceKind::Keyboard => "keyboard",
-                                crate::ipc::InputDeviceKind::Mouse => "mouse",
-                                crate::ipc::InputDeviceKind::Gamepad => "gamepad",
-                                crate::ipc::InputDeviceKind::Unknown => "unknown",
-                            };
-                            let state = match message.state {
-                                crate::ipc::HookKeyState::Down => "DOWN",
-                                crate::ipc::HookKeyState::Up => "UP",
-                            };
-                            let labels_for_emit = message.labels.clone();
-                            let primary_label = labels_for_emit
-                                .get(0)
-                                .cloned()
-                                .unwrap_or_else(|| String::from(""));
-
-                            // Emit raw input stream only when there are subscribers
-                            let app_state = app_handle.state::<AppState>();
-                            if app_state.raw_input_subscriber_count() > 0 {
-                                let raw_payload = json!({
-                                    "label": primary_label,
-                                    "labels": labels_for_emit.clone(),
-                                    "state": state,
-                                    "device": device_str,
-                                });
-                                
-                                // Emit to main window first, then fallback to app-wide emit
-                                if let Some(main) = app_handle.get_webview_window("main") {
-                                    let _ = main.emit("input:raw", &raw_payload);
-                                }
-                                // Also emit to overlay for plugins running there
-                                if let Some(overlay) = app_handle.get_webview_window(OVERLAY_LABEL) {
-                                    let _ = overlay.emit("input:raw", &raw_payload);
-                                }
-                            }
-
-                            let Some(key_label) =
-                                keyboard.match_candidate(message.labels.iter().map(|s| s.as_str()))
-                            else {
-                                continue;
-                            };
-                            let mode = keyboard.current_mode();
-                            if state == "DOWN" {
-                                if app_state.register_key_down(&mode, &key_label) {
-                                    if let Some(count) = app_state.increment_key_counter(&mode, &key_label) {
-                                        log::trace!(
-                                            "[IPC] emit keys:counter: mode={}, key={}, count={}",
-                                            mode, key_label, count
-                                        );
-                                        if let Err(err) = app_handle.emit(
-                                            "keys:counter",
-                                            &json!({
-                                                "mode": mode.clone(),
-                                                "key": key_label.clone(),
-                                                "count": count,
-                                            }),
-                                        ) {
-                                            error!("failed to emit keys:counter event: {err}");
-                                        }
-                                    }
-                                }
-                            } else {
-                                app_state.register_key_up(&mode, &key_label);
-                            }
-                            let payload = json!({ "key": key_label, "state": state, "mode": mode });
-
-                            let mut emitted = false;
-                            if let Some(overlay) = overlay_window.as_ref() {
-                                match overlay.emit("keys:state", &payload) {
-                                    Ok(_) => emitted = true,
-                                    Err(err) => {
-                                        error!("failed to emit keys:state to overlay: {err}");
-                                        overlay_window = None;
-                                    }
-                                }
-                            }
-                            if !emitted {
-                                if overlay_window.is_none() {
-                                    overlay_window = app_handle.get_webview_window(OVERLAY_LABEL);
-                                    if let Some(overlay) = overlay_window.as_ref() {
-                                        if overlay.emit("keys:state", &payload).is_ok() {
-                                            emitted = true;
-                                        } else {
-                                            overlay_window = None;
-                                        }
-                                    }
-                                }
-                                if !emitted {
-                                    if let Err(err) = app_handle.emit("keys:state", &payload) {
-                                        error!("failed to emit keys:state (fallback): {err}");
-                                    }
-                                }
-                            }
+                // The daemon confirms our protocol version on the event link.
+                if let Err(err) = protocol::expect_hello_ack(&mut reader) {
+                    if running_reader.load(Ordering::SeqCst) {
+                        // A version mismatch will not go away by restarting.
+                        let permanent = matches!(err, protocol::ProtocolError::VersionMismatch { .. });
+                        app_handle.state::<AppState>().on_daemon_exit(
+                            &app_handle,
+                            generation,
+                            format!("handshake failed: {err}"),
+                            permanent,
+                        );
+                    }
+                    return;
+                }
+                let _ = app_handle.emit("daemon:status", &supervisor.mark_running());
 
-                            if emitted {
-                                keys_state_emit_count += 1;
-                                if keys_state_emit_count % 500 == 0 {
-                                    log::debug!(
-                                        "[AppState] emitted keys:state {} times (last key={}, state={})",
-                                        keys_state_emit_count,
-                                        key_label,
-                                        state
-                                    );
-                                }
-                            }
+                let mut exit_reason = String::new();
+                while running_reader.load(Ordering::SeqCst) {
+                    let mut message = match protocol::read_frame(&mut reader) {
+                        Ok(Some(Frame::Key(message))) => message,
+                        Ok(Some(Frame::Command(command))) => {
+                            handle_daemon_command(&app_handle, &reader_control, command);
+                            continue;
+                        }
+                        Ok(Some(other)) => {
+                            warn!("unexpected frame from keyboard daemon: {other:?}");
+                            continue;
+                        }
+                        Ok(None) => {
+                            exit_reason = "daemon closed its output".to_string();
+                            break;
                         }
                         Err(err) => {
-                            if err.kind() == std::io::ErrorKind::Interrupted
-                                || err.kind() == std::io::ErrorKind::WouldBlock
-                            {
-                                continue;
-                            }
+                            exit_reason = format!("link error: {err}");
                             break;
                         }
+                    };
+                    if message.labels.is_empty() {
+                        continue;
+                    }
+                    // Events without a capture time fall back to the receive time.
+                    message.timestamp_us.get_or_insert_with(monotonic_us);
+
+                    if message.labels.first().is_some_and(|label| is_wheel_label(label)) {
+                        // 휠 노치는 Down만 오므로 Up은 펄스 스케줄러가 만든다
+                        if message.state == crate::ipc::HookKeyState::Up {
+                            continue;
+                        }
+                        let Some(pulses) = wheel_pulses.as_ref() else {
+                            continue;
+                        };
+                        let input = app_handle.state::<AppState>().input_settings();
+                        let duration = Duration::from_millis(u64::from(input.wheel_pulse_ms));
+                        match pulses.press(&message, duration, input.wheel_coalesce) {
+                            WheelPress::Start => dispatcher.dispatch(&message),
+                            WheelPress::Extend => dispatcher.dispatch_repeat(&message),
+                            WheelPress::Restart => {
+                                let mut release = message.clone();
+                                release.state = crate::ipc::HookKeyState::Up;
+                                dispatcher.dispatch(&release);
+                                dispatcher.dispatch(&message);
+                            }
+                        }
+                        continue;
                     }
+
+                    dispatcher.dispatch(&message);
+                }
+
+                // Still meant to be running: the daemon died or dropped the link.
+                if running_reader.load(Ordering::SeqCst) {
+                    app_handle.state::<AppState>().on_daemon_exit(
+                        &app_handle,
+                        generation,
+                        exit_reason,
+                        false,
+                    );
                 }
             })
             .map_err(|err| anyhow!("failed to spawn keyboard daemon reader: {err}"))?;
@@ -647,6 +716,7 @@ impl AppState {
                         match line {
                             Ok(text) if !text.trim().is_empty() => {
                                 warn!("keyboard-daemon stderr: {text}");
+                                stderr_supervisor.record_stderr(text);
                             }
                             Ok(_) => {}
                             Err(err) => {
@@ -671,6 +741,7 @@ impl AppState {
             reader_handle: Some(reader_handle),
             stderr_handle,
             child: Some(child),
+            control,
         });
         Ok(())
     }
@@ -918,17 +989,341 @@ impl AppState {
             }
         }
 
-        if diff.changed.shortcuts.is_some() {
-            // Restart keyboard daemon to apply updated global hotkeys.
-            if let Some(task) = self.keyboard_task.write().take() {
-                drop(task);
+        if let Some(shortcuts) = diff.changed.shortcuts.as_ref() {
+            // Push updated global hotkeys over the control link; restart only if it is gone.
+            let message = ControlMessage::SetHotkeys {
+                shortcuts: Box::new(shortcuts.clone()),
+            };
+            if let Err(err) = self.send_daemon_control(&message) {
+                warn!("hotkey update not delivered, restarting keyboard daemon: {err}");
+                self.stop_keyboard_hook();
+                self.start_keyboard_hook(app.clone())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stops the keyboard daemon and cancels any pending automatic restart.
+    fn stop_keyboard_hook(&self) {
+        let task = {
+            let mut task_guard = self.keyboard_task.write();
+            self.daemon_generation.fetch_add(1, Ordering::SeqCst);
+            task_guard.take()
+        };
+        // Dropping joins the reader thread, which may itself be waiting on `keyboard_task`,
+        // so the lock is released first.
+        drop(task);
+    }
+
+    /// Restarts the keyboard daemon on request, e.g. after the supervisor gave up.
+    pub fn restart_keyboard_hook(&self, app: &AppHandle) -> Result<()> {
+        self.daemon_supervisor.reset_backoff();
+        self.stop_keyboard_hook();
+        self.start_keyboard_hook(app.clone())
+    }
+
+    pub fn replay_source(&self) -> Option<ReplaySource> {
+        self.replay_source.read().clone()
+    }
+
+    /// Switches the daemon between hardware input and a replay file, restarting it.
+    pub fn set_replay_source(&self, app: &AppHandle, source: Option<ReplaySource>) -> Result<()> {
+        let source = source.map(|source| ReplaySource::new(source.path, source.speed, source.looped));
+        if let Some(source) = source.as_ref() {
+            if !source.path.is_file() {
+                return Err(anyhow!("replay file not found: {}", source.path.display()));
             }
-            self.start_keyboard_hook(app.clone())?;
         }
+        *self.replay_source.write() = source;
+        self.restart_keyboard_hook(app)
+    }
+
+    pub fn daemon_health(&self) -> DaemonHealth {
+        self.daemon_supervisor.health()
+    }
+
+    /// Records an unexpected daemon exit and schedules a restart with backoff.
+    /// Ignored if the daemon of `generation` has since been stopped or replaced.
+    fn on_daemon_exit(&self, app: &AppHandle, generation: u64, reason: String, permanent: bool) {
+        if self.daemon_generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        error!("keyboard daemon stopped: {reason}");
+        self.clear_active_keys();
+        let (action, payload) = self.daemon_supervisor.record_crash(reason, permanent);
+        let _ = app.emit("daemon:status", &payload);
+        let CrashAction::Restart(delay) = action else {
+            return;
+        };
 
+        let app = app.clone();
+        let spawned = thread::Builder::new()
+            .name("keyboard-daemon-supervisor".into())
+            .spawn(move || {
+                thread::sleep(delay);
+                let state = app.state::<AppState>();
+                if state.daemon_generation.load(Ordering::SeqCst) != generation {
+                    return;
+                }
+                state.stop_keyboard_hook();
+                if let Err(err) = state.start_keyboard_hook(app.clone()) {
+                    let current = state.daemon_generation.load(Ordering::SeqCst);
+                    state.on_daemon_exit(&app, current, format!("restart failed: {err}"), false);
+                }
+            });
+        if let Err(err) = spawned {
+            error!("failed to schedule keyboard daemon restart: {err}");
+        }
+    }
+
+    /// Sends a control message to the running keyboard daemon.
+    pub fn send_daemon_control(&self, message: &ControlMessage) -> Result<()> {
+        let guard = self.keyboard_task.read();
+        let task = guard
+            .as_ref()
+            .ok_or_else(|| anyhow!("keyboard daemon is not running"))?;
+        let mut control = task.control.lock();
+        protocol::write_frame(&mut *control, &Frame::Control(message.clone()))
+            .context("failed to write to keyboard daemon")?;
         Ok(())
     }
 
+    pub fn capture_paused(&self) -> bool {
+        self.capture_paused.load(Ordering::SeqCst)
+    }
+
+    /// Pauses or resumes key event forwarding in the daemon. Global hotkeys keep working.
+    pub fn set_capture_paused(&self, app: &AppHandle, paused: bool) -> Result<()> {
+        let message = self.record_capture_paused(app, paused);
+        self.send_daemon_control(&message)
+    }
+
+    /// Stores and announces the pause state; returns the control message for the daemon.
+    fn record_capture_paused(&self, app: &AppHandle, paused: bool) -> ControlMessage {
+        self.capture_paused.store(paused, Ordering::SeqCst);
+        self.emit_capture_changed(app);
+        if paused {
+            ControlMessage::Pause
+        } else {
+            ControlMessage::Resume
+        }
+    }
+
+    pub fn capture_filters(&self) -> CaptureFilters {
+        *self.capture_filters.read()
+    }
+
+    pub fn set_capture_filters(&self, app: &AppHandle, filters: CaptureFilters) -> Result<()> {
+        *self.capture_filters.write() = filters;
+        self.emit_capture_changed(app);
+        self.send_daemon_control(&ControlMessage::SetFilters { filters })
+    }
+
+    pub fn capture_state(&self) -> CaptureState {
+        CaptureState {
+            paused: self.capture_paused(),
+            filters: self.capture_filters(),
+        }
+    }
+
+    fn emit_capture_changed(&self, app: &AppHandle) {
+        if let Err(err) = app.emit("daemon:capture-changed", self.capture_state()) {
+            error!("failed to emit daemon:capture-changed event: {err}");
+        }
+    }
+
+    pub fn ime_state(&self) -> Option<ImeState> {
+        self.ime_state.read().clone()
+    }
+
+    fn set_ime_state(&self, app: &AppHandle, state: ImeState) {
+        *self.ime_state.write() = Some(state.clone());
+        if let Err(err) = app.emit("input:ime", state) {
+            error!("failed to emit input:ime event: {err}");
+        }
+    }
+
+    /// Labels the daemon may forward, or `None` when it forwards every key: `mapped_keys_only`
+    /// is off, or raw input is currently granted (e.g. while a key is being assigned).
+    fn key_allowlist(&self) -> Option<Vec<String>> {
+        if !self.input_settings.read().mapped_keys_only
+            || self.raw_input_grants.load(Ordering::SeqCst) > 0
+        {
+            return None;
+        }
+        let mut keys: Vec<String> = self.store.snapshot().keys.into_values().flatten().collect();
+        keys.sort();
+        keys.dedup();
+        Some(keys)
+    }
+
+    fn sync_key_allowlist(&self) {
+        let message = ControlMessage::SetKeyAllowlist {
+            keys: self.key_allowlist(),
+        };
+        // Not running is fine: the allowlist is sent again when the daemon starts.
+        if let Err(err) = self.send_daemon_control(&message) {
+            log::debug!("[AppState] key allowlist not sent: {err}");
+        }
+    }
+
+    /// Replaces the key mappings used for matching and for the daemon's key allowlist.
+    pub fn update_key_mappings(&self, keys: KeyMappings) {
+        self.keyboard.update_mappings(keys);
+        self.sync_key_allowlist();
+    }
+
+    /// Lets the daemon forward every key until the matching [`AppState::revoke_raw_input`].
+    pub fn grant_raw_input(&self) -> u32 {
+        let count = self.raw_input_grants.fetch_add(1, Ordering::SeqCst) + 1;
+        if count == 1 {
+            self.sync_key_allowlist();
+        }
+        count
+    }
+
+    pub fn revoke_raw_input(&self) -> u32 {
+        let count = self
+            .raw_input_grants
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                Some(count.saturating_sub(1))
+            })
+            .map(|prev| prev.saturating_sub(1))
+            .unwrap_or(0);
+        if count == 0 {
+            self.sync_key_allowlist();
+        }
+        count
+    }
+
+    /// Round-trips a ping through the daemon and returns the elapsed time.
+    pub fn ping_daemon(&self, timeout: Duration) -> Result<Duration> {
+        let nonce = self.next_ping_nonce.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = mpsc::channel();
+        self.daemon_pings.lock().insert(nonce, tx);
+
+        let started = Instant::now();
+        let result = self
+            .send_daemon_control(&ControlMessage::Ping { nonce })
+            .and_then(|_| {
+                rx.recv_timeout(timeout)
+                    .map_err(|_| anyhow!("keyboard daemon did not answer within {timeout:?}"))
+            });
+        self.daemon_pings.lock().remove(&nonce);
+        result.map(|_| started.elapsed())
+    }
+
+    fn complete_daemon_ping(&self, nonce: u64) {
+        if let Some(waiter) = self.daemon_pings.lock().remove(&nonce) {
+            let _ = waiter.send(());
+        }
+    }
+
+    pub fn input_settings(&self) -> InputSettings {
+        self.input_settings.read().clone()
+    }
+
+    pub fn injected_input_policy(&self) -> InjectedInputPolicy {
+        self.input_settings.read().injected_input
+    }
+
+    /// Chatter counts per key since the last reset, most affected first.
+    pub fn chatter_stats(&self) -> Vec<KeyChatterStats> {
+        self.chatter.stats()
+    }
+
+    pub fn reset_chatter_stats(&self) {
+        self.chatter.reset_stats();
+    }
+
+    /// Window for grouping key events into `keys:batch`, or `None` for per-event delivery.
+    fn batch_window(&self) -> Option<Duration> {
+        let window_ms = self.input_settings.read().batch_window_ms;
+        (window_ms > 0).then(|| Duration::from_millis(u64::from(window_ms)))
+    }
+
+    pub fn latency_report(&self) -> LatencyReport {
+        self.latency.report(self.store.settings_snapshot().angle_mode)
+    }
+
+    pub fn reset_latency(&self) {
+        self.latency.reset();
+    }
+
+    /// 오버레이가 측정한 캡처 → 프레임 렌더 지연 (µs)
+    pub fn record_render_latency(&self, samples_us: &[u64]) {
+        self.latency.record_render(samples_us);
+    }
+
+    /// Emits `stats:kps` every tick while any mode's KPS or APM is changing, closes play
+    /// sessions that went idle and runs daily/weekly counter resets.
+    fn start_stats_ticker(&self, app: &AppHandle) {
+        if self.stats_ticker_running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let running = self.stats_ticker_running.clone();
+        let app_handle = app.clone();
+        let spawned = thread::Builder::new()
+            .name("stats-ticker".into())
+            .spawn(move || {
+                while running.load(Ordering::SeqCst) {
+                    thread::sleep(kps_stats::TICK_INTERVAL);
+                    let state = app_handle.state::<AppState>();
+                    let now_us = monotonic_us();
+                    if state.kps.tick(now_us) {
+                        if let Err(err) = app_handle.emit("stats:kps", state.kps_report()) {
+                            error!("failed to emit stats:kps event: {err}");
+                        }
+                    }
+                    state.end_idle_play_session(&app_handle, now_us);
+                    let schedule = state.input_settings.read().counter_reset_schedule;
+                    if state.counter_snapshots.take_scheduled_reset(schedule) {
+                        state.reset_counters_on_schedule(&app_handle);
+                    }
+                }
+            });
+        if let Err(err) = spawned {
+            self.stats_ticker_running.store(false, Ordering::SeqCst);
+            warn!("stats:kps updates, play sessions and scheduled counter resets disabled: {err}");
+        }
+    }
+
+    fn end_idle_play_session(&self, app: &AppHandle, now_us: u64) {
+        let idle_minutes = self.input_settings.read().session_idle_minutes;
+        let idle_us = u64::from(idle_minutes) * 60_000_000;
+        match self.play_sessions.end_if_idle(now_us, idle_us) {
+            Ok(Some(session)) => {
+                if let Err(err) = app.emit("session:ended", &session) {
+                    error!("failed to emit session:ended event: {err}");
+                }
+            }
+            Ok(None) => {}
+            Err(err) => error!("failed to save play session: {err:#}"),
+        }
+    }
+
+    /// 끝난 플레이 세션 (최근 순)
+    pub fn play_sessions(&self) -> Vec<PlaySession> {
+        self.play_sessions.list()
+    }
+
+    pub fn delete_play_session(&self, id: &str) -> Result<bool> {
+        self.play_sessions.delete(id)
+    }
+
+    pub fn kps_report(&self) -> KpsReport {
+        self.kps.report(monotonic_us(), self.keyboard.current_mode())
+    }
+
+    /// `mode`만 초기화하거나, `None`이면 새 통계 세션 시작
+    pub fn reset_kps(&self, app: &AppHandle, mode: Option<&str>) -> Result<KpsReport> {
+        self.kps.reset(mode);
+        let report = self.kps_report();
+        app.emit("stats:kps", &report)?;
+        Ok(report)
+    }
+
     pub fn increment_key_counter(&self, mode: &str, key: &str) -> Option<u32> {
         if !self.key_counter_enabled.load(Ordering::Relaxed) {
             return None;
@@ -937,7 +1332,67 @@ impl AppState {
         let mode_entry = counters.entry(mode.to_string()).or_default();
         let count = mode_entry.entry(key.to_string()).or_insert(0);
         *count = count.saturating_add(1);
-        Some(*count)
+        let count = *count;
+        drop(counters);
+        self.counter_history.record(mode, key);
+        if let Some(flusher) = self.counter_flusher.as_ref() {
+            flusher.mark_dirty();
+        }
+        Some(count)
+    }
+
+    /// Selects a key mode and announces it; falls back to the current mode if `mode` is unknown.
+    pub fn select_key_mode(&self, app: &AppHandle, mode: String) -> Result<(bool, String)> {
+        let success = self.keyboard.set_mode(mode.clone());
+        let effective = if success {
+            mode
+        } else {
+            self.keyboard.current_mode()
+        };
+        self.store.set_selected_key_type(effective.clone())?;
+        app.emit("keys:mode-changed", &json!({ "mode": &effective }))?;
+        Ok((success, effective))
+    }
+
+    /// Moves to the next key mode: built-in modes first, then custom tabs in creation order.
+    pub fn cycle_key_mode(&self, app: &AppHandle) -> Result<String> {
+        let snapshot = self.store.snapshot();
+        let modes: Vec<String> = BUILTIN_KEY_MODES
+            .iter()
+            .map(|mode| mode.to_string())
+            .chain(snapshot.custom_tabs.iter().map(|tab| tab.id.clone()))
+            .filter(|mode| snapshot.keys.contains_key(mode))
+            .collect();
+        if modes.is_empty() {
+            return Ok(self.keyboard.current_mode());
+        }
+        let current = self.keyboard.current_mode();
+        let next = modes
+            .iter()
+            .position(|mode| *mode == current)
+            .map_or(0, |index| (index + 1) % modes.len());
+        let (_, mode) = self.select_key_mode(app, modes[next].clone())?;
+        Ok(mode)
+    }
+
+    /// Resets the counters of the active key mode, persists them and broadcasts the result.
+    /// Runs on the daemon reader thread (ResetModeCounters hotkey), so the store write is left
+    /// to the flush worker that the reset already scheduled.
+    pub fn reset_current_mode_counters(&self, app: &AppHandle) -> Result<()> {
+        self.reset_mode_counters(&self.keyboard.current_mode());
+        app.emit("keys:counters", &self.snapshot_key_counters())?;
+        Ok(())
+    }
+
+    /// `from`..=`to` (로컬 날짜) 기간의 카운터 기록
+    pub fn counter_history(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        granularity: HistoryGranularity,
+        mode: Option<&str>,
+    ) -> CounterHistoryRange {
+        self.counter_history.query(from, to, granularity, mode)
     }
 
     pub fn snapshot_key_counters(&self) -> KeyCounters {
@@ -945,13 +1400,8 @@ impl AppState {
     }
 
     pub fn reset_key_counters(&self) -> KeyCounters {
-        let mut counters = self.key_counters.write();
-        for mode_entry in counters.values_mut() {
-            for value in mode_entry.values_mut() {
-                *value = 0;
-            }
-        }
-        counters.clone()
+        self.reset_counters_in_scope(SnapshotReason::Reset, None, None);
+        self.snapshot_key_counters()
     }
 
     pub fn replace_key_counters(
@@ -968,35 +1418,120 @@ impl AppState {
     }
 
     pub fn reset_mode_counters(&self, mode: &str) {
+        self.reset_counters_in_scope(SnapshotReason::Reset, Some(mode), None);
+    }
+
+    pub fn reset_single_key_counter(&self, mode: &str, key: &str) {
+        self.reset_counters_in_scope(SnapshotReason::Reset, Some(mode), Some(key));
+    }
+
+    /// Zeroes every counter in the scope (`None` = all modes / all keys), archiving the previous
+    /// values first so the reset can be undone.
+    fn reset_counters_in_scope(
+        &self,
+        reason: SnapshotReason,
+        mode: Option<&str>,
+        key: Option<&str>,
+    ) {
         let mut counters = self.key_counters.write();
-        if let Some(entry) = counters.get_mut(mode) {
-            for value in entry.values_mut() {
-                *value = 0;
+        let previous = scoped_counters(&counters, mode, key);
+        for (name, keys) in &previous {
+            if let Some(entry) = counters.get_mut(name) {
+                for label in keys.keys() {
+                    entry.insert(label.clone(), 0);
+                }
             }
         }
+        drop(counters);
+        self.counter_snapshots.archive(previous, reason, mode, key);
+        self.save_counter_snapshots_later();
     }
 
-    pub fn reset_single_key_counter(&self, mode: &str, key: &str) {
-        let mut counters = self.key_counters.write();
-        if let Some(entry) = counters.get_mut(mode) {
-            if let Some(value) = entry.get_mut(key) {
-                *value = 0;
+    /// Leaves the counter and snapshot file writes to the flush worker (or shutdown): resets can
+    /// come from the ResetModeCounters hotkey on the daemon reader thread.
+    fn save_counter_snapshots_later(&self) {
+        if let Some(flusher) = self.counter_flusher.as_ref() {
+            flusher.mark_dirty();
+        }
+    }
+
+    fn reset_counters_on_schedule(&self, app: &AppHandle) {
+        self.reset_counters_in_scope(SnapshotReason::Scheduled, None, None);
+        match self.persist_key_counters() {
+            Ok(snapshot) => {
+                if let Err(err) = app.emit("keys:counters", &snapshot) {
+                    error!("failed to emit keys:counters event: {err}");
+                }
             }
+            Err(err) => error!("failed to persist scheduled counter reset: {err:#}"),
         }
     }
 
-    pub fn register_key_down(&self, mode: &str, key: &str) -> bool {
+    /// 초기화 전 카운터 스냅샷 (최근 순)
+    pub fn counter_snapshots(&self) -> Vec<CounterSnapshot> {
+        self.counter_snapshots.list()
+    }
+
+    pub fn delete_counter_snapshot(&self, id: &str) -> Result<bool> {
+        self.counter_snapshots.delete(id)
+    }
+
+    /// 스냅샷 범위의 카운터를 스냅샷 값으로 되돌리고 저장·전파. 지금 값은 새 스냅샷으로 남김
+    /// (삭제된 모드·키는 건너뜀, 스냅샷이 없으면 `None`)
+    pub fn restore_counter_snapshot(
+        &self,
+        app: &AppHandle,
+        id: &str,
+    ) -> Result<Option<KeyCounters>> {
+        let Some(snapshot) = self.counter_snapshots.get(id) else {
+            return Ok(None);
+        };
+        let mode = snapshot.mode.as_deref();
+        let key = snapshot.key.as_deref();
+        let mut counters = self.key_counters.write();
+        let previous = scoped_counters(&counters, mode, key);
+        restore_counters(&mut counters, &snapshot);
+        drop(counters);
+        self.counter_snapshots.archive(previous, SnapshotReason::Restore, mode, key);
+        self.save_counter_snapshots_later();
+
+        let restored = self.persist_key_counters()?;
+        app.emit("keys:counters", &restored)?;
+        Ok(Some(restored))
+    }
+
+    pub fn register_key_down(&self, mode: &str, key: &str, timestamp_us: u64) -> bool {
         let mut guard = self.active_keys.write();
-        guard.insert(Self::compose_active_key(mode, key))
+        let pressed = guard.insert(Self::compose_active_key(mode, key));
+        drop(guard);
+        // 휠 펄스는 실제 누름이 아니므로 타이밍 분포에서 제외
+        if pressed && !is_wheel_label(key) {
+            self.key_timing.press(mode, key, timestamp_us);
+        }
+        pressed
     }
 
-    pub fn register_key_up(&self, mode: &str, key: &str) {
+    pub fn register_key_up(&self, mode: &str, key: &str, timestamp_us: u64) {
         let mut guard = self.active_keys.write();
-        guard.remove(&Self::compose_active_key(mode, key));
+        let released = guard.remove(&Self::compose_active_key(mode, key));
+        drop(guard);
+        if released && !is_wheel_label(key) {
+            self.key_timing.release(mode, key, timestamp_us);
+        }
     }
 
     pub fn clear_active_keys(&self) {
         self.active_keys.write().clear();
+        self.key_timing.clear_pending();
+    }
+
+    pub fn key_timing_report(&self) -> KeyTimingReport {
+        self.key_timing.report()
+    }
+
+    /// `mode`만 초기화하거나, `None`이면 새 분석 세션 시작
+    pub fn reset_key_timing(&self, mode: Option<&str>) {
+        self.key_timing.reset(mode);
     }
 
     pub fn persist_key_counters(&self) -> Result<KeyCounters> {
@@ -1415,11 +1950,499 @@ fn persist_overlay_bounds(window: &WebviewWindow, store: &Arc<AppStore>) -> Resu
     Ok(())
 }
 
+/// Applies a global hotkey command received from the keyboard daemon.
+/// Runs on the daemon reader thread. Anything that talks back to the daemon must use `control`:
+/// `stop_keyboard_hook` joins this thread, so waiting on `AppState::keyboard_task` here could
+/// deadlock.
+fn handle_daemon_command(
+    app_handle: &AppHandle,
+    control: &Mutex<ChildStdin>,
+    command: crate::ipc::DaemonCommand,
+) {
+    match command {
+        crate::ipc::DaemonCommand::ToggleOverlay => {
+            log::info!("[AppState] received ToggleOverlay command from daemon");
+            let app_state = app_handle.state::<AppState>();
+            let is_visible = *app_state.overlay_visible.read();
+            if let Err(err) = app_state.set_overlay_visibility(app_handle, !is_visible) {
+                log::error!("failed to toggle overlay visibility: {err}");
+            }
+        }
+        crate::ipc::DaemonCommand::ToggleOverlayLock => {
+            log::info!("[AppState] received ToggleOverlayLock command from daemon");
+            let app_state = app_handle.state::<AppState>();
+            let current = app_state.store.snapshot().overlay_locked;
+            match app_state.settings.apply_patch(crate::models::SettingsPatchInput {
+                overlay_locked: Some(!current),
+                ..Default::default()
+            }) {
+                Ok(diff) => {
+                    if let Err(err) = app_state.emit_settings_changed(&diff, app_handle) {
+                        log::error!("failed to apply overlay lock toggle: {err}");
+                    }
+                }
+                Err(err) => log::error!("failed to toggle overlay lock: {err}"),
+            }
+        }
+        crate::ipc::DaemonCommand::ToggleAlwaysOnTop => {
+            log::info!("[AppState] received ToggleAlwaysOnTop command from daemon");
+            let app_state = app_handle.state::<AppState>();
+            let current = app_state.store.snapshot().always_on_top;
+            match app_state.settings.apply_patch(crate::models::SettingsPatchInput {
+                always_on_top: Some(!current),
+                ..Default::default()
+            }) {
+                Ok(diff) => {
+                    if let Err(err) = app_state.emit_settings_changed(&diff, app_handle) {
+                        log::error!("failed to apply always-on-top toggle: {err}");
+                    }
+                }
+                Err(err) => log::error!("failed to toggle always-on-top: {err}"),
+            }
+        }
+        crate::ipc::DaemonCommand::CycleKeyMode => {
+            let app_state = app_handle.state::<AppState>();
+            let shortcuts = app_state.store.settings_snapshot().shortcuts;
+            if main_window_handles(app_handle, &shortcuts.switch_key_mode) {
+                return;
+            }
+            if let Err(err) = app_state.cycle_key_mode(app_handle) {
+                log::error!("failed to cycle key mode: {err}");
+            }
+        }
+        crate::ipc::DaemonCommand::SetKeyMode { mode } => {
+            if let Err(err) = app_handle.state::<AppState>().select_key_mode(app_handle, mode) {
+                log::error!("failed to switch key mode: {err}");
+            }
+        }
+        crate::ipc::DaemonCommand::ResetModeCounters => {
+            log::info!("[AppState] received ResetModeCounters command from daemon");
+            if let Err(err) = app_handle
+                .state::<AppState>()
+                .reset_current_mode_counters(app_handle)
+            {
+                log::error!("failed to reset mode counters: {err}");
+            }
+        }
+        crate::ipc::DaemonCommand::ToggleKeyCounter => {
+            let app_state = app_handle.state::<AppState>();
+            let current = app_state.store.snapshot().key_counter_enabled;
+            match app_state.settings.apply_patch(crate::models::SettingsPatchInput {
+                key_counter_enabled: Some(!current),
+                ..Default::default()
+            }) {
+                Ok(diff) => {
+                    if let Err(err) = app_state.emit_settings_changed(&diff, app_handle) {
+                        log::error!("failed to apply key counter toggle: {err}");
+                    }
+                }
+                Err(err) => log::error!("failed to toggle key counter: {err}"),
+            }
+        }
+        crate::ipc::DaemonCommand::ToggleCapturePause => {
+            let app_state = app_handle.state::<AppState>();
+            let paused = !app_state.capture_paused();
+            log::info!("[AppState] capture pause toggled by hotkey: paused={paused}");
+            let message = app_state.record_capture_paused(app_handle, paused);
+            if let Err(err) = protocol::write_frame(&mut *control.lock(), &Frame::Control(message))
+            {
+                log::error!("failed to toggle capture pause: {err}");
+            }
+        }
+        crate::ipc::DaemonCommand::ToggleSettingsPanel => {
+            emit_canvas_shortcut(app_handle, "toggleSettingsPanel", |shortcuts| {
+                &shortcuts.toggle_settings_panel
+            })
+        }
+        crate::ipc::DaemonCommand::ZoomIn => {
+            emit_canvas_shortcut(app_handle, "zoomIn", |shortcuts| &shortcuts.zoom_in)
+        }
+        crate::ipc::DaemonCommand::ZoomOut => {
+            emit_canvas_shortcut(app_handle, "zoomOut", |shortcuts| &shortcuts.zoom_out)
+        }
+        crate::ipc::DaemonCommand::ResetZoom => {
+            emit_canvas_shortcut(app_handle, "resetZoom", |shortcuts| &shortcuts.reset_zoom)
+        }
+        crate::ipc::DaemonCommand::ImeChanged { state } => {
+            app_handle
+                .state::<AppState>()
+                .set_ime_state(app_handle, state);
+        }
+        crate::ipc::DaemonCommand::Pong { nonce } => {
+            app_handle.state::<AppState>().complete_daemon_ping(nonce);
+        }
+    }
+}
+
+/// Whether the focused main window already handles `binding` itself. The renderer only
+/// matches single-key shortcuts, so chords and sequences always come from the daemon.
+fn main_window_handles(app_handle: &AppHandle, binding: &ShortcutBinding) -> bool {
+    binding.is_simple()
+        && app_handle
+            .get_webview_window("main")
+            .and_then(|main| main.is_focused().ok())
+            .unwrap_or(false)
+}
+
+/// Forwards a global canvas shortcut to the main window, unless the renderer already
+/// handled the same key press.
+fn emit_canvas_shortcut(
+    app_handle: &AppHandle,
+    action: &str,
+    binding: impl FnOnce(&ShortcutsState) -> &ShortcutBinding,
+) {
+    let shortcuts = app_handle
+        .state::<AppState>()
+        .store
+        .settings_snapshot()
+        .shortcuts;
+    if main_window_handles(app_handle, binding(&shortcuts)) {
+        return;
+    }
+    let Some(main) = app_handle.get_webview_window("main") else {
+        return;
+    };
+    if let Err(err) = main.emit_to("main", "shortcuts:triggered", &json!({ "action": action })) {
+        log::error!("failed to forward {action} shortcut: {err}");
+    }
+}
+
+fn emit_key_hold(app_handle: &AppHandle, hold: &KeyHold) {
+    let now = monotonic_us();
+    let payload = json!({
+        "mode": hold.mode,
+        "key": hold.key,
+        "heldMs": now.saturating_sub(hold.pressed_at_us) / 1000,
+        "timestampUs": now,
+        "wallClockOffsetUs": wall_clock_offset_us(),
+    });
+    if let Err(err) = app_handle.emit("keys:hold", &payload) {
+        error!("failed to emit keys:hold event: {err}");
+    }
+}
+
+/// Sends one frame's worth of key events as a single `keys:batch`.
+fn emit_key_batch(app_handle: &AppHandle, entries: Vec<BatchEntry>) {
+    if let Err(err) = app_handle.emit("keys:batch", &json!({ "entries": &entries })) {
+        error!("failed to emit keys:batch event: {err}");
+        return;
+    }
+    let now = monotonic_us();
+    let latency = &app_handle.state::<AppState>().latency;
+    for entry in &entries {
+        if entry.event == "keys:state" && entry.payload["state"] == "DOWN" {
+            if let Some(timestamp_us) = entry.payload["timestampUs"].as_u64() {
+                latency.record_emit(now.saturating_sub(timestamp_us));
+            }
+        }
+    }
+}
+
+/// Turns daemon key messages into `input:raw`, `keys:counter` and `keys:state` events.
+/// Each thread that emits key events owns its own instance (it caches the overlay window).
+struct KeyEventDispatcher {
+    app_handle: AppHandle,
+    keyboard: KeyboardManager,
+    overlay_window: Option<WebviewWindow>,
+    keys_state_emit_count: u64,
+    /// Long-press / double-tap detection; only the reader thread's dispatcher has one.
+    gestures: Option<KeyGestures>,
+    /// Shared by all dispatchers of a daemon run so one batch keeps events in order.
+    batcher: Option<Arc<EventBatcher>>,
+}
+
+impl KeyEventDispatcher {
+    fn new(app_handle: AppHandle, keyboard: KeyboardManager) -> Self {
+        let overlay_window = app_handle.get_webview_window(OVERLAY_LABEL);
+        Self {
+            app_handle,
+            keyboard,
+            overlay_window,
+            keys_state_emit_count: 0,
+            gestures: None,
+            batcher: None,
+        }
+    }
+
+    fn with_gestures(mut self, gestures: Option<KeyGestures>) -> Self {
+        self.gestures = gestures;
+        self
+    }
+
+    fn with_batcher(mut self, batcher: Option<Arc<EventBatcher>>) -> Self {
+        self.batcher = batcher;
+        self
+    }
+
+    /// The batcher and its window when batched delivery is on; `None` emits each event.
+    /// Right after batching is turned off, events keep going through the batcher until its last
+    /// batch is out, so a direct emit never overtakes an older batched one.
+    fn batching(&self) -> Option<(&EventBatcher, Duration)> {
+        let batcher = self.batcher.as_deref()?;
+        match self.app_handle.state::<AppState>().batch_window() {
+            Some(window) => Some((batcher, window)),
+            None => batcher.is_busy().then_some((batcher, Duration::ZERO)),
+        }
+    }
+
+    fn dispatch(&mut self, message: &crate::ipc::HookMessage) {
+        self.emit_raw(message);
+
+        let Some(key_label) = self
+            .keyboard
+            .match_candidate(
+                message
+                    .code
+                    .iter()
+                    .chain(&message.labels)
+                    .map(|s| s.as_str()),
+                message.device_id.as_deref(),
+            )
+        else {
+            return;
+        };
+        let mode = self.keyboard.current_mode();
+        let timestamp_us = message.timestamp_us.unwrap_or_else(monotonic_us);
+        let app_state = self.app_handle.state::<AppState>();
+        let policy = if message.injected {
+            app_state.injected_input_policy()
+        } else {
+            InjectedInputPolicy::Count
+        };
+        if policy == InjectedInputPolicy::Ignore {
+            return;
+        }
+        // Wheel presses are synthesized pulses, not switch contacts or holds.
+        let is_wheel = is_wheel_label(&key_label);
+        if !is_wheel
+            && !app_state
+                .chatter
+                .accept(&key_label, message.state, timestamp_us)
+        {
+            return;
+        }
+        let state = match message.state {
+            crate::ipc::HookKeyState::Down => {
+                if app_state.register_key_down(&mode, &key_label, timestamp_us) {
+                    if policy == InjectedInputPolicy::Count {
+                        self.emit_counter(&mode, &key_label, timestamp_us);
+                    }
+                    if !is_wheel {
+                        self.detect_gestures(&mode, &key_label, timestamp_us);
+                    }
+                }
+                "DOWN"
+            }
+            crate::ipc::HookKeyState::Up => {
+                app_state.register_key_up(&mode, &key_label, timestamp_us);
+                if let Some(gestures) = self.gestures.as_ref() {
+                    gestures.release(&mode, &key_label);
+                }
+                "UP"
+            }
+        };
+        self.emit_state(&mode, &key_label, state, timestamp_us);
+    }
+
+    /// Counts a press of a key that is already shown as held, without a new `keys:state`.
+    fn dispatch_repeat(&mut self, message: &crate::ipc::HookMessage) {
+        self.emit_raw(message);
+
+        let Some(key_label) = self
+            .keyboard
+            .match_candidate(
+                message
+                    .code
+                    .iter()
+                    .chain(&message.labels)
+                    .map(|s| s.as_str()),
+                message.device_id.as_deref(),
+            )
+        else {
+            return;
+        };
+        if message.injected
+            && self.app_handle.state::<AppState>().injected_input_policy()
+                != InjectedInputPolicy::Count
+        {
+            return;
+        }
+        let mode = self.keyboard.current_mode();
+        let timestamp_us = message.timestamp_us.unwrap_or_else(monotonic_us);
+        self.emit_counter(&mode, &key_label, timestamp_us);
+    }
+
+    /// Arms `keys:hold` for a fresh press and emits `keys:double-tap` for a quick second one.
+    fn detect_gestures(&self, mode: &str, key_label: &str, timestamp_us: u64) {
+        let Some(gestures) = self.gestures.as_ref() else {
+            return;
+        };
+        let input = self.app_handle.state::<AppState>().input_settings();
+        let Some(interval_us) = gestures.press(
+            mode,
+            key_label,
+            timestamp_us,
+            Duration::from_millis(u64::from(input.hold_ms)),
+            Duration::from_millis(u64::from(input.double_tap_ms)),
+        ) else {
+            return;
+        };
+        if let Err(err) = self.app_handle.emit(
+            "keys:double-tap",
+            &json!({
+                "mode": mode,
+                "key": key_label,
+                "intervalMs": interval_us / 1000,
+                "timestampUs": timestamp_us,
+                "wallClockOffsetUs": wall_clock_offset_us(),
+            }),
+        ) {
+            error!("failed to emit keys:double-tap event: {err}");
+        }
+    }
+
+    fn emit_raw(&self, message: &crate::ipc::HookMessage) {
+        // Emit raw input stream only when there are subscribers
+        let app_state = self.app_handle.state::<AppState>();
+        if app_state.raw_input_subscriber_count() == 0 {
+            return;
+        }
+
+        let device_str = match message.device {
+            crate::ipc::InputDeviceKind::Keyboard => "keyboard",
+            crate::ipc::InputDeviceKind::Mouse => "mouse",
+            crate::ipc::InputDeviceKind::Gamepad => "gamepad",
+            crate::ipc::InputDeviceKind::Unknown => "unknown",
+        };
+        let state = match message.state {
+            crate::ipc::HookKeyState::Down => "DOWN",
+            crate::ipc::HookKeyState::Up => "UP",
+        };
+        let primary_label = message.labels.first().cloned().unwrap_or_default();
+        let raw_payload = json!({
+            "label": primary_label,
+            "labels": message.labels.clone(),
+            "code": message.code,
+            "state": state,
+            "device": device_str,
+            "deviceId": message.device_id,
+            "deviceName": message.device_name,
+            "injected": message.injected,
+            "repeat": message.repeat,
+            "timestampUs": message.timestamp_us.unwrap_or_else(monotonic_us),
+            "wallClockOffsetUs": wall_clock_offset_us(),
+        });
+
+        if let Some((batcher, window)) = self.batching() {
+            batcher.push("input:raw", raw_payload, window);
+            return;
+        }
+
+        // Emit to main window first, then fallback to app-wide emit
+        if let Some(main) = self.app_handle.get_webview_window("main") {
+            let _ = main.emit("input:raw", &raw_payload);
+        }
+        // Also emit to overlay for plugins running there
+        if let Some(overlay) = self.app_handle.get_webview_window(OVERLAY_LABEL) {
+            let _ = overlay.emit("input:raw", &raw_payload);
+        }
+    }
+
+    fn emit_counter(&self, mode: &str, key_label: &str, timestamp_us: u64) {
+        let app_state = self.app_handle.state::<AppState>();
+        // KPS and play sessions follow real presses even when the on-screen counter is off.
+        app_state.kps.record_press(mode, timestamp_us);
+        app_state.play_sessions.press(mode, key_label, timestamp_us);
+        if let Some(count) = app_state.increment_key_counter(mode, key_label) {
+            log::trace!(
+                "[IPC] emit keys:counter: mode={}, key={}, count={}",
+                mode, key_label, count
+            );
+            let payload = json!({
+                "mode": mode,
+                "key": key_label,
+                "count": count,
+                "timestampUs": timestamp_us,
+                "wallClockOffsetUs": wall_clock_offset_us(),
+            });
+            if let Some((batcher, window)) = self.batching() {
+                batcher.push("keys:counter", payload, window);
+            } else if let Err(err) = self.app_handle.emit("keys:counter", &payload) {
+                error!("failed to emit keys:counter event: {err}");
+            }
+        }
+    }
+
+    fn emit_state(&mut self, mode: &str, key_label: &str, state: &str, timestamp_us: u64) {
+        let payload = json!({
+            "key": key_label,
+            "state": state,
+            "mode": mode,
+            "timestampUs": timestamp_us,
+            "wallClockOffsetUs": wall_clock_offset_us(),
+        });
+        if let Some((batcher, window)) = self.batching() {
+            batcher.push("keys:state", payload, window);
+            return;
+        }
+
+        let mut emitted = false;
+        if let Some(overlay) = self.overlay_window.as_ref() {
+            match overlay.emit("keys:state", &payload) {
+                Ok(_) => emitted = true,
+                Err(err) => {
+                    error!("failed to emit keys:state to overlay: {err}");
+                    self.overlay_window = None;
+                }
+            }
+        }
+        if !emitted {
+            if self.overlay_window.is_none() {
+                self.overlay_window = self.app_handle.get_webview_window(OVERLAY_LABEL);
+                if let Some(overlay) = self.overlay_window.as_ref() {
+                    if overlay.emit("keys:state", &payload).is_ok() {
+                        emitted = true;
+                    } else {
+                        self.overlay_window = None;
+                    }
+                }
+            }
+            if !emitted {
+                if let Err(err) = self.app_handle.emit("keys:state", &payload) {
+                    error!("failed to emit keys:state (fallback): {err}");
+                }
+            }
+        }
+
+        if emitted {
+            // Presses only: a wheel's synthesized release is not a captured input.
+            if state == "DOWN" {
+                let latency_us = monotonic_us().saturating_sub(timestamp_us);
+                self.app_handle
+                    .state::<AppState>()
+                    .latency
+                    .record_emit(latency_us);
+            }
+            self.keys_state_emit_count += 1;
+            if self.keys_state_emit_count % 500 == 0 {
+                log::debug!(
+                    "[AppState] emitted keys:state {} times (last key={}, state={})",
+                    self.keys_state_emit_count,
+                    key_label,
+                    state
+                );
+            }
+        }
+    }
+}
+
 struct KeyboardDaemonTask {
     running: Arc<AtomicBool>,
     reader_handle: Option<JoinHandle<()>>,
     stderr_handle: Option<JoinHandle<()>>,
     child: Option<Child>,
+    /// Shared with the reader thread, which must not go through `AppState::keyboard_task`
+    control: Arc<Mutex<ChildStdin>>,
 }
 
 impl Drop for KeyboardDaemonTask {
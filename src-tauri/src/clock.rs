@@ -0,0 +1,54 @@
+//! System-wide monotonic clock shared by the app and the keyboard daemon.
+//!
+//! Both processes read the same OS clock (CLOCK_MONOTONIC / QueryPerformanceCounter), so a
+//! capture time stamped in the daemon can be compared directly with times taken in the app.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::Lazy;
+
+/// Current monotonic time in microseconds. The origin is unspecified (usually boot).
+#[cfg(unix)]
+pub fn monotonic_us() -> u64 {
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // CLOCK_MONOTONIC cannot fail with a valid timespec pointer.
+    unsafe {
+        libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts);
+    }
+    (ts.tv_sec as u64) * 1_000_000 + (ts.tv_nsec as u64) / 1_000
+}
+
+/// Current monotonic time in microseconds. The origin is unspecified (usually boot).
+#[cfg(windows)]
+pub fn monotonic_us() -> u64 {
+    use windows::Win32::System::Performance::{QueryPerformanceCounter, QueryPerformanceFrequency};
+
+    static FREQUENCY: Lazy<u64> = Lazy::new(|| {
+        let mut frequency = 0i64;
+        let _ = unsafe { QueryPerformanceFrequency(&mut frequency) };
+        frequency.max(1) as u64
+    });
+
+    let mut counter = 0i64;
+    let _ = unsafe { QueryPerformanceCounter(&mut counter) };
+    let counter = counter.max(0) as u64;
+    let frequency = *FREQUENCY;
+    // Split to avoid overflowing u64 on long uptimes.
+    (counter / frequency) * 1_000_000 + (counter % frequency) * 1_000_000 / frequency
+}
+
+/// Microseconds to add to a [`monotonic_us`] value to get Unix wall-clock time.
+/// Measured once per process so the mapping stays stable for the whole session.
+pub fn wall_clock_offset_us() -> i64 {
+    static OFFSET: Lazy<i64> = Lazy::new(|| {
+        let wall = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_micros() as i64)
+            .unwrap_or(0);
+        wall - monotonic_us() as i64
+    });
+    *OFFSET
+}
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use tauri::{AppHandle, State};
+
+use crate::{
+    app_state::AppState,
+    ipc::{CaptureFilters, CaptureState},
+    replay::ReplaySource,
+    services::daemon_supervisor::DaemonHealth,
+};
+
+const PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn daemon_ping(state: State<'_, AppState>) -> Result<f64, String> {
+    state
+        .ping_daemon(PING_TIMEOUT)
+        .map(|elapsed| elapsed.as_secs_f64() * 1000.0)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn daemon_get_capture(state: State<'_, AppState>) -> Result<CaptureState, String> {
+    Ok(state.capture_state())
+}
+
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn daemon_set_paused(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    paused: bool,
+) -> Result<(), String> {
+    state
+        .set_capture_paused(&app, paused)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn daemon_set_filters(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    filters: CaptureFilters,
+) -> Result<(), String> {
+    state
+        .set_capture_filters(&app, filters)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn daemon_get_health(state: State<'_, AppState>) -> Result<DaemonHealth, String> {
+    Ok(state.daemon_health())
+}
+
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn daemon_restart(state: State<'_, AppState>, app: AppHandle) -> Result<(), String> {
+    state
+        .restart_keyboard_hook(&app)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn daemon_get_replay(state: State<'_, AppState>) -> Result<Option<ReplaySource>, String> {
+    Ok(state.replay_source())
+}
+
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn daemon_set_replay(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    source: Option<ReplaySource>,
+) -> Result<(), String> {
+    state
+        .set_replay_source(&app, source)
+        .map_err(|err| err.to_string())
+}
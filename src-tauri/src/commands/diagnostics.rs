@@ -0,0 +1,26 @@
+use tauri::State;
+
+use crate::{app_state::AppState, services::latency::LatencyReport};
+
+/// Capture-to-emit and capture-to-render latency of the current session
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn diagnostics_get_latency(state: State<'_, AppState>) -> Result<LatencyReport, String> {
+    Ok(state.latency_report())
+}
+
+/// Start a new measurement session, e.g. after switching the renderer
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn diagnostics_reset_latency(state: State<'_, AppState>) -> Result<(), String> {
+    state.reset_latency();
+    Ok(())
+}
+
+/// Render acknowledgements from the overlay: capture-to-frame latencies in microseconds
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn diagnostics_report_render_latency(
+    state: State<'_, AppState>,
+    samples_us: Vec<u64>,
+) -> Result<(), String> {
+    state.record_render_latency(&samples_us);
+    Ok(())
+}
@@ -6,10 +6,13 @@ use tauri::{AppHandle, Emitter, State};
 use crate::{
     app_state::AppState,
     defaults::{default_keys, default_positions},
+    ime::ImeState,
+    input_devices::{list_input_devices, InputDeviceInfo},
     models::{
-        CustomCssPatch, CustomTab, KeyCounters, KeyMappings, KeyPositions, NoteSettings,
-        NoteSettingsPatch, SettingsPatchInput,
+        CustomCssPatch, CustomTab, KeyCounters, KeyMappings, KeyModeDevices, KeyPositions,
+        NoteSettings, NoteSettingsPatch, SettingsPatchInput,
     },
+    services::{chatter_filter::KeyChatterStats, counter_snapshots::CounterSnapshot},
 };
 #[derive(Serialize)]
 pub struct ModeResponse {
@@ -73,7 +76,7 @@ pub fn keys_update(
         .store
         .update_keys(mappings)
         .map_err(|err| err.to_string())?;
-    state.keyboard.update_mappings(updated.clone());
+    state.update_key_mappings(updated.clone());
     app.emit("keys:changed", &updated)
         .map_err(|err| err.to_string())?;
     state.sync_counters_with_keys(&updated);
@@ -103,23 +106,9 @@ pub fn keys_set_mode(
     app: AppHandle,
     mode: String,
 ) -> Result<ModeResponse, String> {
-    let success = state.keyboard.set_mode(mode.clone());
-    let effective = if success {
-        mode
-    } else {
-        state.keyboard.current_mode()
-    };
-
-    state
-        .store
-        .set_selected_key_type(effective.clone())
+    let (success, effective) = state
+        .select_key_mode(&app, mode)
         .map_err(|err| err.to_string())?;
-
-    app.emit(
-        "keys:mode-changed",
-        &serde_json::json!({ "mode": &effective }),
-    )
-    .map_err(|err| err.to_string())?;
     Ok(ModeResponse {
         success,
         mode: effective,
@@ -142,13 +131,17 @@ pub fn keys_reset_all(
             store.key_positions = positions.clone();
             store.custom_tabs = custom_tabs.clone();
             store.selected_key_type = selected_key_type.clone();
+            store.key_mode_devices.clear();
         })
         .map_err(|err| err.to_string())?;
 
-    state.keyboard.update_mappings(keys.clone());
+    state.update_key_mappings(keys.clone());
+    state.keyboard.update_device_filters(KeyModeDevices::new());
     state.keyboard.set_mode(selected_key_type.clone());
+    // Reset before syncing so counters of modes dropped by the defaults still get snapshotted
+    state.reset_key_counters();
     state.sync_counters_with_keys(&keys);
-    let counters_snapshot = state.reset_key_counters();
+    let counters_snapshot = state.snapshot_key_counters();
     state
         .persist_key_counters()
         .map_err(|err| err.to_string())?;
@@ -210,6 +203,8 @@ pub fn keys_reset_all(
     .map_err(|err| err.to_string())?;
     app.emit("keys:counters", &counters_snapshot)
         .map_err(|err| err.to_string())?;
+    app.emit("keys:device-filters-changed", &KeyModeDevices::new())
+        .map_err(|err| err.to_string())?;
 
     Ok(ResetAllResponse {
         keys,
@@ -252,9 +247,10 @@ pub fn keys_reset_mode(
         })
         .map_err(|err| err.to_string())?;
 
-    state.keyboard.update_mappings(keys.clone());
-    state.sync_counters_with_keys(&keys);
+    state.update_key_mappings(keys.clone());
+    // Reset before syncing so counters of keys dropped by the defaults still get snapshotted
     state.reset_mode_counters(&mode);
+    state.sync_counters_with_keys(&keys);
     state
         .persist_key_counters()
         .map_err(|err| err.to_string())?;
@@ -329,7 +325,7 @@ pub fn custom_tabs_create(
         })
         .map_err(|err| err.to_string())?;
 
-    state.keyboard.update_mappings(keys.clone());
+    state.update_key_mappings(keys.clone());
     state.keyboard.set_mode(id.clone());
     state.sync_counters_with_keys(&keys);
     state.reset_mode_counters(&id);
@@ -416,7 +412,7 @@ pub fn custom_tabs_delete(
         })
         .map_err(|err| err.to_string())?;
 
-    state.keyboard.update_mappings(keys.clone());
+    state.update_key_mappings(keys.clone());
     state.keyboard.set_mode(next_selected.clone());
     state.sync_counters_with_keys(&keys);
     state
@@ -491,6 +487,63 @@ pub fn custom_tabs_select(
     })
 }
 
+/// Connected keyboards, mice and gamepads that can be used in device filters
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn input_devices_list() -> Result<Vec<InputDeviceInfo>, String> {
+    Ok(list_input_devices())
+}
+
+/// IME and input language of the focused app, once the daemon has reported it
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn input_get_ime_state(state: State<'_, AppState>) -> Result<Option<ImeState>, String> {
+    Ok(state.ime_state())
+}
+
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn keys_get_device_filters(state: State<'_, AppState>) -> Result<KeyModeDevices, String> {
+    Ok(state.store.snapshot().key_mode_devices)
+}
+
+/// Accept input in `mode` only from `devices`; an empty list accepts every device again
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn keys_set_device_filter(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    mode: String,
+    devices: Vec<String>,
+) -> Result<KeyModeDevices, String> {
+    if !state.store.snapshot().keys.contains_key(&mode) {
+        return Err(format!("unknown key mode: {mode}"));
+    }
+    let mut unique: Vec<String> = Vec::with_capacity(devices.len());
+    for device in devices {
+        if !device.is_empty() && !unique.contains(&device) {
+            unique.push(device);
+        }
+    }
+
+    let updated = state
+        .store
+        .set_key_mode_devices(&mode, unique)
+        .map_err(|err| err.to_string())?;
+    state.keyboard.update_device_filters(updated.clone());
+    app.emit("keys:device-filters-changed", &updated)
+        .map_err(|err| err.to_string())?;
+    Ok(updated)
+}
+
+/// Keys whose presses were dropped by the debounce filter, most chatter first
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn keys_get_chatter_stats(state: State<'_, AppState>) -> Result<Vec<KeyChatterStats>, String> {
+    Ok(state.chatter_stats())
+}
+
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn keys_reset_chatter_stats(state: State<'_, AppState>) -> Result<(), String> {
+    state.reset_chatter_stats();
+    Ok(())
+}
+
 #[tauri::command(permission = "dmnote-allow-all")]
 pub fn keys_reset_counters(state: State<'_, AppState>, app: AppHandle) -> Result<KeyCounters, String> {
     let snapshot = state.reset_key_counters();
@@ -550,6 +603,37 @@ pub fn keys_set_counters(
     Ok(updated)
 }
 
+/// Counter values saved before each reset, newest first
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn keys_list_counter_snapshots(
+    state: State<'_, AppState>,
+) -> Result<Vec<CounterSnapshot>, String> {
+    Ok(state.counter_snapshots())
+}
+
+/// Puts the snapshot's counters back; the values it replaces are snapshotted in turn
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn keys_restore_counter_snapshot(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    id: String,
+) -> Result<KeyCounters, String> {
+    state
+        .restore_counter_snapshot(&app, &id)
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("counter snapshot not found: {id}"))
+}
+
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn keys_delete_counter_snapshot(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<bool, String> {
+    state
+        .delete_counter_snapshot(&id)
+        .map_err(|err| err.to_string())
+}
+
 fn generate_custom_tab_id() -> String {
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -578,3 +662,18 @@ pub fn raw_input_unsubscribe(state: State<'_, AppState>) -> Result<RawInputSubsc
     log::debug!("[RawInput] Unsubscribe: count = {}", count);
     Ok(RawInputSubscribeResponse { count })
 }
+
+/// Forward every key, not only mapped ones, until revoked (used while assigning a key)
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn raw_input_grant(state: State<'_, AppState>) -> Result<RawInputSubscribeResponse, String> {
+    let count = state.grant_raw_input();
+    log::debug!("[RawInput] Grant: count = {}", count);
+    Ok(RawInputSubscribeResponse { count })
+}
+
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn raw_input_revoke(state: State<'_, AppState>) -> Result<RawInputSubscribeResponse, String> {
+    let count = state.revoke_raw_input();
+    log::debug!("[RawInput] Revoke: count = {}", count);
+    Ok(RawInputSubscribeResponse { count })
+}
@@ -1,10 +1,13 @@
 pub mod app;
 pub mod bridge;
 pub mod css;
+pub mod daemon;
+pub mod diagnostics;
 pub mod js;
 pub mod keys;
 pub mod overlay;
 pub mod plugin_storage;
 pub mod preset;
 pub mod settings;
+pub mod stats;
 pub mod system;
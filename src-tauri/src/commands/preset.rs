@@ -7,6 +7,7 @@ use tauri::{AppHandle, Emitter, State};
 use crate::{
     app_state::AppState,
     defaults::{default_keys, default_positions},
+    key_codes,
     models::{
         CustomCss, CustomCssPatch, CustomJs, CustomJsPatch, CustomTab, KeyMappings, KeyPositions,
         NoteSettings, NoteSettingsPatch, SettingsPatchInput,
@@ -100,7 +101,9 @@ pub fn preset_load(
     let preset: PresetFile =
         serde_json::from_str(&content).map_err(|_| "invalid-preset".to_string())?;
 
-    let keys = preset.keys.unwrap_or_else(|| default_keys().clone());
+    let mut keys = preset.keys.unwrap_or_else(|| default_keys().clone());
+    // Presets saved by older versions store platform labels.
+    key_codes::migrate_mappings(&mut keys);
     let positions = preset.key_positions.unwrap_or_else(|| default_positions().clone());
     let custom_tabs = preset
         .custom_tabs
@@ -119,7 +122,7 @@ pub fn preset_load(
         })
         .map_err(|err| err.to_string())?;
 
-    state.keyboard.update_mappings(keys.clone());
+    state.update_key_mappings(keys.clone());
     state.keyboard.set_mode(selected_key_type.clone());
 
     let desired_settings = preset.note_settings.unwrap_or_else(NoteSettings::default);
@@ -0,0 +1,215 @@
+use std::{fs, path::Path};
+
+use chrono::NaiveDate;
+use rfd::FileDialog;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use crate::{
+    app_state::AppState,
+    services::{
+        counter_history::{CounterHistoryRange, HistoryGranularity},
+        key_timing::KeyTimingReport,
+        kps_stats::KpsReport,
+        play_sessions::PlaySession,
+    },
+};
+
+#[derive(Serialize)]
+pub struct StatsExportResult {
+    pub success: bool,
+    /// Written file, absent when the dialog was cancelled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl StatsExportResult {
+    fn cancelled() -> Self {
+        Self {
+            success: false,
+            path: None,
+        }
+    }
+
+    fn saved(path: &Path) -> Self {
+        Self {
+            success: true,
+            path: Some(path.display().to_string()),
+        }
+    }
+}
+
+/// Sliding-window KPS, APM, session average and peak for every mode that saw input
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn stats_get_kps(state: State<'_, AppState>) -> Result<KpsReport, String> {
+    Ok(state.kps_report())
+}
+
+/// Clear one mode's statistics, or start a new session when `mode` is omitted
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn stats_reset_kps(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    mode: Option<String>,
+) -> Result<KpsReport, String> {
+    state
+        .reset_kps(&app, mode.as_deref())
+        .map_err(|err| err.to_string())
+}
+
+/// Hold-duration and same-key interval distributions per key and per mode
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn stats_get_key_timing(state: State<'_, AppState>) -> Result<KeyTimingReport, String> {
+    Ok(state.key_timing_report())
+}
+
+/// Clear one mode's timing distributions, or start a new session when `mode` is omitted
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn stats_reset_key_timing(
+    state: State<'_, AppState>,
+    mode: Option<String>,
+) -> Result<KeyTimingReport, String> {
+    state.reset_key_timing(mode.as_deref());
+    Ok(state.key_timing_report())
+}
+
+/// Save the current timing report as JSON to a file chosen by the user
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn stats_export_key_timing(state: State<'_, AppState>) -> Result<StatsExportResult, String> {
+    let Some(path) = FileDialog::new()
+        .set_file_name("key-timing.json")
+        .add_filter("JSON", &["json"])
+        .save_file()
+    else {
+        return Ok(StatsExportResult::cancelled());
+    };
+
+    let json =
+        serde_json::to_string_pretty(&state.key_timing_report()).map_err(|err| err.to_string())?;
+    fs::write(&path, json).map_err(|err| err.to_string())?;
+    Ok(StatsExportResult::saved(&path))
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsExportFormat {
+    Csv,
+    Json,
+}
+
+fn parse_day(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("invalid date (expected YYYY-MM-DD): {value}"))
+}
+
+fn query_counter_history(
+    state: &AppState,
+    from: &str,
+    to: &str,
+    granularity: HistoryGranularity,
+    mode: Option<&str>,
+) -> Result<CounterHistoryRange, String> {
+    let (from, to) = (parse_day(from)?, parse_day(to)?);
+    if from > to {
+        return Err("`from` must not be after `to`".to_string());
+    }
+    Ok(state.counter_history(from, to, granularity, mode))
+}
+
+/// Press counts per hour or day between two local dates (inclusive), optionally for one mode
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn stats_get_counter_history(
+    state: State<'_, AppState>,
+    from: String,
+    to: String,
+    granularity: HistoryGranularity,
+    mode: Option<String>,
+) -> Result<CounterHistoryRange, String> {
+    query_counter_history(&state, &from, &to, granularity, mode.as_deref())
+}
+
+/// Save a counter history range as CSV (`period,mode,key,count`) or JSON
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn stats_export_counter_history(
+    state: State<'_, AppState>,
+    from: String,
+    to: String,
+    granularity: HistoryGranularity,
+    mode: Option<String>,
+    format: StatsExportFormat,
+) -> Result<StatsExportResult, String> {
+    let range = query_counter_history(&state, &from, &to, granularity, mode.as_deref())?;
+    let (extension, filter) = match format {
+        StatsExportFormat::Csv => ("csv", "CSV"),
+        StatsExportFormat::Json => ("json", "JSON"),
+    };
+    let Some(path) = FileDialog::new()
+        .set_file_name(format!(
+            "counter-history-{}-{}.{extension}",
+            range.from, range.to
+        ))
+        .add_filter(filter, &[extension])
+        .save_file()
+    else {
+        return Ok(StatsExportResult::cancelled());
+    };
+
+    let contents = match format {
+        StatsExportFormat::Csv => range.to_csv(),
+        StatsExportFormat::Json => {
+            serde_json::to_string_pretty(&range).map_err(|err| err.to_string())?
+        }
+    };
+    fs::write(&path, contents).map_err(|err| err.to_string())?;
+    Ok(StatsExportResult::saved(&path))
+}
+
+/// Finished play sessions, newest first
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn stats_list_sessions(state: State<'_, AppState>) -> Result<Vec<PlaySession>, String> {
+    Ok(state.play_sessions())
+}
+
+/// Remove a saved play session; `false` when no session has that id
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn stats_delete_session(state: State<'_, AppState>, id: String) -> Result<bool, String> {
+    state
+        .delete_play_session(&id)
+        .map_err(|err| err.to_string())
+}
+
+/// Save all play sessions as CSV (one summary row each) or JSON (with per-key counts)
+#[tauri::command(permission = "dmnote-allow-all")]
+pub fn stats_export_sessions(
+    state: State<'_, AppState>,
+    format: StatsExportFormat,
+) -> Result<StatsExportResult, String> {
+    let (extension, filter) = match format {
+        StatsExportFormat::Csv => ("csv", "CSV"),
+        StatsExportFormat::Json => ("json", "JSON"),
+    };
+    let Some(path) = FileDialog::new()
+        .set_file_name(format!("play-sessions.{extension}"))
+        .add_filter(filter, &[extension])
+        .save_file()
+    else {
+        return Ok(StatsExportResult::cancelled());
+    };
+
+    let sessions = state.play_sessions();
+    let contents = match format {
+        StatsExportFormat::Csv => {
+            let mut csv = format!("{}\n", PlaySession::CSV_HEADER);
+            for session in &sessions {
+                csv.push_str(&session.csv_row());
+                csv.push('\n');
+            }
+            csv
+        }
+        StatsExportFormat::Json => {
+            serde_json::to_string_pretty(&sessions).map_err(|err| err.to_string())?
+        }
+    };
+    fs::write(&path, contents).map_err(|err| err.to_string())?;
+    Ok(StatsExportResult::saved(&path))
+}
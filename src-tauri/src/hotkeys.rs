@@ -0,0 +1,395 @@
+//! Global hotkey matching for the keyboard daemon.
+//!
+//! A binding is a single key with modifiers, a chord of several keys held together
+//! (e.g. F9+F10), or a two-step sequence: a leader combo, then the action combo within a
+//! timeout. Backends feed non-modifier key transitions plus the current modifier state;
+//! keys are whatever the backend identifies them by (VK codes, macOS key names).
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::iter;
+use std::time::{Duration, Instant};
+
+use crate::ipc::DaemonCommand;
+use crate::models::{ShortcutBinding, ShortcutsState};
+
+/// Whether a canvas shortcut (key mode, side panel, zoom) is also registered globally.
+///
+/// Canvas shortcuts always work inside the main window, where an unmodified key such as the
+/// default `Tab` is fine; globally it would fire on every in-game press. This is a deliberate
+/// exception to "every binding is global": such shortcuts stay window-only until they include a
+/// modifier, a chord or a leader, and the shortcut settings modal tells the user so.
+pub fn canvas_binding_is_global(binding: &ShortcutBinding) -> bool {
+    binding.ctrl || binding.shift || binding.alt || binding.meta || !binding.is_simple()
+}
+
+/// Global hotkeys and the command each one sends to the app, in match order.
+/// Canvas shortcuts are included only if [`canvas_binding_is_global`] allows them.
+pub fn global_bindings(hotkeys: &ShortcutsState) -> Vec<(ShortcutBinding, DaemonCommand)> {
+    let mut bindings = vec![
+        (hotkeys.toggle_overlay.clone(), DaemonCommand::ToggleOverlay),
+        (
+            hotkeys.toggle_overlay_lock.clone(),
+            DaemonCommand::ToggleOverlayLock,
+        ),
+        (
+            hotkeys.toggle_always_on_top.clone(),
+            DaemonCommand::ToggleAlwaysOnTop,
+        ),
+        (
+            hotkeys.reset_mode_counters.clone(),
+            DaemonCommand::ResetModeCounters,
+        ),
+        (
+            hotkeys.toggle_key_counter.clone(),
+            DaemonCommand::ToggleKeyCounter,
+        ),
+        (
+            hotkeys.toggle_capture_pause.clone(),
+            DaemonCommand::ToggleCapturePause,
+        ),
+    ];
+    bindings.extend(hotkeys.key_mode_shortcuts.iter().map(|(mode, binding)| {
+        (
+            binding.clone(),
+            DaemonCommand::SetKeyMode { mode: mode.clone() },
+        )
+    }));
+    let canvas = [
+        (&hotkeys.switch_key_mode, DaemonCommand::CycleKeyMode),
+        (
+            &hotkeys.toggle_settings_panel,
+            DaemonCommand::ToggleSettingsPanel,
+        ),
+        (&hotkeys.zoom_in, DaemonCommand::ZoomIn),
+        (&hotkeys.zoom_out, DaemonCommand::ZoomOut),
+        (&hotkeys.reset_zoom, DaemonCommand::ResetZoom),
+    ];
+    bindings.extend(
+        canvas
+            .into_iter()
+            .filter(|(binding, _)| canvas_binding_is_global(binding))
+            .map(|(binding, command)| (binding.clone(), command)),
+    );
+    bindings.retain(|(binding, _)| !binding.key.trim().is_empty());
+    bindings
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+impl Modifiers {
+    fn of(binding: &ShortcutBinding) -> Self {
+        Self {
+            ctrl: binding.ctrl,
+            shift: binding.shift,
+            alt: binding.alt,
+            meta: binding.meta,
+        }
+    }
+}
+
+/// Keys that must be held together, with an exact modifier state.
+#[derive(Debug, Clone)]
+struct Combo<K> {
+    keys: Vec<K>,
+    modifiers: Modifiers,
+}
+
+impl<K: Eq + Hash> Combo<K> {
+    fn parse(binding: &ShortcutBinding, parse_key: &impl Fn(&str) -> Option<K>) -> Option<Self> {
+        let keys = iter::once(&binding.key)
+            .chain(&binding.chord)
+            .map(|code| parse_key(code))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            keys,
+            modifiers: Modifiers::of(binding),
+        })
+    }
+
+    /// Whether pressing `key` (already in `held`) completes this combo.
+    fn completed_by(&self, key: &K, held: &HashSet<K>, modifiers: Modifiers) -> bool {
+        self.modifiers == modifiers
+            && self.keys.contains(key)
+            && self.keys.iter().all(|k| held.contains(k))
+    }
+}
+
+struct Pattern<K> {
+    leader: Option<Combo<K>>,
+    combo: Combo<K>,
+    timeout: Duration,
+    command: DaemonCommand,
+}
+
+pub struct HotkeyMatcher<K> {
+    patterns: Vec<Pattern<K>>,
+    held: HashSet<K>,
+    /// Sequences whose leader was pressed: (pattern index, deadline).
+    armed: Vec<(usize, Instant)>,
+}
+
+impl<K: Eq + Hash + Clone> HotkeyMatcher<K> {
+    /// Bindings whose keys `parse_key` does not know are dropped.
+    pub fn new(
+        bindings: Vec<(ShortcutBinding, DaemonCommand)>,
+        parse_key: impl Fn(&str) -> Option<K>,
+    ) -> Self {
+        let mut matcher = Self {
+            patterns: Vec::new(),
+            held: HashSet::new(),
+            armed: Vec::new(),
+        };
+        matcher.rebind(bindings, parse_key);
+        matcher
+    }
+
+    /// Replaces the bindings. Held keys are kept so a press in progress is not lost.
+    pub fn rebind(
+        &mut self,
+        bindings: Vec<(ShortcutBinding, DaemonCommand)>,
+        parse_key: impl Fn(&str) -> Option<K>,
+    ) {
+        self.armed.clear();
+        self.patterns = bindings
+            .into_iter()
+            .filter_map(|(binding, command)| {
+                let leader = match binding.leader.as_deref() {
+                    Some(leader) => Some(Combo::parse(leader, &parse_key)?),
+                    None => None,
+                };
+                let timeout_ms = binding
+                    .timeout_ms
+                    .unwrap_or(ShortcutBinding::DEFAULT_SEQUENCE_TIMEOUT_MS);
+                Some(Pattern {
+                    leader,
+                    combo: Combo::parse(&binding, &parse_key)?,
+                    timeout: Duration::from_millis(u64::from(timeout_ms)),
+                    command,
+                })
+            })
+            .collect();
+    }
+
+    /// Feeds one non-modifier key transition and returns the command it triggers, if any.
+    /// Auto-repeated presses of a held key never trigger.
+    pub fn update(
+        &mut self,
+        key: K,
+        is_down: bool,
+        modifiers: Modifiers,
+        now: Instant,
+    ) -> Option<DaemonCommand> {
+        if !is_down {
+            self.held.remove(&key);
+            return None;
+        }
+        if !self.held.insert(key.clone()) {
+            return None;
+        }
+
+        self.armed.retain(|(_, deadline)| *deadline >= now);
+        if !self.armed.is_empty() {
+            let armed = std::mem::take(&mut self.armed);
+            if let Some((index, _)) = armed.iter().find(|(index, _)| {
+                self.patterns[*index]
+                    .combo
+                    .completed_by(&key, &self.held, modifiers)
+            }) {
+                return Some(self.patterns[*index].command.clone());
+            }
+            // Keep waiting only for actions this key is part of (a chord still being built).
+            self.armed = armed
+                .into_iter()
+                .filter(|(index, _)| self.patterns[*index].combo.keys.contains(&key))
+                .collect();
+        }
+
+        if let Some(pattern) = self.patterns.iter().find(|pattern| {
+            pattern.leader.is_none() && pattern.combo.completed_by(&key, &self.held, modifiers)
+        }) {
+            return Some(pattern.command.clone());
+        }
+
+        for (index, pattern) in self.patterns.iter().enumerate() {
+            let leader_done = pattern
+                .leader
+                .as_ref()
+                .is_some_and(|leader| leader.completed_by(&key, &self.held, modifiers));
+            if leader_done {
+                self.armed.push((index, now + pattern.timeout));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(key: &str) -> ShortcutBinding {
+        ShortcutBinding {
+            key: key.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn matcher(bindings: Vec<(ShortcutBinding, DaemonCommand)>) -> HotkeyMatcher<String> {
+        HotkeyMatcher::new(bindings, |code| Some(code.to_string()))
+    }
+
+    fn press(
+        m: &mut HotkeyMatcher<String>,
+        key: &str,
+        mods: Modifiers,
+        at: Instant,
+    ) -> Option<DaemonCommand> {
+        m.update(key.to_string(), true, mods, at)
+    }
+
+    fn release(m: &mut HotkeyMatcher<String>, key: &str, at: Instant) {
+        m.update(key.to_string(), false, Modifiers::default(), at);
+    }
+
+    #[test]
+    fn single_key_needs_exact_modifiers_and_ignores_repeat() {
+        let ctrl = Modifiers {
+            ctrl: true,
+            ..Default::default()
+        };
+        let mut m = matcher(vec![(
+            ShortcutBinding {
+                ctrl: true,
+                ..binding("KeyO")
+            },
+            DaemonCommand::ToggleOverlay,
+        )]);
+        let now = Instant::now();
+        assert_eq!(press(&mut m, "KeyO", Modifiers::default(), now), None);
+        release(&mut m, "KeyO", now);
+        assert_eq!(
+            press(&mut m, "KeyO", ctrl, now),
+            Some(DaemonCommand::ToggleOverlay)
+        );
+        assert_eq!(press(&mut m, "KeyO", ctrl, now), None);
+    }
+
+    #[test]
+    fn chord_fires_when_the_last_key_goes_down() {
+        let mut m = matcher(vec![(
+            ShortcutBinding {
+                chord: vec!["F10".to_string()],
+                ..binding("F9")
+            },
+            DaemonCommand::ResetModeCounters,
+        )]);
+        let now = Instant::now();
+        let none = Modifiers::default();
+        assert_eq!(press(&mut m, "F10", none, now), None);
+        assert_eq!(
+            press(&mut m, "F9", none, now),
+            Some(DaemonCommand::ResetModeCounters)
+        );
+        release(&mut m, "F9", now);
+        release(&mut m, "F10", now);
+        assert_eq!(press(&mut m, "F9", none, now), None);
+    }
+
+    #[test]
+    fn sequence_fires_only_within_timeout() {
+        let mut m = matcher(vec![(
+            ShortcutBinding {
+                leader: Some(Box::new(binding("F8"))),
+                timeout_ms: Some(500),
+                ..binding("KeyR")
+            },
+            DaemonCommand::ResetModeCounters,
+        )]);
+        let start = Instant::now();
+        let none = Modifiers::default();
+
+        assert_eq!(press(&mut m, "KeyR", none, start), None);
+        release(&mut m, "KeyR", start);
+
+        assert_eq!(press(&mut m, "F8", none, start), None);
+        release(&mut m, "F8", start);
+        let later = start + Duration::from_millis(200);
+        assert_eq!(
+            press(&mut m, "KeyR", none, later),
+            Some(DaemonCommand::ResetModeCounters)
+        );
+        release(&mut m, "KeyR", later);
+
+        press(&mut m, "F8", none, start);
+        release(&mut m, "F8", start);
+        let late = start + Duration::from_millis(800);
+        assert_eq!(press(&mut m, "KeyR", none, late), None);
+    }
+
+    #[test]
+    fn unmodified_canvas_binding_stays_window_only() {
+        let commands = |hotkeys: &ShortcutsState| -> Vec<DaemonCommand> {
+            global_bindings(hotkeys)
+                .into_iter()
+                .map(|(_, command)| command)
+                .collect()
+        };
+
+        // The default key mode switch is a bare Tab: not global, so Tab in game is left alone.
+        let defaults = ShortcutsState::default();
+        assert!(!canvas_binding_is_global(&defaults.switch_key_mode));
+        assert!(!commands(&defaults).contains(&DaemonCommand::CycleKeyMode));
+        // Default zoom shortcuts carry Ctrl and are global.
+        assert!(commands(&defaults).contains(&DaemonCommand::ZoomIn));
+
+        let with_modifier = ShortcutsState {
+            switch_key_mode: ShortcutBinding {
+                ctrl: true,
+                ..binding("Tab")
+            },
+            ..ShortcutsState::default()
+        };
+        assert!(commands(&with_modifier).contains(&DaemonCommand::CycleKeyMode));
+
+        let with_leader = ShortcutsState {
+            switch_key_mode: ShortcutBinding {
+                leader: Some(Box::new(binding("F8"))),
+                ..binding("Tab")
+            },
+            ..ShortcutsState::default()
+        };
+        assert!(commands(&with_leader).contains(&DaemonCommand::CycleKeyMode));
+
+        // Non-canvas bindings are global even without a modifier.
+        let bare_reset = ShortcutsState {
+            reset_mode_counters: binding("F9"),
+            ..ShortcutsState::default()
+        };
+        assert!(commands(&bare_reset).contains(&DaemonCommand::ResetModeCounters));
+    }
+
+    #[test]
+    fn other_key_cancels_a_pending_sequence() {
+        let mut m = matcher(vec![(
+            ShortcutBinding {
+                leader: Some(Box::new(binding("F8"))),
+                ..binding("KeyR")
+            },
+            DaemonCommand::ToggleKeyCounter,
+        )]);
+        let now = Instant::now();
+        let none = Modifiers::default();
+        press(&mut m, "F8", none, now);
+        release(&mut m, "F8", now);
+        press(&mut m, "KeyW", none, now);
+        release(&mut m, "KeyW", now);
+        assert_eq!(press(&mut m, "KeyR", none, now), None);
+    }
+}
@@ -0,0 +1,213 @@
+//! Input method (IME) and input language state of the focused application.
+//!
+//! Korean and Japanese players switch between native and alphanumeric input while playing, and
+//! a wrong mode swallows keys in some games. The keyboard daemon watches the state and reports
+//! changes to the app, which forwards them to the overlay:
+//! - Windows: keyboard layout and IME conversion mode of the foreground window, polled.
+//! - macOS: the current text input source, checked after each key or button release (Text
+//!   Input Sources may only be queried from the event tap's main thread).
+//!
+//! Linux input methods (IBus, Fcitx) have no common query API, so nothing is reported there.
+
+use serde::{Deserialize, Serialize};
+
+/// What typing produces right now.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImeConversion {
+    #[default]
+    Unknown,
+    /// Latin letters: no IME, IME closed, or 영문/英数 mode.
+    Alphanumeric,
+    /// Hangul or hiragana composition.
+    Native,
+    Katakana,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImeState {
+    /// BCP 47 tag of the input language, e.g. `ko-KR`.
+    pub language: Option<String>,
+    /// Platform id of the layout or input source (Windows HKL, macOS input source id).
+    pub source: Option<String>,
+    pub conversion: ImeConversion,
+}
+
+/// Remembers the last reported state so only changes are sent.
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+#[derive(Default)]
+pub struct ImeWatcher {
+    last: Option<ImeState>,
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+impl ImeWatcher {
+    /// The current state if it differs from the last one returned.
+    pub fn poll(&mut self) -> Option<ImeState> {
+        let state = current_ime_state()?;
+        if self.last.as_ref() == Some(&state) {
+            return None;
+        }
+        self.last = Some(state.clone());
+        Some(state)
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn current_ime_state() -> Option<ImeState> {
+    use windows::Win32::Foundation::{LPARAM, WPARAM};
+    use windows::Win32::Globalization::LCIDToLocaleName;
+    use windows::Win32::UI::Input::Ime::ImmGetDefaultIMEWnd;
+    use windows::Win32::UI::Input::KeyboardAndMouse::GetKeyboardLayout;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowThreadProcessId, SendMessageTimeoutW, SMTO_ABORTIFHUNG,
+    };
+
+    const WM_IME_CONTROL: u32 = 0x0283;
+    const IMC_GETCONVERSIONMODE: usize = 0x0001;
+    const IMC_GETOPENSTATUS: usize = 0x0005;
+    const IME_CMODE_NATIVE: usize = 0x0001;
+    const IME_CMODE_KATAKANA: usize = 0x0002;
+    const LOCALE_NAME_MAX_LENGTH: usize = 85;
+
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_invalid() {
+            return None;
+        }
+        let thread = GetWindowThreadProcessId(hwnd, None);
+        let hkl = GetKeyboardLayout(thread).0 as usize;
+
+        let lang_id = (hkl & 0xFFFF) as u32;
+        let mut name = [0u16; LOCALE_NAME_MAX_LENGTH];
+        let len = LCIDToLocaleName(lang_id, Some(&mut name), 0);
+        let language = (len > 1).then(|| String::from_utf16_lossy(&name[..len as usize - 1]));
+
+        // The IME window belongs to the foreground app; a hung app must not stall the daemon.
+        let ime_wnd = ImmGetDefaultIMEWnd(hwnd);
+        let query = |command: usize| {
+            let mut result = 0usize;
+            let sent = SendMessageTimeoutW(
+                ime_wnd,
+                WM_IME_CONTROL,
+                WPARAM(command),
+                LPARAM(0),
+                SMTO_ABORTIFHUNG,
+                50,
+                Some(&mut result),
+            );
+            (sent.0 != 0).then_some(result)
+        };
+        let conversion = if ime_wnd.is_invalid() {
+            ImeConversion::Unknown
+        } else {
+            match (query(IMC_GETOPENSTATUS), query(IMC_GETCONVERSIONMODE)) {
+                (Some(0), _) => ImeConversion::Alphanumeric,
+                (Some(_), Some(mode)) if mode & IME_CMODE_NATIVE == 0 => {
+                    ImeConversion::Alphanumeric
+                }
+                (Some(_), Some(mode)) if mode & IME_CMODE_KATAKANA != 0 => ImeConversion::Katakana,
+                (Some(_), Some(_)) => ImeConversion::Native,
+                _ => ImeConversion::Unknown,
+            }
+        };
+
+        Some(ImeState {
+            language,
+            source: Some(format!("{:08X}", hkl & 0xFFFF_FFFF)),
+            conversion,
+        })
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub fn current_ime_state() -> Option<ImeState> {
+    use std::ffi::CStr;
+    use std::os::raw::{c_char, c_void};
+
+    type CFStringRef = *const c_void;
+    const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+    #[link(name = "Carbon", kind = "framework")]
+    extern "C" {
+        fn TISCopyCurrentKeyboardInputSource() -> *const c_void;
+        fn TISGetInputSourceProperty(source: *const c_void, key: CFStringRef) -> *const c_void;
+        static kTISPropertyInputSourceID: CFStringRef;
+        static kTISPropertyInputModeID: CFStringRef;
+        static kTISPropertyInputSourceLanguages: CFStringRef;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFStringGetCString(
+            string: CFStringRef,
+            buffer: *mut c_char,
+            size: isize,
+            encoding: u32,
+        ) -> u8;
+        fn CFArrayGetCount(array: *const c_void) -> isize;
+        fn CFArrayGetValueAtIndex(array: *const c_void, index: isize) -> *const c_void;
+        fn CFRelease(cf: *const c_void);
+    }
+
+    unsafe fn to_string(string: CFStringRef) -> Option<String> {
+        if string.is_null() {
+            return None;
+        }
+        let mut buffer = [0 as c_char; 256];
+        let copied = CFStringGetCString(
+            string,
+            buffer.as_mut_ptr(),
+            buffer.len() as isize,
+            CF_STRING_ENCODING_UTF8,
+        );
+        if copied == 0 {
+            return None;
+        }
+        Some(
+            CStr::from_ptr(buffer.as_ptr())
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+
+    unsafe {
+        let source = TISCopyCurrentKeyboardInputSource();
+        if source.is_null() {
+            return None;
+        }
+        // Properties follow the get rule: owned by `source`, valid until it is released.
+        let source_id = to_string(TISGetInputSourceProperty(source, kTISPropertyInputSourceID));
+        let mode_id = to_string(TISGetInputSourceProperty(source, kTISPropertyInputModeID));
+        let languages = TISGetInputSourceProperty(source, kTISPropertyInputSourceLanguages);
+        let language = if !languages.is_null() && CFArrayGetCount(languages) > 0 {
+            to_string(CFArrayGetValueAtIndex(languages, 0))
+        } else {
+            None
+        };
+        CFRelease(source);
+
+        // Input modes look like `com.apple.inputmethod.Japanese.Katakana`; plain layouts like
+        // `com.apple.keylayout.ABC`.
+        let id = mode_id
+            .as_deref()
+            .or(source_id.as_deref())
+            .unwrap_or_default();
+        let conversion = if id.contains("Katakana") {
+            ImeConversion::Katakana
+        } else if id.starts_with("com.apple.keylayout.") || id.contains("Roman") {
+            ImeConversion::Alphanumeric
+        } else if id.contains("inputmethod") {
+            ImeConversion::Native
+        } else {
+            ImeConversion::Unknown
+        };
+
+        Some(ImeState {
+            language,
+            source: source_id,
+            conversion,
+        })
+    }
+}
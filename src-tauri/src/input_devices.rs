@@ -0,0 +1,238 @@
+//! Identity of physical input devices.
+//!
+//! The keyboard daemon tags every [`HookMessage`](crate::ipc::HookMessage) with the device it
+//! came from, and the app lists connected devices so each key mode can accept input only from
+//! chosen ones. Both sides build ids here so they always agree:
+//! - Windows: the Raw Input device interface path, lowercased.
+//! - Linux: `bus:vendor:product`, plus the evdev physical path when the driver reports one.
+//!
+//! Ids stay the same across reconnects and restarts while the device keeps its port.
+//! Devices created by software (Linux uinput, used by remappers and macro tools) are marked
+//! `synthetic`; their input is reported as injected.
+//! macOS input comes from a system-wide event tap without device information, so no ids exist
+//! there.
+
+use serde::Serialize;
+
+use crate::ipc::InputDeviceKind;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InputDeviceInfo {
+    pub id: String,
+    pub name: String,
+    pub kind: InputDeviceKind,
+    pub synthetic: bool,
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn kind_label(kind: InputDeviceKind) -> &'static str {
+    match kind {
+        InputDeviceKind::Keyboard => "Keyboard",
+        InputDeviceKind::Mouse => "Mouse",
+        InputDeviceKind::Gamepad => "Gamepad",
+        InputDeviceKind::Unknown => "Input device",
+    }
+}
+
+/// Keyboards, mice and gamepads that can be captured right now, sorted by name.
+pub fn list_input_devices() -> Vec<InputDeviceInfo> {
+    #[cfg(target_os = "windows")]
+    let mut devices = list_raw_input_devices();
+    #[cfg(target_os = "linux")]
+    let mut devices = list_evdev_devices();
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    let mut devices: Vec<InputDeviceInfo> = Vec::new();
+
+    devices.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+    devices.dedup_by(|a, b| a.id == b.id);
+    devices
+}
+
+/// Identity of a Raw Input device handle, or `None` for input without a device
+/// (for example input injected by software).
+#[cfg(target_os = "windows")]
+pub fn raw_input_device(
+    handle: windows::Win32::Foundation::HANDLE,
+    kind: InputDeviceKind,
+) -> Option<InputDeviceInfo> {
+    use windows::Win32::UI::Input::{GetRawInputDeviceInfoW, RIDI_DEVICENAME};
+
+    if handle.is_invalid() {
+        return None;
+    }
+    // RIDI_DEVICENAME sizes are in UTF-16 units, terminator included.
+    let mut len: u32 = 0;
+    let path = unsafe {
+        let res = GetRawInputDeviceInfoW(Some(handle), RIDI_DEVICENAME, None, &mut len);
+        if res == u32::MAX || len == 0 {
+            return None;
+        }
+        let mut buffer = vec![0u16; len as usize];
+        let copied = GetRawInputDeviceInfoW(
+            Some(handle),
+            RIDI_DEVICENAME,
+            Some(buffer.as_mut_ptr().cast()),
+            &mut len,
+        );
+        if copied == u32::MAX {
+            return None;
+        }
+        buffer.truncate(copied as usize);
+        String::from_utf16_lossy(&buffer)
+    };
+    let id = path.trim_end_matches('\0').to_ascii_lowercase();
+    if id.is_empty() {
+        return None;
+    }
+    Some(InputDeviceInfo {
+        name: windows_device_name(&id, kind),
+        id,
+        kind,
+        synthetic: false,
+    })
+}
+
+/// `Keyboard 046D:C31C` from the VID/PID in a device path; Raw Input has no product names.
+#[cfg(target_os = "windows")]
+fn windows_device_name(path: &str, kind: InputDeviceKind) -> String {
+    let hex_after = |tag: &str| {
+        let start = path.find(tag)? + tag.len();
+        path.get(start..start + 4).map(str::to_ascii_uppercase)
+    };
+    match (hex_after("vid_"), hex_after("pid_")) {
+        (Some(vid), Some(pid)) => format!("{} {vid}:{pid}", kind_label(kind)),
+        _ => kind_label(kind).to_string(),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn list_raw_input_devices() -> Vec<InputDeviceInfo> {
+    use std::mem::size_of;
+    use windows::Win32::UI::Input::{
+        GetRawInputDeviceList, RAWINPUTDEVICELIST, RIM_TYPEKEYBOARD, RIM_TYPEMOUSE,
+    };
+
+    let entry_size = size_of::<RAWINPUTDEVICELIST>() as u32;
+    let mut count: u32 = 0;
+    let entries = unsafe {
+        if GetRawInputDeviceList(None, &mut count, entry_size) == u32::MAX || count == 0 {
+            return Vec::new();
+        }
+        let mut entries = vec![RAWINPUTDEVICELIST::default(); count as usize];
+        let found = GetRawInputDeviceList(Some(entries.as_mut_ptr()), &mut count, entry_size);
+        if found == u32::MAX {
+            return Vec::new();
+        }
+        entries.truncate(found as usize);
+        entries
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let kind = if entry.dwType == RIM_TYPEKEYBOARD {
+                InputDeviceKind::Keyboard
+            } else if entry.dwType == RIM_TYPEMOUSE {
+                InputDeviceKind::Mouse
+            } else {
+                return None;
+            };
+            raw_input_device(entry.hDevice, kind)
+        })
+        .collect()
+}
+
+/// BTN_JOYSTICK/BTN_GAMEPAD block and the BTN_TRIGGER_HAPPY extension used by button boxes.
+#[cfg(target_os = "linux")]
+pub fn is_gamepad_button(code: u16) -> bool {
+    (0x120..=0x13f).contains(&code) || (0x2c0..=0x2e7).contains(&code)
+}
+
+#[cfg(target_os = "linux")]
+pub fn is_linux_gamepad(device: &evdev::Device) -> bool {
+    use evdev::KeyCode;
+
+    device.supported_keys().is_some_and(|keys| {
+        !keys.contains(KeyCode::KEY_A) && keys.iter().any(|key| is_gamepad_button(key.code()))
+    })
+}
+
+/// Only keyboards, mice and gamepads are worth a reader thread; skip power buttons, lid switches etc.
+#[cfg(target_os = "linux")]
+pub fn is_linux_input_candidate(device: &evdev::Device) -> bool {
+    use evdev::KeyCode;
+
+    let has_keys = device.supported_keys().is_some_and(|keys| {
+        keys.contains(KeyCode::KEY_A)
+            || keys.contains(KeyCode::KEY_SPACE)
+            || keys.contains(KeyCode::BTN_LEFT)
+    });
+    has_keys || is_linux_gamepad(device)
+}
+
+#[cfg(target_os = "linux")]
+fn linux_device_kind(device: &evdev::Device) -> InputDeviceKind {
+    use evdev::KeyCode;
+
+    if is_linux_gamepad(device) {
+        return InputDeviceKind::Gamepad;
+    }
+    let keyboard = device
+        .supported_keys()
+        .is_some_and(|keys| keys.contains(KeyCode::KEY_A) || keys.contains(KeyCode::KEY_SPACE));
+    if keyboard {
+        InputDeviceKind::Keyboard
+    } else {
+        InputDeviceKind::Mouse
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn evdev_device(device: &evdev::Device) -> InputDeviceInfo {
+    let kind = linux_device_kind(device);
+    let input_id = device.input_id();
+    let mut id = format!(
+        "{:04x}:{:04x}:{:04x}",
+        input_id.bus_type().0,
+        input_id.vendor(),
+        input_id.product()
+    );
+    if let Some(phys) = device.physical_path().filter(|phys| !phys.is_empty()) {
+        id.push('/');
+        id.push_str(phys);
+    }
+    let name = device
+        .name()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .unwrap_or(kind_label(kind))
+        .to_string();
+    let synthetic = input_id.bus_type() == evdev::BusType::BUS_VIRTUAL;
+    InputDeviceInfo {
+        id,
+        name,
+        kind,
+        synthetic,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn list_evdev_devices() -> Vec<InputDeviceInfo> {
+    let Ok(entries) = std::fs::read_dir("/dev/input") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with("event"))
+        })
+        // Nodes we may not open cannot be captured either, so they are not listed.
+        .filter_map(|entry| evdev::Device::open(entry.path()).ok())
+        .filter(is_linux_input_candidate)
+        .map(|device| evdev_device(&device))
+        .collect()
+}
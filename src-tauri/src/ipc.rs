@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use crate::ime::ImeState;
+use crate::models::ShortcutsState;
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum InputDeviceKind {
@@ -24,6 +27,90 @@ pub enum DaemonCommand {
     ToggleOverlayLock,
     /// Toggle always-on-top (global shortcut)
     ToggleAlwaysOnTop,
+    /// Switch to the next key mode (built-in modes, then custom tabs)
+    CycleKeyMode,
+    /// Switch to a specific key mode
+    SetKeyMode { mode: String },
+    /// Reset the counters of the current key mode
+    ResetModeCounters,
+    /// Toggle key counter display
+    ToggleKeyCounter,
+    /// Pause or resume key event forwarding
+    ToggleCapturePause,
+    /// Toggle the canvas side panel in the main window
+    ToggleSettingsPanel,
+    /// Canvas zoom in the main window
+    ZoomIn,
+    ZoomOut,
+    ResetZoom,
+    /// The focused app's input language or IME conversion mode changed
+    ImeChanged { state: ImeState },
+    /// Reply to [`ControlMessage::Ping`]
+    Pong { nonce: u64 },
+}
+
+/// Control messages from the app to the keyboard daemon (sent on the daemon's stdin).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ControlMessage {
+    /// Replace the global hotkey bindings without restarting the daemon.
+    SetHotkeys { shortcuts: Box<ShortcutsState> },
+    /// Stop forwarding key events; global hotkeys keep working.
+    Pause,
+    /// Resume forwarding key events.
+    Resume,
+    /// Choose which device kinds are forwarded.
+    SetFilters { filters: CaptureFilters },
+    /// Forward only events whose code or one of whose labels is listed; `None` forwards everything.
+    /// Global hotkeys are matched before this filter and keep working.
+    SetKeyAllowlist { keys: Option<Vec<String>> },
+    /// Health check; the daemon answers with [`DaemonCommand::Pong`].
+    Ping { nonce: u64 },
+}
+
+/// Device kinds the daemon forwards to the app.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureFilters {
+    #[serde(default = "default_true")]
+    pub keyboard: bool,
+    #[serde(default = "default_true")]
+    pub mouse: bool,
+    #[serde(default = "default_true")]
+    pub gamepad: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for CaptureFilters {
+    fn default() -> Self {
+        Self {
+            keyboard: true,
+            mouse: true,
+            gamepad: true,
+        }
+    }
+}
+
+/// What the daemon currently forwards; sent with `daemon:capture-changed`.
+#[derive(Debug, Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureState {
+    pub paused: bool,
+    pub filters: CaptureFilters,
+}
+
+impl CaptureFilters {
+    pub fn allows(&self, device: InputDeviceKind) -> bool {
+        match device {
+            InputDeviceKind::Keyboard => self.keyboard,
+            InputDeviceKind::Mouse => self.mouse,
+            InputDeviceKind::Gamepad => self.gamepad,
+            InputDeviceKind::Unknown => true,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -32,6 +119,10 @@ pub struct HookMessage {
     #[serde(default = "default_device_kind")]
     pub device: InputDeviceKind,
     pub labels: Vec<String>,
+    /// Platform-neutral id of the key, see [`crate::key_codes`]. `None` for keys without one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub code: Option<String>,
     pub state: HookKeyState,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
@@ -42,6 +133,27 @@ pub struct HookMessage {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub flags: Option<u32>,
+    /// Capture time from [`crate::clock::monotonic_us`], stamped by the daemon.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub timestamp_us: Option<u64>,
+    /// Stable id of the physical device, see [`crate::input_devices::InputDeviceInfo`].
+    /// `None` when the backend cannot tell devices apart (macOS, injected input).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub device_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub device_name: Option<String>,
+    /// Generated by software (SendInput, uinput) rather than a physical device.
+    /// Only detected where the backend can tell; always `false` on macOS.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    #[serde(default)]
+    pub injected: bool,
+    /// Auto-repeat of a key that is already held.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    #[serde(default)]
+    pub repeat: bool,
 }
 
 #[repr(u8)]
@@ -0,0 +1,263 @@
+//! Platform-neutral key identifiers.
+//!
+//! Backends describe a key with legacy labels (`LEFT SHIFT`, `DOT`, or a bare virtual key
+//! number such as `25` for right Ctrl). Those labels were never meant to be stored: some are
+//! Windows VK numbers, and the set a key gets differs between backends. Every key event also
+//! carries one canonical id in W3C `KeyboardEvent.code` style (`ShiftLeft`, `Period`,
+//! `ControlRight`), and key mappings are stored with those ids.
+//!
+//! Mouse buttons, wheel notches and gamepad inputs (`MOUSE1`, `WHEEL UP`, `PAD0 BUTTON 3`) have
+//! no `KeyboardEvent.code`; their labels are already the same on every backend and serve as
+//! their canonical ids.
+
+use crate::models::{KeyCounters, KeyMappings};
+
+/// Legacy label -> canonical id. Where a key emits several labels, the first one it emits is
+/// listed first; the extra aliases only matter when migrating stored mappings.
+const LEGACY_CODES: &[(&str, &str)] = &[
+    ("A", "KeyA"),
+    ("B", "KeyB"),
+    ("C", "KeyC"),
+    ("D", "KeyD"),
+    ("E", "KeyE"),
+    ("F", "KeyF"),
+    ("G", "KeyG"),
+    ("H", "KeyH"),
+    ("I", "KeyI"),
+    ("J", "KeyJ"),
+    ("K", "KeyK"),
+    ("L", "KeyL"),
+    ("M", "KeyM"),
+    ("N", "KeyN"),
+    ("O", "KeyO"),
+    ("P", "KeyP"),
+    ("Q", "KeyQ"),
+    ("R", "KeyR"),
+    ("S", "KeyS"),
+    ("T", "KeyT"),
+    ("U", "KeyU"),
+    ("V", "KeyV"),
+    ("W", "KeyW"),
+    ("X", "KeyX"),
+    ("Y", "KeyY"),
+    ("Z", "KeyZ"),
+    ("0", "Digit0"),
+    ("1", "Digit1"),
+    ("2", "Digit2"),
+    ("3", "Digit3"),
+    ("4", "Digit4"),
+    ("5", "Digit5"),
+    ("6", "Digit6"),
+    ("7", "Digit7"),
+    ("8", "Digit8"),
+    ("9", "Digit9"),
+    ("LEFT SHIFT", "ShiftLeft"),
+    ("RIGHT SHIFT", "ShiftRight"),
+    ("LEFT CTRL", "ControlLeft"),
+    // VK_HANJA shares the right Ctrl key on Korean layouts.
+    ("25", "ControlRight"),
+    ("RIGHT CTRL", "ControlRight"),
+    ("LEFT ALT", "AltLeft"),
+    // VK_HANGUL shares the right Alt key on Korean layouts.
+    ("21", "AltRight"),
+    ("RIGHT ALT", "AltRight"),
+    ("91", "MetaLeft"),
+    ("LEFT WINDOWS", "MetaLeft"),
+    ("92", "MetaRight"),
+    ("RIGHT WINDOWS", "MetaRight"),
+    ("FN", "Fn"),
+    ("SPACE", "Space"),
+    ("RETURN", "Enter"),
+    ("TAB", "Tab"),
+    ("BACKSPACE", "Backspace"),
+    ("CAPS LOCK", "CapsLock"),
+    ("ESCAPE", "Escape"),
+    ("UP ARROW", "ArrowUp"),
+    ("DOWN ARROW", "ArrowDown"),
+    ("LEFT ARROW", "ArrowLeft"),
+    ("RIGHT ARROW", "ArrowRight"),
+    ("HOME", "Home"),
+    ("END", "End"),
+    ("35", "End"),
+    ("PAGE UP", "PageUp"),
+    ("PAGE DOWN", "PageDown"),
+    ("INS", "Insert"),
+    ("DELETE", "Delete"),
+    ("PRINT SCREEN", "PrintScreen"),
+    ("SCROLL LOCK", "ScrollLock"),
+    ("PAUSE", "Pause"),
+    ("19", "Pause"),
+    ("CONTEXT MENU", "ContextMenu"),
+    ("APPS", "ContextMenu"),
+    ("93", "ContextMenu"),
+    // Korean and Japanese IME keys. Mac keyboards put 英数/かな where Korean ones have 한자/한/영.
+    ("HANGUL", "Lang1"),
+    ("JIS KANA", "Lang1"),
+    ("HANJA", "Lang2"),
+    ("EISU", "Lang2"),
+    ("CONVERT", "Convert"),
+    ("NONCONVERT", "NonConvert"),
+    ("KANA", "KanaMode"),
+    // 半角/全角 is the key at the Backquote position.
+    ("ZENKAKU HANKAKU", "Backquote"),
+    ("NUM LOCK", "NumLock"),
+    ("MINUS", "Minus"),
+    ("-", "Minus"),
+    ("EQUALS", "Equal"),
+    ("=", "Equal"),
+    ("SQUARE BRACKET OPEN", "BracketLeft"),
+    ("SQUARE BRACKET CLOSE", "BracketRight"),
+    ("SEMICOLON", "Semicolon"),
+    ("QUOTE", "Quote"),
+    ("SECTION", "Backquote"),
+    ("GRAVE", "Backquote"),
+    ("BACKSLASH", "Backslash"),
+    // VK_OEM_102, the extra key next to left Shift on ISO keyboards.
+    ("226", "IntlBackslash"),
+    // JIS keys.
+    ("RO", "IntlRo"),
+    ("YEN", "IntlYen"),
+    ("COMMA", "Comma"),
+    ("DOT", "Period"),
+    ("PERIOD", "Period"),
+    ("FORWARD SLASH", "Slash"),
+    ("/", "Slash"),
+    ("NUMPAD 0", "Numpad0"),
+    ("NUMPAD 1", "Numpad1"),
+    ("NUMPAD 2", "Numpad2"),
+    ("NUMPAD 3", "Numpad3"),
+    ("NUMPAD 4", "Numpad4"),
+    ("NUMPAD 5", "Numpad5"),
+    ("NUMPAD 6", "Numpad6"),
+    ("NUMPAD 7", "Numpad7"),
+    ("NUMPAD 8", "Numpad8"),
+    ("NUMPAD 9", "Numpad9"),
+    ("NUMPAD PLUS", "NumpadAdd"),
+    ("+", "NumpadAdd"),
+    ("NUMPAD MINUS", "NumpadSubtract"),
+    ("NUMPAD MULTIPLY", "NumpadMultiply"),
+    ("*", "NumpadMultiply"),
+    ("NUMPAD DIVIDE", "NumpadDivide"),
+    ("NUMPAD DELETE", "NumpadDecimal"),
+    ("DECIMAL", "NumpadDecimal"),
+    ("NUMPAD RETURN", "NumpadEnter"),
+    ("NUMPAD SEPARATOR", "NumpadComma"),
+];
+
+/// Canonical id for one legacy label, if it names a keyboard key.
+pub fn code_for_label(label: &str) -> Option<&'static str> {
+    if let Some((_, code)) = LEGACY_CODES.iter().find(|(legacy, _)| *legacy == label) {
+        return Some(code);
+    }
+    // F1..F24 keep their names.
+    let number = label.strip_prefix('F')?.parse::<u8>().ok()?;
+    (1..=24).contains(&number).then(|| {
+        const F_KEYS: [&str; 24] = [
+            "F1", "F2", "F3", "F4", "F5", "F6", "F7", "F8", "F9", "F10", "F11", "F12", "F13",
+            "F14", "F15", "F16", "F17", "F18", "F19", "F20", "F21", "F22", "F23", "F24",
+        ];
+        F_KEYS[number as usize - 1]
+    })
+}
+
+/// Inputs that are not keyboard keys and already use the same label everywhere.
+fn is_neutral_label(label: &str) -> bool {
+    (label.starts_with("MOUSE") && label[5..].chars().all(|c| c.is_ascii_digit()))
+        || label.starts_with("WHEEL ")
+        || label.starts_with("PAD")
+}
+
+/// Canonical id for an event given the labels its backend produced, or `None` for keys this
+/// table does not know (their bare VK numbers are platform specific).
+pub fn canonical_id(labels: &[String]) -> Option<String> {
+    if let Some(code) = labels.iter().find_map(|label| code_for_label(label)) {
+        return Some(code.to_string());
+    }
+    labels
+        .first()
+        .filter(|label| is_neutral_label(label))
+        .cloned()
+}
+
+/// Rewrites legacy labels in stored mappings to canonical ids.
+pub fn migrate_mappings(mappings: &mut KeyMappings) {
+    for key in mappings.values_mut().flatten() {
+        if let Some(code) = code_for_label(key) {
+            *key = code.to_string();
+        }
+    }
+}
+
+/// Moves counts stored under legacy labels to the canonical id of the same key.
+pub fn migrate_counters(counters: &mut KeyCounters) {
+    for counts in counters.values_mut() {
+        let legacy: Vec<(String, &'static str)> = counts
+            .keys()
+            .filter_map(|key| code_for_label(key).map(|code| (key.clone(), code)))
+            .collect();
+        for (key, code) in legacy {
+            if let Some(count) = counts.remove(&key) {
+                let total = counts.entry(code.to_string()).or_insert(0);
+                *total = total.saturating_add(count);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn labels(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn backends_agree_on_canonical_ids() {
+        // Windows/Linux right Ctrl and the macOS name for the same key.
+        assert_eq!(
+            canonical_id(&labels(&["25", "RIGHT CTRL"])).as_deref(),
+            Some("ControlRight")
+        );
+        assert_eq!(canonical_id(&labels(&["91"])).as_deref(), Some("MetaLeft"));
+        assert_eq!(
+            canonical_id(&labels(&["RETURN", "NUMPAD RETURN"])).as_deref(),
+            Some("Enter")
+        );
+        // The Korean 한/영 key and the Mac かな key share a code; 半角/全角 is at Backquote.
+        assert_eq!(canonical_id(&labels(&["HANGUL"])).as_deref(), Some("Lang1"));
+        assert_eq!(canonical_id(&labels(&["JIS KANA"])).as_deref(), Some("Lang1"));
+        assert_eq!(
+            canonical_id(&labels(&["ZENKAKU HANKAKU"])).as_deref(),
+            Some("Backquote")
+        );
+        assert_eq!(canonical_id(&labels(&["F13"])).as_deref(), Some("F13"));
+        assert_eq!(canonical_id(&labels(&["MOUSE4"])).as_deref(), Some("MOUSE4"));
+        assert_eq!(
+            canonical_id(&labels(&["PAD0 BUTTON 3"])).as_deref(),
+            Some("PAD0 BUTTON 3")
+        );
+        assert_eq!(canonical_id(&labels(&["173"])), None);
+    }
+
+    #[test]
+    fn migration_rewrites_mappings_and_merges_counters() {
+        let mut mappings: KeyMappings = HashMap::from([(
+            "4key".to_string(),
+            labels(&["LEFT SHIFT", "DOT", "KeyZ", "MOUSE1", "173"]),
+        )]);
+        migrate_mappings(&mut mappings);
+        assert_eq!(
+            mappings["4key"],
+            labels(&["ShiftLeft", "Period", "KeyZ", "MOUSE1", "173"])
+        );
+
+        let mut counters: KeyCounters = HashMap::from([(
+            "4key".to_string(),
+            HashMap::from([("DOT".to_string(), 3), ("Period".to_string(), 2)]),
+        )]);
+        migrate_counters(&mut counters);
+        assert_eq!(counters["4key"], HashMap::from([("Period".to_string(), 5)]));
+    }
+}
@@ -2,25 +2,35 @@ use std::{collections::HashSet, sync::Arc};
 
 use parking_lot::RwLock;
 
-use crate::models::KeyMappings;
+use crate::models::{KeyMappings, KeyModeDevices};
 
 #[derive(Clone)]
 pub struct KeyboardManager {
     mappings: Arc<RwLock<KeyMappings>>,
     current_mode: Arc<RwLock<String>>,
     valid_keys: Arc<RwLock<HashSet<String>>>,
+    device_filters: Arc<RwLock<KeyModeDevices>>,
+    /// Devices accepted in the current mode; `None` accepts every device.
+    allowed_devices: Arc<RwLock<Option<HashSet<String>>>>,
 }
 
 impl KeyboardManager {
-    pub fn new(initial: KeyMappings, default_mode: impl Into<String>) -> Self {
+    pub fn new(
+        initial: KeyMappings,
+        device_filters: KeyModeDevices,
+        default_mode: impl Into<String>,
+    ) -> Self {
         let mappings = Arc::new(RwLock::new(initial));
         let current_mode = Arc::new(RwLock::new(default_mode.into()));
         let manager = Self {
             mappings,
             current_mode,
             valid_keys: Arc::new(RwLock::new(HashSet::new())),
+            device_filters: Arc::new(RwLock::new(device_filters)),
+            allowed_devices: Arc::new(RwLock::new(None)),
         };
         manager.rebuild_valid_keys();
+        manager.rebuild_allowed_devices();
         manager
     }
 
@@ -29,12 +39,18 @@ impl KeyboardManager {
         self.rebuild_valid_keys();
     }
 
+    pub fn update_device_filters(&self, filters: KeyModeDevices) {
+        *self.device_filters.write() = filters;
+        self.rebuild_allowed_devices();
+    }
+
     pub fn set_mode(&self, mode: impl Into<String>) -> bool {
         let mode = mode.into();
         let exists = self.mappings.read().contains_key(&mode);
         if exists {
             *self.current_mode.write() = mode;
             self.rebuild_valid_keys();
+            self.rebuild_allowed_devices();
         }
         exists
     }
@@ -43,10 +59,18 @@ impl KeyboardManager {
         self.current_mode.read().clone()
     }
 
+    /// Input from a device outside the current mode's filter never matches. Input without a
+    /// device id is rejected too while a filter is set, since its source cannot be checked.
     pub fn match_candidate<'a>(
         &self,
         candidates: impl IntoIterator<Item = &'a str>,
+        device_id: Option<&str>,
     ) -> Option<String> {
+        if let Some(allowed) = self.allowed_devices.read().as_ref() {
+            if !device_id.is_some_and(|id| allowed.contains(id)) {
+                return None;
+            }
+        }
         let guard = self.valid_keys.read();
         for candidate in candidates {
             if guard.contains(candidate) {
@@ -66,4 +90,13 @@ impl KeyboardManager {
             guard.insert(key);
         }
     }
+
+    fn rebuild_allowed_devices(&self) {
+        let filters = self.device_filters.read();
+        let mode = self.current_mode.read();
+        *self.allowed_devices.write() = filters
+            .get(mode.as_str())
+            .filter(|devices| !devices.is_empty())
+            .map(|devices| devices.iter().cloned().collect());
+    }
 }
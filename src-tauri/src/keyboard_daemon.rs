@@ -1,29 +1,48 @@
+use std::collections::HashSet;
 use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
 
-use anyhow::{anyhow, Result};
-use serde_json::to_string;
+use parking_lot::Mutex;
 
-use crate::ipc::{DaemonCommand, HookKeyState, HookMessage, InputDeviceKind};
-use crate::models::{ShortcutBinding, ShortcutsState};
+use anyhow::{anyhow, Result};
 
-fn load_hotkeys_from_env() -> ShortcutsState {
-    std::env::var("DMNOTE_HOTKEYS_V1")
-        .ok()
-        .and_then(|value| serde_json::from_str::<ShortcutsState>(&value).ok())
-        .unwrap_or_default()
-}
+use crate::clock::monotonic_us;
+use crate::ipc::{
+    CaptureFilters, ControlMessage, DaemonCommand, HookKeyState, HookMessage, InputDeviceKind,
+};
+use crate::hotkeys::{global_bindings, HotkeyMatcher, Modifiers};
+use crate::input_devices::InputDeviceInfo;
+use crate::key_codes;
+use crate::models::ShortcutsState;
+use crate::protocol::{self, Frame};
+use crate::replay::{self, ReplaySource};
 
 #[cfg(target_os = "windows")]
-use crate::{
-    ipc::pipe_client_connect,
-    keyboard_labels::{
-        build_key_labels, should_skip_keyboard_event, IsKeyboardEventInjected, KeyboardEvent,
-        KeyboardKey, KeyPress,
-    },
+use crate::ipc::pipe_client_connect;
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+use crate::ime::ImeWatcher;
+
+#[cfg(target_os = "linux")]
+use crate::input_devices::{
+    evdev_device, is_gamepad_button, is_linux_gamepad, is_linux_input_candidate,
+};
+#[cfg(target_os = "linux")]
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc,
+};
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+use crate::keyboard_labels::{
+    build_key_labels, should_skip_keyboard_event, IsKeyboardEventInjected, KeyboardEvent,
+    KeyboardKey, KeyPress,
 };
 
 /// Global hotkey state tracker
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 struct HotkeyState {
     ctrl_left: bool,
     ctrl_right: bool,
@@ -33,14 +52,12 @@ struct HotkeyState {
     alt_right: bool,
     meta_left: bool,
     meta_right: bool,
-    toggle_overlay: Option<ParsedHotkey>,
-    toggle_overlay_lock: Option<ParsedHotkey>,
-    toggle_always_on_top: Option<ParsedHotkey>,
+    matcher: HotkeyMatcher<u32>,
 }
 
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 impl HotkeyState {
-    fn new(toggle_overlay: ShortcutBinding, toggle_overlay_lock: ShortcutBinding, toggle_always_on_top: ShortcutBinding) -> Self {
+    fn new(hotkeys: &ShortcutsState) -> Self {
         Self {
             ctrl_left: false,
             ctrl_right: false,
@@ -50,12 +67,15 @@ impl HotkeyState {
             alt_right: false,
             meta_left: false,
             meta_right: false,
-            toggle_overlay: ParsedHotkey::from_binding(&toggle_overlay),
-            toggle_overlay_lock: ParsedHotkey::from_binding(&toggle_overlay_lock),
-            toggle_always_on_top: ParsedHotkey::from_binding(&toggle_always_on_top),
+            matcher: HotkeyMatcher::new(global_bindings(hotkeys), vk_from_key_code),
         }
     }
 
+    /// Swap in bindings pushed by the app, keeping the current modifier and held-key state.
+    fn rebind(&mut self, hotkeys: ShortcutsState) {
+        self.matcher.rebind(global_bindings(&hotkeys), vk_from_key_code);
+    }
+
     /// Update modifier state and check for hotkey triggers
     /// Returns Some(command) if a hotkey was triggered
     fn update(&mut self, vk_code: u32, is_down: bool) -> Option<DaemonCommand> {
@@ -78,77 +98,29 @@ impl HotkeyState {
             VK_RMENU => self.alt_right = is_down,
             VK_LWIN => self.meta_left = is_down,
             VK_RWIN => self.meta_right = is_down,
-            _ => {}
-        }
-
-        if !is_down {
-            return None;
-        }
-
-        let ctrl = self.ctrl_left || self.ctrl_right;
-        let shift = self.shift_left || self.shift_right;
-        let alt = self.alt_left || self.alt_right;
-        let meta = self.meta_left || self.meta_right;
-
-        let matches = |hotkey: &ParsedHotkey| {
-            vk_code == hotkey.key_vk
-                && ctrl == hotkey.ctrl
-                && shift == hotkey.shift
-                && alt == hotkey.alt
-                && meta == hotkey.meta
-        };
-
-        if let Some(hk) = self.toggle_overlay.as_ref() {
-            if matches(hk) {
-                return Some(DaemonCommand::ToggleOverlay);
-            }
-        }
-        if let Some(hk) = self.toggle_overlay_lock.as_ref() {
-            if matches(hk) {
-                return Some(DaemonCommand::ToggleOverlayLock);
-            }
-        }
-        if let Some(hk) = self.toggle_always_on_top.as_ref() {
-            if matches(hk) {
-                return Some(DaemonCommand::ToggleAlwaysOnTop);
+            _ => {
+                let modifiers = Modifiers {
+                    ctrl: self.ctrl_left || self.ctrl_right,
+                    shift: self.shift_left || self.shift_right,
+                    alt: self.alt_left || self.alt_right,
+                    meta: self.meta_left || self.meta_right,
+                };
+                return self
+                    .matcher
+                    .update(vk_code, is_down, modifiers, Instant::now());
             }
         }
-
         None
     }
 }
 
-#[cfg(target_os = "windows")]
-#[derive(Debug, Clone)]
-struct ParsedHotkey {
-    key_vk: u32,
-    ctrl: bool,
-    shift: bool,
-    alt: bool,
-    meta: bool,
-}
-
-#[cfg(target_os = "windows")]
-impl ParsedHotkey {
-    fn from_binding(binding: &ShortcutBinding) -> Option<Self> {
-        let key_vk = vk_from_key_code(&binding.key)?;
-        Some(Self {
-            key_vk,
-            ctrl: binding.ctrl,
-            shift: binding.shift,
-            alt: binding.alt,
-            meta: binding.meta,
-        })
-    }
-}
-
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 fn vk_from_key_code(code: &str) -> Option<u32> {
     // Uses KeyboardEvent.code-style keys (e.g., KeyO, Tab, Digit1).
     if let Some(rest) = code.strip_prefix("Key") {
         if rest.len() == 1 {
             let ch = rest.chars().next()?.to_ascii_uppercase();
-            if ('A'..='Z').contains(&ch) {
+            if ch.is_ascii_uppercase() {
                 return Some(ch as u32);
             }
         }
@@ -157,7 +129,7 @@ fn vk_from_key_code(code: &str) -> Option<u32> {
     if let Some(rest) = code.strip_prefix("Digit") {
         if rest.len() == 1 {
             let ch = rest.chars().next()?;
-            if ('0'..='9').contains(&ch) {
+            if ch.is_ascii_digit() {
                 return Some(ch as u32);
             }
         }
@@ -213,20 +185,13 @@ struct MacHotkeyState {
     alt_right: bool,
     meta_left: bool,
     meta_right: bool,
-    toggle_overlay_key: String,
-    toggle_overlay: ShortcutBinding,
-    toggle_overlay_lock_key: String,
-    toggle_overlay_lock: ShortcutBinding,
-    toggle_always_on_top_key: String,
-    toggle_always_on_top: ShortcutBinding,
+    /// Keys are lowercased key names.
+    matcher: HotkeyMatcher<String>,
 }
 
 #[cfg(target_os = "macos")]
 impl MacHotkeyState {
-    fn new(toggle_overlay: ShortcutBinding, toggle_overlay_lock: ShortcutBinding, toggle_always_on_top: ShortcutBinding) -> Self {
-        let toggle_overlay_key = toggle_overlay.key.to_ascii_lowercase();
-        let toggle_overlay_lock_key = toggle_overlay_lock.key.to_ascii_lowercase();
-        let toggle_always_on_top_key = toggle_always_on_top.key.to_ascii_lowercase();
+    fn new(hotkeys: &ShortcutsState) -> Self {
         Self {
             ctrl_left: false,
             ctrl_right: false,
@@ -236,15 +201,19 @@ impl MacHotkeyState {
             alt_right: false,
             meta_left: false,
             meta_right: false,
-            toggle_overlay_key,
-            toggle_overlay,
-            toggle_overlay_lock_key,
-            toggle_overlay_lock,
-            toggle_always_on_top_key,
-            toggle_always_on_top,
+            matcher: HotkeyMatcher::new(global_bindings(hotkeys), Self::parse_key),
         }
     }
 
+    fn parse_key(code: &str) -> Option<String> {
+        Some(code.to_ascii_lowercase())
+    }
+
+    /// Swap in bindings pushed by the app, keeping the current modifier and held-key state.
+    fn rebind(&mut self, hotkeys: ShortcutsState) {
+        self.matcher.rebind(global_bindings(&hotkeys), Self::parse_key);
+    }
+
     fn update(&mut self, key_name: &str, is_down: bool) -> Option<DaemonCommand> {
         match key_name {
             "controlleft" | "controlright" => {
@@ -275,37 +244,21 @@ impl MacHotkeyState {
                     self.meta_left = is_down;
                 }
             }
-            _ => {}
-        }
-
-        if !is_down {
-            return None;
-        }
-
-        let ctrl = self.ctrl_left || self.ctrl_right;
-        let shift = self.shift_left || self.shift_right;
-        let alt = self.alt_left || self.alt_right;
-        let meta = self.meta_left || self.meta_right;
-
-        let matches = |key: &str, binding: &ShortcutBinding| {
-            !key.trim().is_empty()
-                && key_name == key
-                && ctrl == binding.ctrl
-                && shift == binding.shift
-                && alt == binding.alt
-                && meta == binding.meta
-        };
-
-        if matches(&self.toggle_overlay_key, &self.toggle_overlay) {
-            return Some(DaemonCommand::ToggleOverlay);
-        }
-        if matches(&self.toggle_overlay_lock_key, &self.toggle_overlay_lock) {
-            return Some(DaemonCommand::ToggleOverlayLock);
-        }
-        if matches(&self.toggle_always_on_top_key, &self.toggle_always_on_top) {
-            return Some(DaemonCommand::ToggleAlwaysOnTop);
+            _ => {
+                let modifiers = Modifiers {
+                    ctrl: self.ctrl_left || self.ctrl_right,
+                    shift: self.shift_left || self.shift_right,
+                    alt: self.alt_left || self.alt_right,
+                    meta: self.meta_left || self.meta_right,
+                };
+                return self.matcher.update(
+                    key_name.to_string(),
+                    is_down,
+                    modifiers,
+                    Instant::now(),
+                );
+            }
         }
-
         None
     }
 }
@@ -442,6 +395,20 @@ fn labels_from_key_name(name: &str) -> Vec<String> {
 
 #[cfg(target_os = "macos")]
 fn mac_key_labels(key: rdev::Key, name_hint: Option<&str>) -> Vec<String> {
+    // JIS keys have no rdev name, and the character hint would make ろ and ¥ look like
+    // the ANSI keys that type the same character.
+    if let rdev::Key::Unknown(code) = key {
+        let label = match code {
+            102 => Some("EISU"),
+            104 => Some("JIS KANA"),
+            93 => Some("YEN"),
+            94 => Some("RO"),
+            _ => None,
+        };
+        if let Some(label) = label {
+            return vec![label.to_string()];
+        }
+    }
     if let Some(name) = name_hint {
         if let Some(labels) = labels_from_name_hint(name) {
             return labels;
@@ -464,27 +431,207 @@ fn mac_mouse_label(button: rdev::Button) -> Option<String> {
     }
 }
 
-fn write_message(
-    sink: &mut Box<dyn Write + Send>,
-    message: &HookMessage,
-) -> Result<()> {
-    let line = to_string(message)?;
-    sink.write_all(line.as_bytes())?;
-    sink.write_all(b"\n")?;
+/// State pushed by the app over the control channel and read by the capture loop.
+#[derive(Default)]
+struct DaemonControl {
+    paused: AtomicBool,
+    filters: Mutex<CaptureFilters>,
+    /// Labels that may be forwarded; `None` forwards every key.
+    allowlist: Mutex<Option<HashSet<String>>>,
+    hotkeys_changed: AtomicBool,
+    pending_hotkeys: Mutex<Option<ShortcutsState>>,
+    /// Keys forwarded as Down whose Up has not been forwarded yet.
+    held: Mutex<HashSet<String>>,
+}
+
+/// Output side of the app link, shared by the capture loop and the control thread.
+#[derive(Clone)]
+struct AppLink {
+    out: Arc<Mutex<Box<dyn Write + Send>>>,
+    control: Arc<DaemonControl>,
+}
+
+impl AppLink {
+    /// New hotkey bindings pushed since the last call, if any.
+    fn take_hotkeys(&self) -> Option<ShortcutsState> {
+        if !self.control.hotkeys_changed.swap(false, Ordering::AcqRel) {
+            return None;
+        }
+        self.control.pending_hotkeys.lock().take()
+    }
+}
+
+fn write_message(link: &AppLink, message: &HookMessage) -> Result<()> {
+    let blocked = link.control.paused.load(Ordering::Relaxed)
+        || !link.control.filters.lock().allows(message.device)
+        || link.control.allowlist.lock().as_ref().is_some_and(|allowed| {
+            !message
+                .code
+                .iter()
+                .chain(&message.labels)
+                .any(|key| allowed.contains(key))
+        });
+    let key = message.labels.first();
+    let mut held = link.control.held.lock();
+    match message.state {
+        HookKeyState::Down if blocked => return Ok(()),
+        HookKeyState::Down => {
+            if let Some(key) = key {
+                held.insert(key.clone());
+            }
+        }
+        // A key that went down before a pause, filter or allowlist change is still released,
+        // otherwise the app would show it pressed until it is pressed again.
+        HookKeyState::Up => {
+            let was_forwarded = key.is_some_and(|key| held.remove(key));
+            if blocked && !was_forwarded {
+                return Ok(());
+            }
+        }
+    }
+    drop(held);
+    protocol::write_key_event(&mut *link.out.lock(), message)?;
     Ok(())
 }
 
-fn write_command(
-    sink: &mut Box<dyn Write + Send>,
-    command: &DaemonCommand,
+fn write_command(link: &AppLink, command: &DaemonCommand) -> Result<()> {
+    protocol::write_frame(&mut *link.out.lock(), &Frame::Command(command.clone()))?;
+    Ok(())
+}
+
+/// Reports the foreground window's IME state whenever it changes. Layout and conversion
+/// mode switches raise no input the daemon sees, so the state is polled.
+#[cfg(target_os = "windows")]
+fn spawn_ime_watch(link: AppLink) -> Result<()> {
+    const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
+    std::thread::Builder::new()
+        .name("ime-watch".into())
+        .spawn(move || {
+            let mut watcher = ImeWatcher::default();
+            loop {
+                if let Some(state) = watcher.poll() {
+                    let _ = write_command(&link, &DaemonCommand::ImeChanged { state });
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        })
+        .map_err(|err| anyhow!("failed to spawn IME watch thread: {err}"))?;
+    Ok(())
+}
+
+/// Opens the output link to the app and completes the protocol handshake on it.
+/// The app's `Hello` arrives on stdin; the reply goes out on the same link as key events.
+/// The app's first control frame carries the initial hotkeys, which are returned with the link.
+/// stdin then stays open as the control channel, served by a background thread.
+fn connect_app() -> Result<(AppLink, ShortcutsState)> {
+    // Try to connect to named pipe; fall back to stdout if unavailable
+    #[cfg(target_os = "windows")]
+    let mut sink: Box<dyn Write + Send> = match pipe_client_connect("dmnote_keys_v1") {
+        Ok(file) => Box::new(file),
+        Err(_) => Box::new(std::io::stdout()),
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut sink: Box<dyn Write + Send> = Box::new(std::io::stdout());
+
+    let mut stdin = std::io::stdin().lock();
+    protocol::accept_hello(&mut stdin, &mut sink)
+        .map_err(|err| anyhow!("daemon handshake failed: {err}"))?;
+    let hotkeys = match protocol::read_frame(&mut stdin) {
+        Ok(Some(Frame::Control(ControlMessage::SetHotkeys { shortcuts }))) => *shortcuts,
+        Ok(Some(_)) => return Err(anyhow!("daemon handshake failed: expected initial hotkeys")),
+        Ok(None) => return Err(anyhow!("daemon handshake failed: control channel closed")),
+        Err(err) => return Err(anyhow!("daemon handshake failed: {err}")),
+    };
+    drop(stdin);
+
+    let link = AppLink {
+        out: Arc::new(Mutex::new(sink)),
+        control: Arc::new(DaemonControl::default()),
+    };
+    let control_link = link.clone();
+    std::thread::Builder::new()
+        .name("daemon-control".into())
+        .spawn(move || run_control_channel(control_link))
+        .map_err(|err| anyhow!("failed to spawn daemon control thread: {err}"))?;
+    Ok((link, hotkeys))
+}
+
+/// Applies control messages from the app until it closes stdin, then exits the daemon:
+/// a closed control channel means the app is gone.
+fn run_control_channel(link: AppLink) {
+    let mut stdin = std::io::stdin().lock();
+    loop {
+        let message = match protocol::read_frame(&mut stdin) {
+            Ok(Some(Frame::Control(message))) => message,
+            Ok(Some(_)) => continue,
+            Ok(None) => std::process::exit(0),
+            Err(err) => {
+                eprintln!("daemon control channel failed: {err}");
+                std::process::exit(1);
+            }
+        };
+        match message {
+            ControlMessage::SetHotkeys { shortcuts } => {
+                *link.control.pending_hotkeys.lock() = Some(*shortcuts);
+                link.control.hotkeys_changed.store(true, Ordering::Release);
+            }
+            ControlMessage::Pause => link.control.paused.store(true, Ordering::Relaxed),
+            ControlMessage::Resume => link.control.paused.store(false, Ordering::Relaxed),
+            ControlMessage::SetFilters { filters } => *link.control.filters.lock() = filters,
+            ControlMessage::SetKeyAllowlist { keys } => {
+                *link.control.allowlist.lock() = keys.map(|keys| keys.into_iter().collect());
+            }
+            ControlMessage::Ping { nonce } => {
+                let _ = write_command(&link, &DaemonCommand::Pong { nonce });
+            }
+        }
+    }
+}
+
+/// Writes one `WHEEL UP/DOWN/LEFT/RIGHT` press per notch. Positive notches mean up / right.
+/// Only `Down` is sent; the app turns each press into a short timed pulse.
+fn write_wheel(
+    link: &AppLink,
+    horizontal: bool,
+    notches: i32,
+    timestamp_us: u64,
+    source: Option<&InputDeviceInfo>,
+    injected: bool,
 ) -> Result<()> {
-    let line = to_string(command)?;
-    sink.write_all(line.as_bytes())?;
-    sink.write_all(b"\n")?;
+    let label = match (horizontal, notches > 0) {
+        (false, true) => "WHEEL UP",
+        (false, false) => "WHEEL DOWN",
+        (true, true) => "WHEEL RIGHT",
+        (true, false) => "WHEEL LEFT",
+    };
+    for _ in 0..notches.unsigned_abs() {
+        write_message(
+            link,
+            &HookMessage {
+                device: InputDeviceKind::Mouse,
+                labels: vec![label.to_string()],
+                code: Some(label.to_string()),
+                state: HookKeyState::Down,
+                vk_code: None,
+                scan_code: None,
+                flags: None,
+                timestamp_us: Some(timestamp_us),
+                device_id: source.map(|device| device.id.clone()),
+                device_name: source.map(|device| device.name.clone()),
+                injected,
+                repeat: false,
+            },
+        )?;
+    }
     Ok(())
 }
 
 pub fn run() -> Result<()> {
+    if let Some(source) = ReplaySource::from_args(std::env::args()) {
+        return run_replay(&source);
+    }
+
     #[cfg(target_os = "windows")]
     {
         return run_raw_input();
@@ -495,19 +642,60 @@ pub fn run() -> Result<()> {
         return run_macos();
     }
 
-    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[cfg(target_os = "linux")]
     {
-        Err(anyhow!("Raw input backend is only available on Windows and macOS"))
+        run_linux()
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    {
+        Err(anyhow!("Raw input backend is only available on Windows, macOS and Linux"))
+    }
+}
+
+/// Plays a recorded event file through the normal app link, keeping the original timing.
+/// Global hotkeys are not evaluated for replayed input.
+fn run_replay(source: &ReplaySource) -> Result<()> {
+    let events = replay::load_replay(&source.path)?;
+    let (link, _) = connect_app()?;
+
+    loop {
+        let started = Instant::now();
+        for event in &events {
+            let due = started + event.at.div_f64(source.speed);
+            let now = Instant::now();
+            if due > now {
+                std::thread::sleep(due - now);
+            }
+            let mut message = event.message.clone();
+            message.timestamp_us = Some(monotonic_us());
+            // Recordings made before canonical ids existed only carry labels.
+            if message.code.is_none() {
+                message.code = key_codes::canonical_id(&message.labels);
+            }
+            write_message(&link, &message)?;
+        }
+        if !source.looped || events.is_empty() {
+            break;
+        }
+    }
+
+    // Stay connected after a single pass; the control thread exits once the app closes stdin.
+    loop {
+        std::thread::park();
     }
 }
 
 #[cfg(target_os = "windows")]
 fn run_raw_input() -> Result<()> {
+    use std::collections::HashMap;
     use std::ffi::c_void;
     use std::mem::size_of;
 
     use windows::core::PCWSTR;
-    use windows::Win32::Foundation::{GetLastError, HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::Foundation::{GetLastError, HANDLE, HWND, LPARAM, LRESULT, WPARAM};
+
+    use crate::input_devices::raw_input_device;
     use windows::Win32::UI::Input::{
         GetRawInputData, RegisterRawInputDevices, HRAWINPUT, RAWINPUT, RAWINPUTDEVICE,
         RAWINPUTHEADER, RIDEV_INPUTSINK, RIDEV_NOLEGACY, RID_INPUT, RIM_TYPEKEYBOARD, RIM_TYPEMOUSE,
@@ -518,19 +706,11 @@ fn run_raw_input() -> Result<()> {
         WM_INPUT, WM_QUIT, WS_OVERLAPPEDWINDOW, PostQuitMessage, RI_KEY_BREAK, RI_KEY_E0,
     };
 
-    // Try to connect to named pipe; fall back to stdout if unavailable
-    let mut sink: Box<dyn Write + Send> = match pipe_client_connect("dmnote_keys_v1") {
-        Ok(file) => Box::new(file),
-        Err(_) => Box::new(std::io::stdout()),
-    };
+    let (link, hotkeys) = connect_app()?;
+    spawn_ime_watch(link.clone())?;
 
     // Global hotkey state tracker
-    let hotkeys = load_hotkeys_from_env();
-    let mut hotkey_state = HotkeyState::new(
-        hotkeys.toggle_overlay,
-        hotkeys.toggle_overlay_lock,
-        hotkeys.toggle_always_on_top,
-    );
+    let mut hotkey_state = HotkeyState::new(&hotkeys);
 
     // Raw Input mouse button flags (not exposed as constants in windows crate today).
     const RI_MOUSE_LEFT_BUTTON_DOWN: u16 = 0x0001;
@@ -543,9 +723,9 @@ fn run_raw_input() -> Result<()> {
     const RI_MOUSE_BUTTON_4_UP: u16 = 0x0080;
     const RI_MOUSE_BUTTON_5_DOWN: u16 = 0x0100;
     const RI_MOUSE_BUTTON_5_UP: u16 = 0x0200;
-    // Wheel constants kept for completeness but not used (wheel events disabled).
-    const _RI_MOUSE_WHEEL: u16 = 0x0400;
-    const _RI_MOUSE_HWHEEL: u16 = 0x0800;
+    const RI_MOUSE_WHEEL: u16 = 0x0400;
+    const RI_MOUSE_HWHEEL: u16 = 0x0800;
+    const WHEEL_DELTA: i32 = 120;
 
     unsafe extern "system" fn wndproc(
         hwnd: HWND,
@@ -616,10 +796,25 @@ fn run_raw_input() -> Result<()> {
         RegisterRawInputDevices(&devices, size_of::<RAWINPUTDEVICE>() as u32)
             .map_err(|e| anyhow!("RegisterRawInputDevices failed: {e}"))?;
 
+        // Sub-notch deltas from smooth-scrolling wheels, accumulated per axis (vertical, horizontal).
+        let mut wheel_accum = [0i32; 2];
+
+        // Device identity per Raw Input handle, looked up once per device.
+        let mut device_cache: HashMap<isize, Option<InputDeviceInfo>> = HashMap::new();
+        // Keys currently held, so auto-repeat Downs can be flagged (Raw Input does not mark them).
+        let mut held_keys: HashSet<u32> = HashSet::new();
+        let mut device_of = |handle: HANDLE, kind: InputDeviceKind| {
+            device_cache
+                .entry(handle.0 as isize)
+                .or_insert_with(|| raw_input_device(handle, kind))
+                .clone()
+        };
+
         // Message loop: process WM_INPUT and translate to HookMessage.
         let mut msg = MSG::default();
         while GetMessageW(&mut msg, None, 0, 0).into() {
             if msg.message == WM_INPUT {
+                let captured_at = monotonic_us();
                 // First query required buffer size.
                 let mut size: u32 = 0;
                 let header_size = size_of::<RAWINPUTHEADER>() as u32;
@@ -651,6 +846,9 @@ fn run_raw_input() -> Result<()> {
                 let raw: &RAWINPUT = &*(buffer.as_ptr() as *const RAWINPUT);
                 match raw.header.dwType {
                     t if t == RIM_TYPEKEYBOARD.0 => {
+                        let source = device_of(raw.header.hDevice, InputDeviceKind::Keyboard);
+                        // SendInput events carry no device handle.
+                        let injected = raw.header.hDevice.is_invalid();
                         let kbd = raw.data.keyboard;
                         let vkey = kbd.VKey as u32;
                         let scan_code = kbd.MakeCode as u32;
@@ -704,10 +902,19 @@ fn run_raw_input() -> Result<()> {
                         } else {
                             KeyPress::Down(false)
                         };
+                        let repeat = if is_break {
+                            held_keys.remove(&vk_norm);
+                            false
+                        } else {
+                            !held_keys.insert(vk_norm)
+                        };
 
                         // Check for global hotkeys (Ctrl+Shift+O for overlay toggle)
+                        if let Some(hotkeys) = link.take_hotkeys() {
+                            hotkey_state.rebind(hotkeys);
+                        }
                         if let Some(command) = hotkey_state.update(vk_norm, !is_break) {
-                            let _ = write_command(&mut sink, &command);
+                            let _ = write_command(&link, &command);
                             // Continue processing the key event normally
                         }
 
@@ -725,7 +932,11 @@ fn run_raw_input() -> Result<()> {
                             vk_code: Some(vk_norm),
                             scan_code: Some(scan_code),
                             flags: Some(ll_flags),
-                            is_injected: Some(IsKeyboardEventInjected::NotInjected),
+                            is_injected: Some(if injected {
+                                IsKeyboardEventInjected::Injected
+                            } else {
+                                IsKeyboardEventInjected::NotInjected
+                            }),
                         };
 
                         if should_skip_keyboard_event(&event) {
@@ -748,16 +959,24 @@ fn run_raw_input() -> Result<()> {
 
                         let message = HookMessage {
                             device: InputDeviceKind::Keyboard,
+                            code: key_codes::canonical_id(&labels),
                             labels,
                             state,
                             vk_code: event.vk_code,
                             scan_code: event.scan_code,
                             flags: event.flags,
+                            timestamp_us: Some(captured_at),
+                            device_id: source.as_ref().map(|device| device.id.clone()),
+                            device_name: source.as_ref().map(|device| device.name.clone()),
+                            injected,
+                            repeat,
                         };
 
-                        let _ = write_message(&mut sink, &message);
+                        let _ = write_message(&link, &message);
                     }
                     t if t == RIM_TYPEMOUSE.0 => {
+                        let source = device_of(raw.header.hDevice, InputDeviceKind::Mouse);
+                        let injected = raw.header.hDevice.is_invalid();
                         let mouse = raw.data.mouse;
                         let button_flags = mouse.Anonymous.Anonymous.usButtonFlags;
 
@@ -799,17 +1018,48 @@ fn run_raw_input() -> Result<()> {
 
                         for (label, state) in events {
                             let _ = write_message(
-                                &mut sink,
+                                &link,
                                 &HookMessage {
                                     device: InputDeviceKind::Mouse,
+                                    code: Some(label.clone()),
                                     labels: vec![label],
                                     state,
                                     vk_code: None,
                                     scan_code: None,
                                     flags: None,
+                                    timestamp_us: Some(captured_at),
+                                    device_id: source.as_ref().map(|device| device.id.clone()),
+                                    device_name: source.as_ref().map(|device| device.name.clone()),
+                                    injected,
+                                    repeat: false,
                                 },
                             );
                         }
+
+                        for (axis, flag) in [(0usize, RI_MOUSE_WHEEL), (1, RI_MOUSE_HWHEEL)] {
+                            if (button_flags & flag) == 0 {
+                                continue;
+                            }
+                            // usButtonData carries the signed wheel delta.
+                            let delta = mouse.Anonymous.Anonymous.usButtonData as i16 as i32;
+                            let accum = &mut wheel_accum[axis];
+                            if accum.signum() * delta.signum() < 0 {
+                                *accum = 0;
+                            }
+                            *accum += delta;
+                            let notches = *accum / WHEEL_DELTA;
+                            *accum %= WHEEL_DELTA;
+                            if notches != 0 {
+                                let _ = write_wheel(
+                                    &link,
+                                    axis == 1,
+                                    notches,
+                                    captured_at,
+                                    source.as_ref(),
+                                    injected,
+                                );
+                            }
+                        }
                     }
                     _ => {}
                 }
@@ -831,21 +1081,29 @@ fn run_raw_input() -> Result<()> {
 fn run_macos() -> Result<()> {
     use rdev::{listen, EventType};
 
-    let mut sink: Box<dyn Write + Send> = Box::new(std::io::stdout());
-    let hotkeys = load_hotkeys_from_env();
-    let mut hotkey_state = MacHotkeyState::new(
-        hotkeys.toggle_overlay,
-        hotkeys.toggle_overlay_lock,
-        hotkeys.toggle_always_on_top,
-    );
+    let (link, hotkeys) = connect_app()?;
+    let mut hotkey_state = MacHotkeyState::new(&hotkeys);
+    // The event tap reports auto-repeat as further key presses.
+    let mut held_keys: HashSet<String> = HashSet::new();
+    // Input source switches are key or menu bar clicks, so the state is checked after every
+    // release; the first check reports the state at startup.
+    let mut ime_watcher = ImeWatcher::default();
+    if let Some(state) = ime_watcher.poll() {
+        let _ = write_command(&link, &DaemonCommand::ImeChanged { state });
+    }
 
     let callback = move |event: rdev::Event| {
+        if let Some(hotkeys) = link.take_hotkeys() {
+            hotkey_state.rebind(hotkeys);
+        }
+        let captured_at = monotonic_us();
         match event.event_type {
             EventType::KeyPress(key) => {
                 let key_name = format!("{:?}", key).to_ascii_lowercase();
                 if let Some(command) = hotkey_state.update(&key_name, true) {
-                    let _ = write_command(&mut sink, &command);
+                    let _ = write_command(&link, &command);
                 }
+                let repeat = !held_keys.insert(key_name);
 
                 let labels = mac_key_labels(key, event.name.as_deref());
                 if labels.is_empty() {
@@ -854,17 +1112,28 @@ fn run_macos() -> Result<()> {
 
                 let message = HookMessage {
                     device: InputDeviceKind::Keyboard,
+                    code: key_codes::canonical_id(&labels),
                     labels,
                     state: HookKeyState::Down,
                     vk_code: None,
                     scan_code: None,
                     flags: None,
+                    timestamp_us: Some(captured_at),
+                    device_id: None,
+                    device_name: None,
+                    // The event tap does not say where an event came from.
+                    injected: false,
+                    repeat,
                 };
-                let _ = write_message(&mut sink, &message);
+                let _ = write_message(&link, &message);
             }
             EventType::KeyRelease(key) => {
                 let key_name = format!("{:?}", key).to_ascii_lowercase();
                 let _ = hotkey_state.update(&key_name, false);
+                held_keys.remove(&key_name);
+                if let Some(state) = ime_watcher.poll() {
+                    let _ = write_command(&link, &DaemonCommand::ImeChanged { state });
+                }
 
                 let labels = mac_key_labels(key, event.name.as_deref());
                 if labels.is_empty() {
@@ -873,40 +1142,71 @@ fn run_macos() -> Result<()> {
 
                 let message = HookMessage {
                     device: InputDeviceKind::Keyboard,
+                    code: key_codes::canonical_id(&labels),
                     labels,
                     state: HookKeyState::Up,
                     vk_code: None,
                     scan_code: None,
                     flags: None,
+                    timestamp_us: Some(captured_at),
+                    device_id: None,
+                    device_name: None,
+                    // The event tap does not say where an event came from.
+                    injected: false,
+                    repeat: false,
                 };
-                let _ = write_message(&mut sink, &message);
+                let _ = write_message(&link, &message);
             }
             EventType::ButtonPress(button) => {
                 if let Some(label) = mac_mouse_label(button) {
                     let _ = write_message(
-                        &mut sink,
+                        &link,
                         &HookMessage {
                             device: InputDeviceKind::Mouse,
+                            code: Some(label.clone()),
                             labels: vec![label],
                             state: HookKeyState::Down,
                             vk_code: None,
                             scan_code: None,
                             flags: None,
+                            timestamp_us: Some(captured_at),
+                            device_id: None,
+                            device_name: None,
+                            injected: false,
+                            repeat: false,
                         },
                     );
                 }
             }
+            EventType::Wheel { delta_x, delta_y } => {
+                // rdev reports whole lines per event; positive is up / right.
+                if delta_y != 0 {
+                    let _ = write_wheel(&link, false, delta_y.signum() as i32, captured_at, None, false);
+                }
+                if delta_x != 0 {
+                    let _ = write_wheel(&link, true, delta_x.signum() as i32, captured_at, None, false);
+                }
+            }
             EventType::ButtonRelease(button) => {
+                if let Some(state) = ime_watcher.poll() {
+                    let _ = write_command(&link, &DaemonCommand::ImeChanged { state });
+                }
                 if let Some(label) = mac_mouse_label(button) {
                     let _ = write_message(
-                        &mut sink,
+                        &link,
                         &HookMessage {
                             device: InputDeviceKind::Mouse,
+                            code: Some(label.clone()),
                             labels: vec![label],
                             state: HookKeyState::Up,
                             vk_code: None,
                             scan_code: None,
                             flags: None,
+                            timestamp_us: Some(captured_at),
+                            device_id: None,
+                            device_name: None,
+                            injected: false,
+                            repeat: false,
                         },
                     );
                 }
@@ -918,3 +1218,684 @@ fn run_macos() -> Result<()> {
     listen(callback).map_err(|err| anyhow!("macOS input listener failed: {err:?}"))?;
     Ok(())
 }
+
+/// Input read from a single evdev device, before label translation.
+/// Sent over the channel as a [`LinuxInput`], with its capture time and source device.
+#[cfg(target_os = "linux")]
+enum LinuxInputEvent {
+    /// Keyboard key or mouse button transition (value 0 = up, 1 = down, 2 = repeat).
+    Key { code: u16, value: i32 },
+    /// Wheel movement in notches (REL_WHEEL / REL_HWHEEL); positive is up / right.
+    Wheel { horizontal: bool, notches: i32 },
+    /// Gamepad input already translated to a `PAD<n> ...` label.
+    Gamepad { label: String, state: HookKeyState },
+}
+
+/// (capture time from `monotonic_us`, source device, event)
+#[cfg(target_os = "linux")]
+type LinuxInput = (u64, Arc<InputDeviceInfo>, LinuxInputEvent);
+
+/// Devices currently being read, plus the pad slot assigned to each gamepad.
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+struct LinuxDeviceRegistry {
+    open: HashSet<PathBuf>,
+    /// Pad slots in connection order; a slot is reused once its device is unplugged.
+    pads: Vec<Option<PathBuf>>,
+}
+
+#[cfg(target_os = "linux")]
+impl LinuxDeviceRegistry {
+    fn claim_pad(&mut self, path: &Path) -> usize {
+        if let Some(index) = self.pads.iter().position(|slot| slot.is_none()) {
+            self.pads[index] = Some(path.to_path_buf());
+            return index;
+        }
+        self.pads.push(Some(path.to_path_buf()));
+        self.pads.len() - 1
+    }
+
+    fn release(&mut self, path: &Path) {
+        self.open.remove(path);
+        for slot in self.pads.iter_mut() {
+            if slot.as_deref() == Some(path) {
+                *slot = None;
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+type SharedLinuxDevices = Arc<Mutex<LinuxDeviceRegistry>>;
+
+/// Map an evdev key code to the Windows virtual key, set-1 scan code and extended flag
+/// that `keyboard_labels::build_key_labels` expects, so every backend shares one label set.
+#[cfg(target_os = "linux")]
+fn evdev_key_to_vk(code: u16) -> Option<(u32, u32, bool)> {
+    // Letter rows keep their physical (QWERTY) order in evdev.
+    const LETTERS: [(u16, u8); 26] = [
+        (16, b'Q'), (17, b'W'), (18, b'E'), (19, b'R'), (20, b'T'), (21, b'Y'), (22, b'U'),
+        (23, b'I'), (24, b'O'), (25, b'P'), (30, b'A'), (31, b'S'), (32, b'D'), (33, b'F'),
+        (34, b'G'), (35, b'H'), (36, b'J'), (37, b'K'), (38, b'L'), (44, b'Z'), (45, b'X'),
+        (46, b'C'), (47, b'V'), (48, b'B'), (49, b'N'), (50, b'M'),
+    ];
+    if let Some((_, letter)) = LETTERS.iter().find(|(c, _)| *c == code) {
+        return Some((*letter as u32, code as u32, false));
+    }
+
+    // Evdev codes 1..=88 are the set-1 make codes, which is what Raw Input reports as MakeCode.
+    let plain = |vk: u32| Some((vk, code as u32, false));
+    let extended = |vk: u32, scan: u32| Some((vk, scan, true));
+
+    match code {
+        1 => plain(0x1B),
+        2..=10 => plain(0x31 + (code as u32 - 2)),
+        11 => plain(0x30),
+        12 => plain(0xBD),
+        13 => plain(0xBB),
+        14 => plain(0x08),
+        15 => plain(0x09),
+        26 => plain(0xDB),
+        27 => plain(0xDD),
+        28 => plain(0x0D),
+        29 => plain(0xA2),
+        39 => plain(0xBA),
+        40 => plain(0xDE),
+        41 => plain(0xC0),
+        42 => plain(0xA0),
+        43 => plain(0xDC),
+        51 => plain(0xBC),
+        52 => plain(0xBE),
+        53 => plain(0xBF),
+        54 => plain(0xA1),
+        55 => plain(0x6A),
+        56 => plain(0xA4),
+        57 => plain(0x20),
+        58 => plain(0x14),
+        59..=68 => plain(0x70 + (code as u32 - 59)),
+        69 => plain(0x90),
+        70 => plain(0x91),
+        71 => plain(0x67),
+        72 => plain(0x68),
+        73 => plain(0x69),
+        74 => plain(0x6D),
+        75 => plain(0x64),
+        76 => plain(0x65),
+        77 => plain(0x66),
+        78 => plain(0x6B),
+        79 => plain(0x61),
+        80 => plain(0x62),
+        81 => plain(0x63),
+        82 => plain(0x60),
+        83 => plain(0x6E),
+        86 => plain(0xE2),
+        87 => plain(0x7A),
+        88 => plain(0x7B),
+        // Japanese keys carry the scan codes of a JIS keyboard.
+        85 => Some((0xF3, 0x29, false)),
+        89 => Some((0xE2, 0x73, false)),
+        90 | 91 | 93 => Some((0xF2, 0x70, false)),
+        92 => Some((0x1C, 0x79, false)),
+        94 => Some((0x1D, 0x7B, false)),
+        96 => extended(0x0D, 28),
+        97 => extended(0xA3, 29),
+        98 => extended(0x6F, 53),
+        99 => extended(0x2C, 55),
+        100 => extended(0xA5, 56),
+        102 => extended(0x24, 71),
+        103 => extended(0x26, 72),
+        104 => extended(0x21, 73),
+        105 => extended(0x25, 75),
+        106 => extended(0x27, 77),
+        107 => extended(0x23, 79),
+        108 => extended(0x28, 80),
+        109 => extended(0x22, 81),
+        110 => extended(0x2D, 82),
+        111 => extended(0x2E, 83),
+        119 => Some((0x13, 69, false)),
+        122 => Some((0x15, 0x72, false)),
+        123 => Some((0x19, 0x71, false)),
+        124 => Some((0xDC, 0x7D, false)),
+        125 => extended(0x5B, 91),
+        126 => extended(0x5C, 92),
+        127 => extended(0x5D, 93),
+        183..=194 => Some((0x7C + (code as u32 - 183), 0, false)),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn linux_mouse_label(code: u16) -> Option<&'static str> {
+    match code {
+        0x110 => Some("MOUSE1"), // BTN_LEFT
+        0x111 => Some("MOUSE2"), // BTN_RIGHT
+        0x112 => Some("MOUSE3"), // BTN_MIDDLE
+        0x113 => Some("MOUSE4"), // BTN_SIDE
+        0x114 => Some("MOUSE5"), // BTN_EXTRA
+        _ => None,
+    }
+}
+
+/// Trigger axes report as pressed above this fraction of their range...
+#[cfg(target_os = "linux")]
+const GAMEPAD_TRIGGER_PRESS: f32 = 0.5;
+/// ...and released below this one, so a trigger resting near the threshold does not flicker.
+#[cfg(target_os = "linux")]
+const GAMEPAD_TRIGGER_RELEASE: f32 = 0.4;
+
+/// An analog trigger axis reported as a `PAD<n> TRIGGER LEFT/RIGHT` key with hysteresis.
+#[cfg(target_os = "linux")]
+struct LinuxTrigger {
+    axis: u16,
+    side: &'static str,
+    minimum: i32,
+    maximum: i32,
+    pressed: bool,
+}
+
+/// Per-device gamepad state: button numbering, trigger ranges and the current hat direction.
+#[cfg(target_os = "linux")]
+struct LinuxGamepad {
+    index: usize,
+    /// Supported button codes in ascending order; a button's label number is its position here.
+    buttons: Vec<u16>,
+    triggers: Vec<LinuxTrigger>,
+    hat_x: i32,
+    hat_y: i32,
+}
+
+#[cfg(target_os = "linux")]
+impl LinuxGamepad {
+    fn new(index: usize, device: &evdev::Device) -> Self {
+        use evdev::AbsoluteAxisCode;
+
+        let buttons: Vec<u16> = device
+            .supported_keys()
+            .map(|keys| {
+                keys.iter()
+                    .map(|key| key.code())
+                    .filter(|code| is_gamepad_button(*code))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut triggers: Vec<LinuxTrigger> = Vec::new();
+        if let Ok(axes) = device.get_absinfo() {
+            for (axis, info) in axes {
+                let side = match axis {
+                    AbsoluteAxisCode::ABS_Z | AbsoluteAxisCode::ABS_BRAKE => "LEFT",
+                    AbsoluteAxisCode::ABS_RZ | AbsoluteAxisCode::ABS_GAS => "RIGHT",
+                    _ => continue,
+                };
+                // Many pads use Z/RZ for the right stick; only one-sided axes behave like triggers.
+                let one_sided = matches!(
+                    axis,
+                    AbsoluteAxisCode::ABS_GAS | AbsoluteAxisCode::ABS_BRAKE
+                ) || info.minimum() == 0;
+                if !one_sided
+                    || info.maximum() <= info.minimum()
+                    || triggers.iter().any(|trigger| trigger.side == side)
+                {
+                    continue;
+                }
+                triggers.push(LinuxTrigger {
+                    axis: axis.0,
+                    side,
+                    minimum: info.minimum(),
+                    maximum: info.maximum(),
+                    pressed: false,
+                });
+            }
+        }
+
+        Self {
+            index,
+            buttons,
+            triggers,
+            hat_x: 0,
+            hat_y: 0,
+        }
+    }
+
+    fn label(&self, name: &str) -> String {
+        format!("PAD{} {}", self.index, name)
+    }
+
+    fn translate(&mut self, event: evdev::EventSummary, out: &mut Vec<(String, HookKeyState)>) {
+        use evdev::{AbsoluteAxisCode, EventSummary};
+
+        let state_of = |down: bool| {
+            if down {
+                HookKeyState::Down
+            } else {
+                HookKeyState::Up
+            }
+        };
+
+        match event {
+            EventSummary::Key(_, key, value) => {
+                let code = key.code();
+                let name = match code {
+                    0x220 => "DPAD UP".to_string(),
+                    0x221 => "DPAD DOWN".to_string(),
+                    0x222 => "DPAD LEFT".to_string(),
+                    0x223 => "DPAD RIGHT".to_string(),
+                    _ => match self.buttons.iter().position(|button| *button == code) {
+                        Some(number) => format!("BUTTON {number}"),
+                        None => return,
+                    },
+                };
+                out.push((self.label(&name), state_of(value != 0)));
+            }
+            EventSummary::AbsoluteAxis(_, axis, value) => match axis {
+                AbsoluteAxisCode::ABS_HAT0X => {
+                    let previous = std::mem::replace(&mut self.hat_x, value.signum());
+                    self.push_hat(previous, self.hat_x, "DPAD LEFT", "DPAD RIGHT", out);
+                }
+                AbsoluteAxisCode::ABS_HAT0Y => {
+                    let previous = std::mem::replace(&mut self.hat_y, value.signum());
+                    self.push_hat(previous, self.hat_y, "DPAD UP", "DPAD DOWN", out);
+                }
+                _ => {
+                    let index = self.index;
+                    let Some(trigger) = self.triggers.iter_mut().find(|t| t.axis == axis.0) else {
+                        return;
+                    };
+                    let range = (trigger.maximum - trigger.minimum) as f32;
+                    let level = (value - trigger.minimum) as f32 / range;
+                    let pressed = if trigger.pressed {
+                        level > GAMEPAD_TRIGGER_RELEASE
+                    } else {
+                        level >= GAMEPAD_TRIGGER_PRESS
+                    };
+                    if pressed != trigger.pressed {
+                        trigger.pressed = pressed;
+                        out.push((
+                            format!("PAD{} TRIGGER {}", index, trigger.side),
+                            state_of(pressed),
+                        ));
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn push_hat(
+        &self,
+        previous: i32,
+        current: i32,
+        negative: &str,
+        positive: &str,
+        out: &mut Vec<(String, HookKeyState)>,
+    ) {
+        if previous == current {
+            return;
+        }
+        let name_of = |direction: i32| if direction < 0 { negative } else { positive };
+        if previous != 0 {
+            out.push((self.label(name_of(previous)), HookKeyState::Up));
+        }
+        if current != 0 {
+            out.push((self.label(name_of(current)), HookKeyState::Down));
+        }
+    }
+}
+
+/// Reports a non-fatal backend problem on stderr, which the app's daemon supervisor captures
+/// and logs. Fatal errors are returned from [`run`] instead.
+#[cfg(target_os = "linux")]
+fn report_warning(err: &anyhow::Error) {
+    eprintln!("keyboard daemon warning: {err:#}");
+}
+
+#[cfg(target_os = "linux")]
+fn permission_denied(path: &Path) -> anyhow::Error {
+    anyhow!(
+        "permission denied opening {}; add your user to the `input` group to capture it",
+        path.display()
+    )
+}
+
+#[cfg(target_os = "linux")]
+fn spawn_linux_device_reader(
+    path: PathBuf,
+    mut device: evdev::Device,
+    source: Arc<InputDeviceInfo>,
+    mut gamepad: Option<LinuxGamepad>,
+    tx: mpsc::Sender<LinuxInput>,
+    devices: SharedLinuxDevices,
+) -> Result<()> {
+    use evdev::{EventSummary, RelativeAxisCode};
+
+    let name = format!("evdev-{}", path.display());
+    let spawned = std::thread::Builder::new().name(name).spawn(move || {
+        let mut pad_events: Vec<(String, HookKeyState)> = Vec::new();
+        // Inputs currently down on this device, released when the device goes away.
+        let mut held_codes: HashSet<u16> = HashSet::new();
+        let mut held_labels: HashSet<String> = HashSet::new();
+        'read: loop {
+            let events = match device.fetch_events() {
+                Ok(events) => events,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    // ENODEV once the device is unplugged; the scanner will pick it up again.
+                    report_warning(&anyhow!("evdev device {} closed: {err}", path.display()));
+                    break;
+                }
+            };
+            let captured_at = monotonic_us();
+            for event in events {
+                let summary = event.destructure();
+                if let Some(pad) = gamepad.as_mut() {
+                    pad.translate(summary, &mut pad_events);
+                    for (label, state) in pad_events.drain(..) {
+                        match state {
+                            HookKeyState::Down => held_labels.insert(label.clone()),
+                            HookKeyState::Up => held_labels.remove(&label),
+                        };
+                        let event = LinuxInputEvent::Gamepad { label, state };
+                        if tx.send((captured_at, source.clone(), event)).is_err() {
+                            break 'read;
+                        }
+                    }
+                } else {
+                    let event = match summary {
+                        EventSummary::Key(_, code, value) => {
+                            match value {
+                                0 => held_codes.remove(&code.code()),
+                                1 => held_codes.insert(code.code()),
+                                _ => false,
+                            };
+                            LinuxInputEvent::Key {
+                                code: code.code(),
+                                value,
+                            }
+                        }
+                        EventSummary::RelativeAxis(_, RelativeAxisCode::REL_WHEEL, notches) => {
+                            LinuxInputEvent::Wheel {
+                                horizontal: false,
+                                notches,
+                            }
+                        }
+                        EventSummary::RelativeAxis(_, RelativeAxisCode::REL_HWHEEL, notches) => {
+                            LinuxInputEvent::Wheel {
+                                horizontal: true,
+                                notches,
+                            }
+                        }
+                        _ => continue,
+                    };
+                    if tx.send((captured_at, source.clone(), event)).is_err() {
+                        break 'read;
+                    }
+                }
+            }
+        }
+        // An unplugged device never reports its releases; send them so nothing stays pressed.
+        let released_at = monotonic_us();
+        let key_ups = held_codes
+            .into_iter()
+            .map(|code| LinuxInputEvent::Key { code, value: 0 });
+        let pad_ups = held_labels
+            .into_iter()
+            .map(|label| LinuxInputEvent::Gamepad {
+                label,
+                state: HookKeyState::Up,
+            });
+        for event in key_ups.chain(pad_ups) {
+            if tx.send((released_at, source.clone(), event)).is_err() {
+                break;
+            }
+        }
+        devices.lock().release(&path);
+    });
+    spawned
+        .map(|_| ())
+        .map_err(|err| anyhow!("failed to spawn evdev reader thread: {err}"))
+}
+
+/// Open every readable `/dev/input/event*` node that is not already being read.
+/// Returns the paths that were refused with a permission error.
+#[cfg(target_os = "linux")]
+fn scan_linux_input_devices(
+    tx: &mpsc::Sender<LinuxInput>,
+    devices: &SharedLinuxDevices,
+) -> Result<Vec<PathBuf>> {
+    let mut denied = Vec::new();
+    let entries = std::fs::read_dir("/dev/input")
+        .map_err(|err| anyhow!("failed to read /dev/input: {err}"))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_event_node = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("event"));
+        if !is_event_node {
+            continue;
+        }
+        if devices.lock().open.contains(&path) {
+            continue;
+        }
+
+        let device = match evdev::Device::open(&path) {
+            Ok(device) => device,
+            Err(err) if err.kind() == std::io::ErrorKind::PermissionDenied => {
+                denied.push(path);
+                continue;
+            }
+            Err(_) => continue,
+        };
+        if !is_linux_input_candidate(&device) {
+            continue;
+        }
+
+        let gamepad = {
+            let mut guard = devices.lock();
+            guard.open.insert(path.clone());
+            if is_linux_gamepad(&device) {
+                Some(LinuxGamepad::new(guard.claim_pad(&path), &device))
+            } else {
+                None
+            }
+        };
+        let source = Arc::new(evdev_device(&device));
+        let spawned = spawn_linux_device_reader(
+            path.clone(),
+            device,
+            source,
+            gamepad,
+            tx.clone(),
+            devices.clone(),
+        );
+        if let Err(err) = spawned {
+            devices.lock().release(&path);
+            return Err(err);
+        }
+    }
+
+    Ok(denied)
+}
+
+#[cfg(target_os = "linux")]
+fn run_linux() -> Result<()> {
+    use std::time::Duration;
+
+    // evdev has no hotplug notification of its own, and udev applies group permissions
+    // only after the node appears, so a periodic rescan picks up new devices reliably.
+    const RESCAN_INTERVAL: Duration = Duration::from_secs(2);
+
+    let (link, hotkeys) = connect_app()?;
+    let mut hotkey_state = HotkeyState::new(&hotkeys);
+
+    let (tx, rx) = mpsc::channel::<LinuxInput>();
+    let devices: SharedLinuxDevices = Arc::new(Mutex::new(LinuxDeviceRegistry::default()));
+
+    let denied = scan_linux_input_devices(&tx, &devices)?;
+    let opened_any = !devices.lock().open.is_empty();
+    if !opened_any && !denied.is_empty() {
+        return Err(anyhow!(
+            "permission denied for {} input device(s) under /dev/input; add your user to the \
+             `input` group (e.g. `sudo usermod -aG input $USER`) and log in again",
+            denied.len()
+        ));
+    }
+    let mut reported: HashSet<PathBuf> = HashSet::new();
+    for path in denied {
+        report_warning(&permission_denied(&path));
+        reported.insert(path);
+    }
+
+    let scanner_tx = tx.clone();
+    let scanner_devices = devices.clone();
+    std::thread::Builder::new()
+        .name("evdev-scanner".into())
+        .spawn(move || loop {
+            std::thread::sleep(RESCAN_INTERVAL);
+            match scan_linux_input_devices(&scanner_tx, &scanner_devices) {
+                Ok(denied) => {
+                    for path in denied {
+                        if reported.insert(path.clone()) {
+                            report_warning(&permission_denied(&path));
+                        }
+                    }
+                }
+                Err(err) => report_warning(&err.context("evdev rescan failed")),
+            }
+        })
+        .map_err(|err| anyhow!("failed to spawn evdev scanner: {err}"))?;
+    drop(tx);
+
+    for (captured_at, source, event) in rx {
+        let (code, value) = match event {
+            LinuxInputEvent::Key { code, value } => (code, value),
+            LinuxInputEvent::Wheel {
+                horizontal,
+                notches,
+            } => {
+                if notches != 0 {
+                    let _ = write_wheel(
+                        &link,
+                        horizontal,
+                        notches,
+                        captured_at,
+                        Some(&*source),
+                        source.synthetic,
+                    );
+                }
+                continue;
+            }
+            LinuxInputEvent::Gamepad { label, state } => {
+                let _ = write_message(
+                    &link,
+                    &HookMessage {
+                        device: InputDeviceKind::Gamepad,
+                        code: Some(label.clone()),
+                        labels: vec![label],
+                        state,
+                        vk_code: None,
+                        scan_code: None,
+                        flags: None,
+                        timestamp_us: Some(captured_at),
+                        device_id: Some(source.id.clone()),
+                        device_name: Some(source.name.clone()),
+                        injected: source.synthetic,
+                        repeat: false,
+                    },
+                );
+                continue;
+            }
+        };
+
+        // value: 0 = release, 1 = press, 2 = auto-repeat (forwarded as a Down flagged `repeat`).
+        let is_down = value != 0;
+
+        if let Some(label) = linux_mouse_label(code) {
+            let _ = write_message(
+                &link,
+                &HookMessage {
+                    device: InputDeviceKind::Mouse,
+                    labels: vec![label.to_string()],
+                    code: Some(label.to_string()),
+                    state: if is_down {
+                        HookKeyState::Down
+                    } else {
+                        HookKeyState::Up
+                    },
+                    vk_code: None,
+                    scan_code: None,
+                    flags: None,
+                    timestamp_us: Some(captured_at),
+                    device_id: Some(source.id.clone()),
+                    device_name: Some(source.name.clone()),
+                    injected: source.synthetic,
+                    repeat: value == 2,
+                },
+            );
+            continue;
+        }
+
+        let Some((vk_code, scan_code, is_extended)) = evdev_key_to_vk(code) else {
+            continue;
+        };
+
+        if let Some(hotkeys) = link.take_hotkeys() {
+            hotkey_state.rebind(hotkeys);
+        }
+        if let Some(command) = hotkey_state.update(vk_code, is_down) {
+            let _ = write_command(&link, &command);
+        }
+
+        let keyboard_event = KeyboardEvent {
+            pressed: if is_down {
+                KeyPress::Down(false)
+            } else {
+                KeyPress::Up(false)
+            },
+            key: Some(KeyboardKey::from(vk_code)),
+            vk_code: Some(vk_code),
+            scan_code: Some(scan_code),
+            // LLKHF_EXTENDED, as in the Raw Input backend.
+            flags: Some(if is_extended { 0x01 } else { 0 }),
+            is_injected: Some(if source.synthetic {
+                IsKeyboardEventInjected::Injected
+            } else {
+                IsKeyboardEventInjected::NotInjected
+            }),
+        };
+        if should_skip_keyboard_event(&keyboard_event) {
+            continue;
+        }
+
+        let labels = build_key_labels(&keyboard_event);
+        if labels.is_empty() {
+            continue;
+        }
+
+        let state = match keyboard_event.pressed {
+            KeyPress::Down(_) => HookKeyState::Down,
+            KeyPress::Up(_) => HookKeyState::Up,
+        };
+
+        let _ = write_message(
+            &link,
+            &HookMessage {
+                device: InputDeviceKind::Keyboard,
+                code: key_codes::canonical_id(&labels),
+                labels,
+                state,
+                vk_code: keyboard_event.vk_code,
+                scan_code: keyboard_event.scan_code,
+                flags: keyboard_event.flags,
+                timestamp_us: Some(captured_at),
+                device_id: Some(source.id.clone()),
+                device_name: Some(source.name.clone()),
+                injected: source.synthetic,
+                repeat: value == 2,
+            },
+        );
+    }
+
+    Ok(())
+}
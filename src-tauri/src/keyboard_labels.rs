@@ -44,6 +44,7 @@ pub enum KeyboardKey {
     F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
     F13, F14, F15, F16, F17, F18, F19, F20, F21, F22, F23, F24,
     NumLock, ScrollLock, CapsLock,
+    Hangul, Hanja, Convert, NonConvert, Kana, ZenkakuHankaku, Eisu,
     Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7, Numpad8, Numpad9,
     Other(u32),
     InvalidKeyCodeReceived,
@@ -88,6 +89,12 @@ impl From<u32> for KeyboardKey {
             0x80 => KeyboardKey::F17, 0x81 => KeyboardKey::F18, 0x82 => KeyboardKey::F19, 0x83 => KeyboardKey::F20,
             0x84 => KeyboardKey::F21, 0x85 => KeyboardKey::F22, 0x86 => KeyboardKey::F23, 0x87 => KeyboardKey::F24,
             0x90 => KeyboardKey::NumLock, 0x91 => KeyboardKey::ScrollLock, 0x14 => KeyboardKey::CapsLock,
+            // VK_OEM_COPY is かな and VK_OEM_ATTN is 英数 while a Japanese IME is on;
+            // VK_OEM_AUTO / VK_OEM_ENLW alternate on each press of 半角/全角.
+            0x15 => KeyboardKey::Hangul, 0x19 => KeyboardKey::Hanja,
+            0x1C => KeyboardKey::Convert, 0x1D => KeyboardKey::NonConvert,
+            0xF2 => KeyboardKey::Kana, 0xF3 | 0xF4 => KeyboardKey::ZenkakuHankaku,
+            0xF0 => KeyboardKey::Eisu,
             0x60 => KeyboardKey::Numpad0, 0x61 => KeyboardKey::Numpad1, 0x62 => KeyboardKey::Numpad2,
             0x63 => KeyboardKey::Numpad3, 0x64 => KeyboardKey::Numpad4, 0x65 => KeyboardKey::Numpad5,
             0x66 => KeyboardKey::Numpad6, 0x67 => KeyboardKey::Numpad7, 0x68 => KeyboardKey::Numpad8,
@@ -100,15 +107,8 @@ impl From<u32> for KeyboardKey {
 pub fn build_key_labels(event: &KeyboardEvent) -> Vec<String> {
     let mut labels = Vec::new();
 
-    // Handle Right Alt / Han/Eng key specifically by VK code first
-    // VK_RMENU (0xA5/165): Right Alt in US keyboard layout -> map to "21"
-    // VK_HANGUL (0x15/21): Han/Eng toggle in Korean IME (same physical key) -> use "21"
-    if let Some(vk_code) = event.vk_code {
-        if vk_code == 0xA5 || vk_code == 0x15 {
-            labels.push("21".to_string());
-            labels.push("RIGHT ALT".to_string());
-            return labels;
-        }
+    if let Some(shared) = shared_position_labels(event) {
+        return shared.iter().map(|label| label.to_string()).collect();
     }
 
     if let Some(label) = numpad_override_label(event) {
@@ -150,6 +150,22 @@ pub fn should_skip_keyboard_event(event: &KeyboardEvent) -> bool {
     false
 }
 
+/// Keys whose VK code alone names the wrong key. Korean layouts send VK_HANGUL / VK_HANJA
+/// from right Alt / right Ctrl, which keep their legacy labels, and JIS keyboards reuse VK
+/// codes of other keys; the scan code tells them apart.
+fn shared_position_labels(event: &KeyboardEvent) -> Option<&'static [&'static str]> {
+    let labels: &'static [&'static str] = match (event.vk_code?, event.scan_code) {
+        (0xA5, _) | (0x15, None | Some(56)) => &["21", "RIGHT ALT"],
+        (0x19, None | Some(29)) => &["25", "RIGHT CTRL"],
+        // VK_KANA has the value of VK_HANGUL
+        (0x15, Some(0x70)) => &["KANA"],
+        (0xE2, Some(0x73)) => &["RO"],
+        (0xDC, Some(0x7D)) => &["YEN"],
+        _ => return None,
+    };
+    Some(labels)
+}
+
 fn extend_unique(target: &mut Vec<String>, items: Vec<String>) {
     for item in items {
         if !target.iter().any(|existing| existing == &item) {
@@ -302,6 +318,14 @@ fn keyboard_key_to_global(key: KeyboardKey) -> Vec<String> {
         NumLock => vec!["NUM LOCK".to_string()],
         ScrollLock => vec!["SCROLL LOCK".to_string()],
         CapsLock => vec!["CAPS LOCK".to_string()],
+        Hangul => vec!["HANGUL".to_string()],
+        Hanja => vec!["HANJA".to_string()],
+        Convert => vec!["CONVERT".to_string()],
+        NonConvert => vec!["NONCONVERT".to_string()],
+        Kana => vec!["KANA".to_string()],
+        ZenkakuHankaku => vec!["ZENKAKU HANKAKU".to_string()],
+        // 英数 shares the Caps Lock key
+        Eisu => vec!["CAPS LOCK".to_string()],
         Numpad0 => vec!["NUMPAD 0".to_string()],
         Numpad1 => vec!["NUMPAD 1".to_string()],
         Numpad2 => vec!["NUMPAD 2".to_string()],
@@ -1,12 +1,19 @@
 pub mod app_state;
+pub mod clock;
 pub mod commands;
 pub mod cursor;
 pub mod defaults;
+pub mod hotkeys;
+pub mod ime;
+pub mod input_devices;
+pub mod key_codes;
 pub mod keyboard;
 pub mod keyboard_daemon;
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 pub mod keyboard_labels;
 pub mod ipc;
 pub mod models;
+pub mod protocol;
+pub mod replay;
 pub mod services;
 pub mod store;
@@ -1,15 +1,22 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod app_state;
+mod clock;
 mod commands;
 mod cursor;
 mod defaults;
+mod hotkeys;
+mod ime;
+mod input_devices;
+mod key_codes;
 mod keyboard;
 mod keyboard_daemon;
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 mod keyboard_labels;
 mod ipc;
 mod models;
+mod protocol;
+mod replay;
 mod services;
 mod store;
 
@@ -92,12 +99,23 @@ fn main() {
             commands::keys::keys_set_mode,
             commands::keys::keys_reset_all,
             commands::keys::keys_reset_mode,
+            commands::keys::input_devices_list,
+            commands::keys::input_get_ime_state,
+            commands::keys::keys_get_device_filters,
+            commands::keys::keys_set_device_filter,
+            commands::keys::keys_get_chatter_stats,
+            commands::keys::keys_reset_chatter_stats,
             commands::keys::keys_reset_counters,
             commands::keys::keys_reset_counters_mode,
             commands::keys::keys_reset_single_counter,
             commands::keys::keys_set_counters,
+            commands::keys::keys_list_counter_snapshots,
+            commands::keys::keys_restore_counter_snapshot,
+            commands::keys::keys_delete_counter_snapshot,
             commands::keys::raw_input_subscribe,
             commands::keys::raw_input_unsubscribe,
+            commands::keys::raw_input_grant,
+            commands::keys::raw_input_revoke,
             commands::keys::custom_tabs_list,
             commands::keys::custom_tabs_create,
             commands::keys::custom_tabs_delete,
@@ -130,6 +148,27 @@ fn main() {
             commands::overlay::overlay_set_lock,
             commands::overlay::overlay_set_anchor,
             commands::overlay::overlay_resize,
+            commands::daemon::daemon_ping,
+            commands::daemon::daemon_get_capture,
+            commands::daemon::daemon_set_paused,
+            commands::daemon::daemon_set_filters,
+            commands::daemon::daemon_get_health,
+            commands::daemon::daemon_restart,
+            commands::daemon::daemon_get_replay,
+            commands::daemon::daemon_set_replay,
+            commands::diagnostics::diagnostics_get_latency,
+            commands::diagnostics::diagnostics_reset_latency,
+            commands::diagnostics::diagnostics_report_render_latency,
+            commands::stats::stats_get_kps,
+            commands::stats::stats_reset_kps,
+            commands::stats::stats_get_key_timing,
+            commands::stats::stats_reset_key_timing,
+            commands::stats::stats_export_key_timing,
+            commands::stats::stats_get_counter_history,
+            commands::stats::stats_export_counter_history,
+            commands::stats::stats_list_sessions,
+            commands::stats::stats_delete_session,
+            commands::stats::stats_export_sessions,
             commands::bridge::plugin_bridge_send,
             commands::bridge::plugin_bridge_send_to,
             commands::plugin_storage::plugin_storage_get,
@@ -1,13 +1,15 @@
 use serde::{Deserialize, Serialize, Serializer, Deserializer};
 use serde::ser::SerializeMap;
 use serde::de::Error as DeError;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 use uuid::Uuid;
 
 pub type KeyMappings = HashMap<String, Vec<String>>;
 pub type KeyPositions = HashMap<String, Vec<KeyPosition>>;
 pub type KeyCounters = HashMap<String, HashMap<String, u32>>;
+/// 키 모드별 입력을 받을 장치 id 목록 (없거나 비어 있으면 모든 장치)
+pub type KeyModeDevices = HashMap<String, Vec<String>>;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum NoteColor {
@@ -497,6 +499,114 @@ impl Default for GridSettings {
     }
 }
 
+/// 입력 처리 설정
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InputSettings {
+    /// 휠 가상 키(WHEEL UP 등)가 눌린 상태로 유지되는 시간 (ms)
+    #[serde(default = "default_wheel_pulse_ms")]
+    pub wheel_pulse_ms: u32,
+    /// 펄스 도중 들어온 휠 노치를 하나의 입력으로 합칠지 여부 (카운터는 노치마다 증가)
+    #[serde(default = "default_true")]
+    pub wheel_coalesce: bool,
+    /// 직전 Up 이후 이 시간 안에 들어온 Down을 채터로 보고 버림 (ms, 0이면 비활성)
+    #[serde(default)]
+    pub debounce_ms: u32,
+    /// 소프트웨어가 만든 입력(매크로, 리매퍼) 처리 방식
+    #[serde(default)]
+    pub injected_input: InjectedInputPolicy,
+    /// 이 시간 이상 누르고 있으면 `keys:hold` 발생 (ms)
+    #[serde(default = "default_hold_ms")]
+    pub hold_ms: u32,
+    /// 직전 누름 후 이 시간 안에 다시 누르면 `keys:double-tap` 발생 (ms)
+    #[serde(default = "default_double_tap_ms")]
+    pub double_tap_ms: u32,
+    /// 매핑된 키만 데몬에서 전달 (키 입력 화면 등에서 원시 입력을 허용한 동안은 전체 전달)
+    #[serde(default)]
+    pub mapped_keys_only: bool,
+    /// 오버레이에 지연 시간 진단 패널 표시 (켜져 있는 동안 오버레이가 렌더 지연을 보고)
+    #[serde(default)]
+    pub latency_panel: bool,
+    /// 이 시간 안에 들어온 키 이벤트를 `keys:batch` 하나로 묶어 전달 (ms, 0이면 이벤트마다 전달)
+    #[serde(default)]
+    pub batch_window_ms: u32,
+    /// 카운터 기록의 시간 단위 칸을 보관하는 기간 (일, 지나면 하루 단위로 합침)
+    #[serde(default = "default_history_retention_days")]
+    pub history_retention_days: u32,
+    /// 이 시간 동안 입력이 없으면 플레이 세션 종료 (분)
+    #[serde(default = "default_session_idle_minutes")]
+    pub session_idle_minutes: u32,
+    /// 카운터 자동 초기화 주기 (초기화 전 값은 스냅샷으로 보관)
+    #[serde(default)]
+    pub counter_reset_schedule: CounterResetSchedule,
+}
+
+fn default_hold_ms() -> u32 {
+    500
+}
+
+fn default_double_tap_ms() -> u32 {
+    300
+}
+
+fn default_history_retention_days() -> u32 {
+    7
+}
+
+fn default_session_idle_minutes() -> u32 {
+    5
+}
+
+/// 주입된 입력 처리 방식
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum InjectedInputPolicy {
+    /// 실제 입력처럼 표시하고 카운트
+    #[default]
+    Count,
+    /// 표시만 하고 카운트하지 않음
+    Show,
+    /// 무시 (`input:raw`에는 표시를 달아 그대로 전달)
+    Ignore,
+}
+
+/// 카운터 자동 초기화 주기
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum CounterResetSchedule {
+    #[default]
+    Off,
+    /// 로컬 날짜가 바뀌면
+    Daily,
+    /// ISO 주(월요일 시작)가 바뀌면
+    Weekly,
+    /// 앱을 시작할 때마다 (방송 시작 전에 앱을 켜는 경우)
+    AppStart,
+}
+
+fn default_wheel_pulse_ms() -> u32 {
+    80
+}
+
+impl Default for InputSettings {
+    fn default() -> Self {
+        Self {
+            wheel_pulse_ms: default_wheel_pulse_ms(),
+            wheel_coalesce: true,
+            debounce_ms: 0,
+            injected_input: InjectedInputPolicy::Count,
+            hold_ms: default_hold_ms(),
+            double_tap_ms: default_double_tap_ms(),
+            mapped_keys_only: false,
+            latency_panel: false,
+            batch_window_ms: 0,
+            history_retention_days: default_history_retention_days(),
+            session_idle_minutes: default_session_idle_minutes(),
+            counter_reset_schedule: CounterResetSchedule::Off,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct OverlayBounds {
@@ -557,6 +667,9 @@ pub struct AppStoreData {
     pub key_positions: KeyPositions,
     #[serde(default)]
     pub key_counters: KeyCounters,
+    /// 키 모드별 장치 필터
+    #[serde(default)]
+    pub key_mode_devices: KeyModeDevices,
     pub background_color: String,
     pub use_custom_css: bool,
     #[serde(default)]
@@ -578,6 +691,9 @@ pub struct AppStoreData {
     /// 그리드 스마트 가이드 설정
     #[serde(default)]
     pub grid_settings: GridSettings,
+    /// 입력 처리 설정
+    #[serde(default)]
+    pub input_settings: InputSettings,
     /// 단축키 설정
     #[serde(default)]
     pub shortcuts: ShortcutsState,
@@ -607,6 +723,7 @@ impl Default for AppStoreData {
             keys: KeyMappings::new(),
             key_positions: KeyPositions::new(),
             key_counters: KeyCounters::new(),
+            key_mode_devices: KeyModeDevices::new(),
             background_color: "transparent".to_string(),
             use_custom_css: false,
             custom_css: CustomCss::default(),
@@ -619,13 +736,18 @@ impl Default for AppStoreData {
             overlay_bounds_are_logical: false,
             key_counter_enabled: false,
             grid_settings: GridSettings::default(),
+            input_settings: InputSettings::default(),
             shortcuts: ShortcutsState::default(),
             plugin_data: HashMap::new(),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// 단축키 바인딩
+/// - 기본: `key` + 수정자
+/// - 동시 입력: `chord`의 키를 `key`와 함께 누름 (예: F9+F10)
+/// - 시퀀스: `leader`를 누른 뒤 제한 시간 안에 이 바인딩을 누름
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ShortcutBinding {
     pub key: String,
@@ -637,6 +759,25 @@ pub struct ShortcutBinding {
     pub alt: bool,
     #[serde(default)]
     pub meta: bool,
+    /// 함께 눌러야 하는 추가 키 (KeyboardEvent.code)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chord: Vec<String>,
+    /// 시퀀스의 첫 단계 (자체 leader는 무시)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leader: Option<Box<ShortcutBinding>>,
+    /// leader 이후 대기 시간 (ms, 기본 1000)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u32>,
+}
+
+impl ShortcutBinding {
+    /// 시퀀스 대기 시간 기본값 (ms)
+    pub const DEFAULT_SEQUENCE_TIMEOUT_MS: u32 = 1000;
+
+    /// 수정자 + 단일 키 바인딩 여부 (렌더러가 창 안에서 직접 처리할 수 있는 형태)
+    pub fn is_simple(&self) -> bool {
+        self.chord.is_empty() && self.leader.is_none()
+    }
 }
 
 fn default_toggle_overlay_shortcut() -> ShortcutBinding {
@@ -649,6 +790,7 @@ fn default_toggle_overlay_shortcut() -> ShortcutBinding {
             shift: true,
             alt: false,
             meta: true,
+            ..Default::default()
         }
     } else {
         ShortcutBinding {
@@ -657,6 +799,7 @@ fn default_toggle_overlay_shortcut() -> ShortcutBinding {
             shift: true,
             alt: false,
             meta: false,
+            ..Default::default()
         }
     }
 }
@@ -668,6 +811,7 @@ fn default_switch_key_mode_shortcut() -> ShortcutBinding {
         shift: false,
         alt: false,
         meta: false,
+        ..Default::default()
     }
 }
 
@@ -678,6 +822,7 @@ fn default_unbound_shortcut() -> ShortcutBinding {
         shift: false,
         alt: false,
         meta: false,
+        ..Default::default()
     }
 }
 
@@ -689,6 +834,7 @@ fn default_toggle_settings_panel_shortcut() -> ShortcutBinding {
             shift: false,
             alt: false,
             meta: true,
+            ..Default::default()
         }
     } else {
         ShortcutBinding {
@@ -697,6 +843,7 @@ fn default_toggle_settings_panel_shortcut() -> ShortcutBinding {
             shift: false,
             alt: false,
             meta: false,
+            ..Default::default()
         }
     }
 }
@@ -709,6 +856,7 @@ fn default_zoom_in_shortcut() -> ShortcutBinding {
             shift: false,
             alt: false,
             meta: true,
+            ..Default::default()
         }
     } else {
         ShortcutBinding {
@@ -717,6 +865,7 @@ fn default_zoom_in_shortcut() -> ShortcutBinding {
             shift: false,
             alt: false,
             meta: false,
+            ..Default::default()
         }
     }
 }
@@ -729,6 +878,7 @@ fn default_zoom_out_shortcut() -> ShortcutBinding {
             shift: false,
             alt: false,
             meta: true,
+            ..Default::default()
         }
     } else {
         ShortcutBinding {
@@ -737,6 +887,7 @@ fn default_zoom_out_shortcut() -> ShortcutBinding {
             shift: false,
             alt: false,
             meta: false,
+            ..Default::default()
         }
     }
 }
@@ -749,6 +900,7 @@ fn default_zoom_reset_shortcut() -> ShortcutBinding {
             shift: false,
             alt: false,
             meta: true,
+            ..Default::default()
         }
     } else {
         ShortcutBinding {
@@ -757,6 +909,7 @@ fn default_zoom_reset_shortcut() -> ShortcutBinding {
             shift: false,
             alt: false,
             meta: false,
+            ..Default::default()
         }
     }
 }
@@ -780,6 +933,18 @@ pub struct ShortcutsState {
     pub zoom_out: ShortcutBinding,
     #[serde(default = "default_zoom_reset_shortcut")]
     pub reset_zoom: ShortcutBinding,
+    /// 현재 모드의 카운터 초기화 (전역)
+    #[serde(default = "default_unbound_shortcut")]
+    pub reset_mode_counters: ShortcutBinding,
+    /// 키 카운터 표시 토글 (전역)
+    #[serde(default = "default_unbound_shortcut")]
+    pub toggle_key_counter: ShortcutBinding,
+    /// 입력 캡처 일시정지/재개 (전역)
+    #[serde(default = "default_unbound_shortcut")]
+    pub toggle_capture_pause: ShortcutBinding,
+    /// 특정 키 모드로 바로 전환 (모드 → 단축키, 전역)
+    #[serde(default)]
+    pub key_mode_shortcuts: BTreeMap<String, ShortcutBinding>,
 }
 
 impl Default for ShortcutsState {
@@ -793,6 +958,10 @@ impl Default for ShortcutsState {
             zoom_in: default_zoom_in_shortcut(),
             zoom_out: default_zoom_out_shortcut(),
             reset_zoom: default_zoom_reset_shortcut(),
+            reset_mode_counters: default_unbound_shortcut(),
+            toggle_key_counter: default_unbound_shortcut(),
+            toggle_capture_pause: default_unbound_shortcut(),
+            key_mode_shortcuts: BTreeMap::new(),
         }
     }
 }
@@ -816,6 +985,15 @@ pub struct ShortcutsPatchInput {
     pub zoom_out: Option<ShortcutBinding>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reset_zoom: Option<ShortcutBinding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_mode_counters: Option<ShortcutBinding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toggle_key_counter: Option<ShortcutBinding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toggle_capture_pause: Option<ShortcutBinding>,
+    /// 전체 교체 (빈 키는 제거)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_mode_shortcuts: Option<BTreeMap<String, ShortcutBinding>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -870,6 +1048,8 @@ pub struct SettingsState {
     #[serde(default)]
     pub grid_settings: GridSettings,
     #[serde(default)]
+    pub input_settings: InputSettings,
+    #[serde(default)]
     pub shortcuts: ShortcutsState,
 }
 
@@ -897,6 +1077,7 @@ impl Default for SettingsState {
             overlay_resize_anchor: OverlayResizeAnchor::TopLeft,
             key_counter_enabled: false,
             grid_settings: GridSettings::default(),
+            input_settings: InputSettings::default(),
             shortcuts: ShortcutsState::default(),
         }
     }
@@ -954,6 +1135,7 @@ pub struct SettingsPatchInput {
     pub overlay_resize_anchor: Option<OverlayResizeAnchor>,
     pub key_counter_enabled: Option<bool>,
     pub grid_settings: Option<GridSettings>,
+    pub input_settings: Option<InputSettings>,
     pub shortcuts: Option<ShortcutsPatchInput>,
 }
 
@@ -1005,6 +1187,7 @@ impl SettingsDiff {
             p.overlay_resize_anchor.is_some(),
             p.key_counter_enabled.is_some(),
             p.grid_settings.is_some(),
+            p.input_settings.is_some(),
             p.shortcuts.is_some(),
         ]
         .iter()
@@ -1055,5 +1238,7 @@ pub struct SettingsPatch {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grid_settings: Option<GridSettings>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_settings: Option<InputSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub shortcuts: Option<ShortcutsState>,
 }
@@ -0,0 +1,608 @@
+//! Framing protocol between the app and the keyboard daemon.
+//!
+//! Every frame is `[len: u32 LE][tag: u8][payload]`, where `len` counts the tag and payload.
+//! The app opens the link by sending `Hello` with its protocol version on the daemon's stdin;
+//! the daemon answers `HelloAck` on its output (stdout or the named pipe) before any other
+//! frame, or `VersionMismatch` followed by exiting. Key events use a compact binary layout;
+//! commands travel as tagged frames carrying the serialized [`DaemonCommand`]. After the
+//! handshake the app keeps stdin open as a control channel of [`ControlMessage`] frames.
+
+use std::io::{self, Read, Write};
+
+use crate::ipc::{ControlMessage, DaemonCommand, HookKeyState, HookMessage, InputDeviceKind};
+
+/// Bumped whenever the layout of any frame changes.
+pub const PROTOCOL_VERSION: u16 = 6;
+
+/// Upper bound for a single frame; anything larger is treated as a corrupt stream.
+pub const MAX_FRAME_LEN: usize = 64 * 1024;
+
+const TAG_HELLO: u8 = 0x01;
+const TAG_HELLO_ACK: u8 = 0x02;
+const TAG_VERSION_MISMATCH: u8 = 0x03;
+const TAG_KEY_EVENT: u8 = 0x10;
+const TAG_COMMAND: u8 = 0x20;
+const TAG_CONTROL: u8 = 0x30;
+
+// Presence bits for optional key event fields.
+const HAS_VK_CODE: u8 = 0x01;
+const HAS_SCAN_CODE: u8 = 0x02;
+const HAS_FLAGS: u8 = 0x04;
+const HAS_TIMESTAMP: u8 = 0x08;
+const HAS_DEVICE_ID: u8 = 0x10;
+const HAS_DEVICE_NAME: u8 = 0x20;
+// Flag bits in the same byte.
+const IS_INJECTED: u8 = 0x40;
+const IS_REPEAT: u8 = 0x80;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProtocolError {
+    #[error("daemon link i/o error: {0}")]
+    Io(#[from] io::Error),
+    #[error("protocol version mismatch (local {local}, remote {remote})")]
+    VersionMismatch { local: u16, remote: u16 },
+    #[error("unknown frame tag 0x{0:02x}")]
+    UnknownTag(u8),
+    #[error("frame of {0} bytes exceeds the limit")]
+    FrameTooLarge(usize),
+    #[error("malformed frame: {0}")]
+    Malformed(&'static str),
+    #[error("unexpected frame during handshake")]
+    UnexpectedFrame,
+}
+
+#[derive(Debug, Clone)]
+pub enum Frame {
+    Hello { version: u16 },
+    HelloAck { version: u16 },
+    VersionMismatch { supported: u16 },
+    Key(HookMessage),
+    Command(DaemonCommand),
+    Control(ControlMessage),
+}
+
+/// Encodes a frame (length prefix included) into `out`.
+pub fn encode_frame(frame: &Frame, out: &mut Vec<u8>) -> Result<(), ProtocolError> {
+    match frame {
+        Frame::Hello { version } => encode_with(TAG_HELLO, out, |out| {
+            out.extend_from_slice(&version.to_le_bytes());
+            Ok(())
+        }),
+        Frame::HelloAck { version } => encode_with(TAG_HELLO_ACK, out, |out| {
+            out.extend_from_slice(&version.to_le_bytes());
+            Ok(())
+        }),
+        Frame::VersionMismatch { supported } => encode_with(TAG_VERSION_MISMATCH, out, |out| {
+            out.extend_from_slice(&supported.to_le_bytes());
+            Ok(())
+        }),
+        Frame::Key(message) => encode_with(TAG_KEY_EVENT, out, |out| encode_key_event(message, out)),
+        Frame::Command(command) => encode_with(TAG_COMMAND, out, |out| {
+            serde_json::to_writer(out, command)
+                .map_err(|_| ProtocolError::Malformed("unserializable command"))
+        }),
+        Frame::Control(message) => encode_with(TAG_CONTROL, out, |out| {
+            serde_json::to_writer(out, message)
+                .map_err(|_| ProtocolError::Malformed("unserializable control message"))
+        }),
+    }
+}
+
+fn encode_with<F>(tag: u8, out: &mut Vec<u8>, body: F) -> Result<(), ProtocolError>
+where
+    F: FnOnce(&mut Vec<u8>) -> Result<(), ProtocolError>,
+{
+    let start = out.len();
+    out.extend_from_slice(&[0; 4]);
+    out.push(tag);
+    if let Err(err) = body(out) {
+        out.truncate(start);
+        return Err(err);
+    }
+
+    let len = out.len() - start - 4;
+    if len > MAX_FRAME_LEN {
+        out.truncate(start);
+        return Err(ProtocolError::FrameTooLarge(len));
+    }
+    out[start..start + 4].copy_from_slice(&(len as u32).to_le_bytes());
+    Ok(())
+}
+
+fn encode_key_event(message: &HookMessage, out: &mut Vec<u8>) -> Result<(), ProtocolError> {
+    out.push(match message.device {
+        InputDeviceKind::Keyboard => 0,
+        InputDeviceKind::Mouse => 1,
+        InputDeviceKind::Gamepad => 2,
+        InputDeviceKind::Unknown => 0xff,
+    });
+    out.push(message.state as u8);
+
+    let mut presence = 0u8;
+    if message.vk_code.is_some() {
+        presence |= HAS_VK_CODE;
+    }
+    if message.scan_code.is_some() {
+        presence |= HAS_SCAN_CODE;
+    }
+    if message.flags.is_some() {
+        presence |= HAS_FLAGS;
+    }
+    if message.timestamp_us.is_some() {
+        presence |= HAS_TIMESTAMP;
+    }
+    if message.device_id.is_some() {
+        presence |= HAS_DEVICE_ID;
+    }
+    if message.device_name.is_some() {
+        presence |= HAS_DEVICE_NAME;
+    }
+    if message.injected {
+        presence |= IS_INJECTED;
+    }
+    if message.repeat {
+        presence |= IS_REPEAT;
+    }
+    out.push(presence);
+    for value in [message.vk_code, message.scan_code, message.flags]
+        .into_iter()
+        .flatten()
+    {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    if let Some(timestamp) = message.timestamp_us {
+        out.extend_from_slice(&timestamp.to_le_bytes());
+    }
+    for value in [&message.device_id, &message.device_name].into_iter().flatten() {
+        encode_str(value, out, "device field too long")?;
+    }
+
+    let count = u8::try_from(message.labels.len())
+        .map_err(|_| ProtocolError::Malformed("too many labels"))?;
+    out.push(count);
+    for label in &message.labels {
+        encode_str(label, out, "label too long")?;
+    }
+    // The presence byte is full; an empty code means none.
+    encode_str(message.code.as_deref().unwrap_or_default(), out, "code too long")?;
+    Ok(())
+}
+
+/// `[len: u16 LE][utf-8 bytes]`
+fn encode_str(value: &str, out: &mut Vec<u8>, too_long: &'static str) -> Result<(), ProtocolError> {
+    let len = u16::try_from(value.len()).map_err(|_| ProtocolError::Malformed(too_long))?;
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(value.as_bytes());
+    Ok(())
+}
+
+/// Decodes one frame body (tag + payload, without the length prefix).
+pub fn decode_frame(body: &[u8]) -> Result<Frame, ProtocolError> {
+    let mut decoder = Decoder { buf: body, pos: 0 };
+    let tag = decoder.u8()?;
+    let frame = match tag {
+        TAG_HELLO => Frame::Hello {
+            version: decoder.u16()?,
+        },
+        TAG_HELLO_ACK => Frame::HelloAck {
+            version: decoder.u16()?,
+        },
+        TAG_VERSION_MISMATCH => Frame::VersionMismatch {
+            supported: decoder.u16()?,
+        },
+        TAG_KEY_EVENT => Frame::Key(decode_key_event(&mut decoder)?),
+        TAG_COMMAND => Frame::Command(
+            serde_json::from_slice(decoder.rest())
+                .map_err(|_| ProtocolError::Malformed("invalid command payload"))?,
+        ),
+        TAG_CONTROL => Frame::Control(
+            serde_json::from_slice(decoder.rest())
+                .map_err(|_| ProtocolError::Malformed("invalid control payload"))?,
+        ),
+        other => return Err(ProtocolError::UnknownTag(other)),
+    };
+    if !decoder.is_empty() {
+        return Err(ProtocolError::Malformed("trailing bytes"));
+    }
+    Ok(frame)
+}
+
+fn decode_key_event(decoder: &mut Decoder<'_>) -> Result<HookMessage, ProtocolError> {
+    let device = match decoder.u8()? {
+        0 => InputDeviceKind::Keyboard,
+        1 => InputDeviceKind::Mouse,
+        2 => InputDeviceKind::Gamepad,
+        _ => InputDeviceKind::Unknown,
+    };
+    let state = match decoder.u8()? {
+        0 => HookKeyState::Down,
+        1 => HookKeyState::Up,
+        _ => return Err(ProtocolError::Malformed("invalid key state")),
+    };
+    let presence = decoder.u8()?;
+    let vk_code = decoder.optional_u32(presence & HAS_VK_CODE != 0)?;
+    let scan_code = decoder.optional_u32(presence & HAS_SCAN_CODE != 0)?;
+    let flags = decoder.optional_u32(presence & HAS_FLAGS != 0)?;
+    let timestamp_us = if presence & HAS_TIMESTAMP != 0 {
+        Some(decoder.u64()?)
+    } else {
+        None
+    };
+    let device_id = decoder.optional_str(presence & HAS_DEVICE_ID != 0)?;
+    let device_name = decoder.optional_str(presence & HAS_DEVICE_NAME != 0)?;
+    let injected = presence & IS_INJECTED != 0;
+    let repeat = presence & IS_REPEAT != 0;
+
+    let count = decoder.u8()?;
+    let mut labels = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        labels.push(decoder.str()?);
+    }
+    let code = Some(decoder.str()?).filter(|code| !code.is_empty());
+
+    Ok(HookMessage {
+        device,
+        labels,
+        code,
+        state,
+        vk_code,
+        scan_code,
+        flags,
+        timestamp_us,
+        device_id,
+        device_name,
+        injected,
+        repeat,
+    })
+}
+
+struct Decoder<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ProtocolError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.buf.len())
+            .ok_or(ProtocolError::Malformed("truncated frame"))?;
+        let bytes = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, ProtocolError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, ProtocolError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, ProtocolError> {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn u64(&mut self) -> Result<u64, ProtocolError> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn optional_u32(&mut self, present: bool) -> Result<Option<u32>, ProtocolError> {
+        if present {
+            self.u32().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn str(&mut self) -> Result<String, ProtocolError> {
+        let len = self.u16()? as usize;
+        let bytes = self.take(len)?;
+        std::str::from_utf8(bytes)
+            .map(str::to_string)
+            .map_err(|_| ProtocolError::Malformed("string is not utf-8"))
+    }
+
+    fn optional_str(&mut self, present: bool) -> Result<Option<String>, ProtocolError> {
+        if present {
+            self.str().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn rest(&mut self) -> &'a [u8] {
+        let bytes = &self.buf[self.pos..];
+        self.pos = self.buf.len();
+        bytes
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos == self.buf.len()
+    }
+}
+
+/// Writes a single frame and flushes it, so line-buffered stdout never holds a frame back.
+pub fn write_frame<W: Write + ?Sized>(writer: &mut W, frame: &Frame) -> Result<(), ProtocolError> {
+    let mut buf = Vec::with_capacity(64);
+    encode_frame(frame, &mut buf)?;
+    writer.write_all(&buf)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes a key event frame without building an owned [`Frame`] first.
+pub fn write_key_event<W: Write + ?Sized>(
+    writer: &mut W,
+    message: &HookMessage,
+) -> Result<(), ProtocolError> {
+    let mut buf = Vec::with_capacity(64);
+    encode_with(TAG_KEY_EVENT, &mut buf, |out| encode_key_event(message, out))?;
+    writer.write_all(&buf)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads one frame. Returns `Ok(None)` on a clean end of stream between frames.
+pub fn read_frame<R: Read + ?Sized>(reader: &mut R) -> Result<Option<Frame>, ProtocolError> {
+    let mut len_bytes = [0u8; 4];
+    let mut filled = 0;
+    while filled < len_bytes.len() {
+        match reader.read(&mut len_bytes[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(ProtocolError::Malformed("truncated length prefix")),
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    let len = u32::from_le_bytes(len_bytes) as usize;
+    if len == 0 {
+        return Err(ProtocolError::Malformed("empty frame"));
+    }
+    if len > MAX_FRAME_LEN {
+        return Err(ProtocolError::FrameTooLarge(len));
+    }
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body)?;
+    decode_frame(&body).map(Some)
+}
+
+/// App side, step 1: announce our protocol version to the daemon.
+pub fn send_hello<W: Write + ?Sized>(writer: &mut W) -> Result<(), ProtocolError> {
+    write_frame(
+        writer,
+        &Frame::Hello {
+            version: PROTOCOL_VERSION,
+        },
+    )
+}
+
+/// App side, step 2: wait for the daemon to confirm the version.
+pub fn expect_hello_ack<R: Read + ?Sized>(reader: &mut R) -> Result<(), ProtocolError> {
+    match read_frame(reader)? {
+        Some(Frame::HelloAck { version }) if version == PROTOCOL_VERSION => Ok(()),
+        Some(Frame::HelloAck { version }) | Some(Frame::VersionMismatch { supported: version }) => {
+            Err(ProtocolError::VersionMismatch {
+                local: PROTOCOL_VERSION,
+                remote: version,
+            })
+        }
+        Some(_) => Err(ProtocolError::UnexpectedFrame),
+        None => Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+    }
+}
+
+/// Daemon side: read the app's `Hello` and confirm it, or report a mismatch.
+pub fn accept_hello<R, W>(reader: &mut R, writer: &mut W) -> Result<(), ProtocolError>
+where
+    R: Read + ?Sized,
+    W: Write + ?Sized,
+{
+    match read_frame(reader)? {
+        Some(Frame::Hello { version }) if version == PROTOCOL_VERSION => write_frame(
+            writer,
+            &Frame::HelloAck {
+                version: PROTOCOL_VERSION,
+            },
+        ),
+        Some(Frame::Hello { version }) => {
+            write_frame(
+                writer,
+                &Frame::VersionMismatch {
+                    supported: PROTOCOL_VERSION,
+                },
+            )?;
+            Err(ProtocolError::VersionMismatch {
+                local: PROTOCOL_VERSION,
+                remote: version,
+            })
+        }
+        Some(_) => Err(ProtocolError::UnexpectedFrame),
+        None => Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn round_trip(frame: &Frame) -> Frame {
+        let mut buf = Vec::new();
+        write_frame(&mut buf, frame).unwrap();
+        let mut reader = Cursor::new(buf);
+        let decoded = read_frame(&mut reader).unwrap().unwrap();
+        assert!(read_frame(&mut reader).unwrap().is_none());
+        decoded
+    }
+
+    #[test]
+    fn key_event_round_trip() {
+        let message = HookMessage {
+            device: InputDeviceKind::Keyboard,
+            labels: vec!["A".to_string(), "가".to_string()],
+            code: Some("KeyA".to_string()),
+            state: HookKeyState::Down,
+            vk_code: Some(0x41),
+            scan_code: Some(30),
+            flags: Some(1),
+            timestamp_us: Some(123_456_789_012),
+            device_id: Some("046d:c31c/usb-0000:00:14.0-2/input0".to_string()),
+            device_name: Some("USB Keyboard".to_string()),
+            injected: true,
+            repeat: true,
+        };
+        let Frame::Key(decoded) = round_trip(&Frame::Key(message.clone())) else {
+            panic!("expected key frame");
+        };
+        assert_eq!(decoded.device, message.device);
+        assert_eq!(decoded.labels, message.labels);
+        assert_eq!(decoded.code, message.code);
+        assert_eq!(decoded.state, message.state);
+        assert_eq!(decoded.vk_code, message.vk_code);
+        assert_eq!(decoded.scan_code, message.scan_code);
+        assert_eq!(decoded.flags, message.flags);
+        assert_eq!(decoded.timestamp_us, message.timestamp_us);
+        assert_eq!(decoded.device_id, message.device_id);
+        assert_eq!(decoded.device_name, message.device_name);
+        assert!(decoded.injected);
+        assert!(decoded.repeat);
+    }
+
+    #[test]
+    fn key_event_without_optional_fields_round_trip() {
+        let message = HookMessage {
+            device: InputDeviceKind::Mouse,
+            labels: vec!["WHEEL UP".to_string()],
+            code: None,
+            state: HookKeyState::Up,
+            vk_code: None,
+            scan_code: None,
+            flags: None,
+            timestamp_us: None,
+            device_id: None,
+            device_name: None,
+            injected: false,
+            repeat: false,
+        };
+        let Frame::Key(decoded) = round_trip(&Frame::Key(message)) else {
+            panic!("expected key frame");
+        };
+        assert_eq!(decoded.device, InputDeviceKind::Mouse);
+        assert_eq!(decoded.labels, vec!["WHEEL UP".to_string()]);
+        assert_eq!(decoded.state, HookKeyState::Up);
+        assert!(decoded.code.is_none());
+        assert!(decoded.vk_code.is_none());
+        assert!(decoded.scan_code.is_none());
+        assert!(decoded.flags.is_none());
+        assert!(decoded.timestamp_us.is_none());
+        assert!(decoded.device_id.is_none());
+        assert!(!decoded.injected);
+        assert!(!decoded.repeat);
+    }
+
+    #[test]
+    fn command_round_trip() {
+        let decoded = round_trip(&Frame::Command(DaemonCommand::ToggleOverlayLock));
+        assert!(matches!(
+            decoded,
+            Frame::Command(DaemonCommand::ToggleOverlayLock)
+        ));
+    }
+
+    #[test]
+    fn control_round_trip() {
+        let message = ControlMessage::SetFilters {
+            filters: crate::ipc::CaptureFilters {
+                keyboard: true,
+                mouse: false,
+                gamepad: true,
+            },
+        };
+        let Frame::Control(decoded) = round_trip(&Frame::Control(message.clone())) else {
+            panic!("expected control frame");
+        };
+        assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn handshake_succeeds_with_matching_versions() {
+        let mut to_daemon = Vec::new();
+        send_hello(&mut to_daemon).unwrap();
+
+        let mut to_app = Vec::new();
+        accept_hello(&mut Cursor::new(to_daemon), &mut to_app).unwrap();
+        expect_hello_ack(&mut Cursor::new(to_app)).unwrap();
+    }
+
+    #[test]
+    fn daemon_rejects_other_version() {
+        let mut to_daemon = Vec::new();
+        write_frame(
+            &mut to_daemon,
+            &Frame::Hello {
+                version: PROTOCOL_VERSION + 1,
+            },
+        )
+        .unwrap();
+
+        let mut to_app = Vec::new();
+        let err = accept_hello(&mut Cursor::new(to_daemon), &mut to_app).unwrap_err();
+        assert!(matches!(
+            err,
+            ProtocolError::VersionMismatch { remote, .. } if remote == PROTOCOL_VERSION + 1
+        ));
+
+        // The app learns which version the daemon speaks instead of reading garbage.
+        let err = expect_hello_ack(&mut Cursor::new(to_app)).unwrap_err();
+        assert!(matches!(
+            err,
+            ProtocolError::VersionMismatch { remote, .. } if remote == PROTOCOL_VERSION
+        ));
+    }
+
+    #[test]
+    fn app_rejects_ack_with_other_version() {
+        let mut to_app = Vec::new();
+        write_frame(
+            &mut to_app,
+            &Frame::HelloAck {
+                version: PROTOCOL_VERSION + 1,
+            },
+        )
+        .unwrap();
+        assert!(matches!(
+            expect_hello_ack(&mut Cursor::new(to_app)),
+            Err(ProtocolError::VersionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_truncated_and_unknown_frames() {
+        let mut buf = Vec::new();
+        write_frame(&mut buf, &Frame::Hello { version: 1 }).unwrap();
+        buf.truncate(buf.len() - 1);
+        assert!(read_frame(&mut Cursor::new(buf)).is_err());
+
+        let body = [0x7f_u8];
+        assert!(matches!(
+            decode_frame(&body),
+            Err(ProtocolError::UnknownTag(0x7f))
+        ));
+
+        let oversized = ((MAX_FRAME_LEN + 1) as u32).to_le_bytes();
+        assert!(matches!(
+            read_frame(&mut Cursor::new(oversized.to_vec())),
+            Err(ProtocolError::FrameTooLarge(_))
+        ));
+    }
+}
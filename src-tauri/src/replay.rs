@@ -0,0 +1,192 @@
+//! Recorded input played back by the keyboard daemon instead of a hardware backend.
+//!
+//! Started as `--keyboard-daemon --replay <file> [--replay-speed <x>] [--replay-loop]`.
+//! The file is JSON Lines: one [`HookMessage`] per line plus `at_ms`, the offset from the start
+//! of the recording, e.g. `{"at_ms": 120, "labels": ["A"], "state": "DOWN"}`.
+//! Blank lines and lines starting with `#` are ignored.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::ipc::HookMessage;
+
+const MIN_SPEED: f64 = 0.01;
+const MAX_SPEED: f64 = 100.0;
+
+fn default_speed() -> f64 {
+    1.0
+}
+
+/// Replay file and playback options.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaySource {
+    pub path: PathBuf,
+    /// Playback rate; 2.0 plays twice as fast.
+    #[serde(default = "default_speed")]
+    pub speed: f64,
+    /// Start over after the last event.
+    #[serde(default, rename = "loop")]
+    pub looped: bool,
+}
+
+impl ReplaySource {
+    pub fn new(path: PathBuf, speed: f64, looped: bool) -> Self {
+        let speed = if speed.is_finite() {
+            speed.clamp(MIN_SPEED, MAX_SPEED)
+        } else {
+            default_speed()
+        };
+        Self {
+            path,
+            speed,
+            looped,
+        }
+    }
+
+    /// Replay requested through `DMNOTE_REPLAY_FILE` (with optional `DMNOTE_REPLAY_SPEED`
+    /// and `DMNOTE_REPLAY_LOOP`), used by CI and rehearsals to run the app without hardware.
+    pub fn from_env() -> Option<Self> {
+        let path = std::env::var_os("DMNOTE_REPLAY_FILE").filter(|path| !path.is_empty())?;
+        let speed = std::env::var("DMNOTE_REPLAY_SPEED")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_else(default_speed);
+        let looped = std::env::var("DMNOTE_REPLAY_LOOP")
+            .is_ok_and(|value| matches!(value.as_str(), "1" | "true"));
+        Some(Self::new(PathBuf::from(path), speed, looped))
+    }
+
+    /// Parses the daemon's `--replay` options; `None` if no replay was requested.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Option<Self> {
+        let mut path = None;
+        let mut speed = default_speed();
+        let mut looped = false;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--replay" => path = args.next().map(PathBuf::from),
+                "--replay-speed" => {
+                    speed = args
+                        .next()
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or(speed)
+                }
+                "--replay-loop" => looped = true,
+                _ => {}
+            }
+        }
+        path.map(|path| Self::new(path, speed, looped))
+    }
+
+    /// Daemon arguments that reproduce this source; inverse of [`ReplaySource::from_args`].
+    pub fn to_args(&self) -> Vec<OsString> {
+        let mut args = vec![
+            OsString::from("--replay"),
+            self.path.clone().into_os_string(),
+            OsString::from("--replay-speed"),
+            OsString::from(self.speed.to_string()),
+        ];
+        if self.looped {
+            args.push(OsString::from("--replay-loop"));
+        }
+        args
+    }
+}
+
+/// One recorded event and its offset from the start of the recording.
+#[derive(Debug, Clone)]
+pub struct ReplayEvent {
+    pub at: Duration,
+    pub message: HookMessage,
+}
+
+#[derive(Deserialize)]
+struct ReplayLine {
+    at_ms: f64,
+    #[serde(flatten)]
+    message: HookMessage,
+}
+
+/// Parses a replay file body. Events are returned in time order.
+pub fn parse_replay(text: &str) -> Result<Vec<ReplayEvent>> {
+    let mut events = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let parsed: ReplayLine = serde_json::from_str(line)
+            .map_err(|err| anyhow!("replay line {}: {err}", index + 1))?;
+        if !parsed.at_ms.is_finite() || parsed.at_ms < 0.0 {
+            return Err(anyhow!("replay line {}: invalid at_ms", index + 1));
+        }
+        events.push(ReplayEvent {
+            at: Duration::from_secs_f64(parsed.at_ms / 1000.0),
+            message: parsed.message,
+        });
+    }
+    // Stable, so events sharing a timestamp keep their file order.
+    events.sort_by_key(|event| event.at);
+    Ok(events)
+}
+
+pub fn load_replay(path: &Path) -> Result<Vec<ReplayEvent>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read replay file {}", path.display()))?;
+    parse_replay(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::{HookKeyState, InputDeviceKind};
+
+    #[test]
+    fn parses_and_orders_events() {
+        let text = r#"
+# recorded 2024-01-01
+{"at_ms": 40, "labels": ["A"], "state": "UP"}
+{"at_ms": 0, "labels": ["A"], "state": "DOWN", "vk_code": 65}
+
+{"at_ms": 40, "device": "mouse", "labels": ["MOUSE1"], "state": "DOWN"}
+"#;
+        let events = parse_replay(text).unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].at, Duration::ZERO);
+        assert_eq!(events[0].message.vk_code, Some(65));
+        assert_eq!(events[1].message.state, HookKeyState::Up);
+        assert_eq!(events[1].message.device, InputDeviceKind::Keyboard);
+        assert_eq!(events[2].message.device, InputDeviceKind::Mouse);
+        assert_eq!(events[2].at, Duration::from_millis(40));
+    }
+
+    #[test]
+    fn reports_the_bad_line() {
+        let err =
+            parse_replay("{\"at_ms\": 0, \"labels\": [\"A\"], \"state\": \"DOWN\"}\nnot json")
+                .unwrap_err();
+        assert!(err.to_string().starts_with("replay line 2:"));
+        assert!(parse_replay("{\"at_ms\": -1, \"labels\": [], \"state\": \"UP\"}").is_err());
+    }
+
+    #[test]
+    fn args_round_trip() {
+        let source = ReplaySource::new(PathBuf::from("demo.jsonl"), 2.5, true);
+        let args = source
+            .to_args()
+            .into_iter()
+            .map(|arg| arg.into_string().unwrap());
+        let parsed =
+            ReplaySource::from_args(std::iter::once("--keyboard-daemon".to_string()).chain(args));
+        assert_eq!(parsed, Some(source));
+        assert_eq!(
+            ReplaySource::from_args(vec!["--keyboard-daemon".to_string()]),
+            None
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! 키 채터링(디바운스) 필터 서비스
+//!
+//! 스위치가 마모되면 한 번 누를 때 Down/Up이 여러 번 들어옵니다. 직전 Up 이후
+//! 설정한 시간 안에 들어온 Down을 채터로 보고 버립니다.
+//! - 키별 마지막 Up 시각 관리
+//! - 버린 Down과 짝이 되는 Up도 함께 버림 (눌림 상태가 어긋나지 않도록)
+//! - 키별 채터 통계 (고장 난 스위치 확인용)
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::clock::wall_clock_offset_us;
+use crate::ipc::HookKeyState;
+
+/// 키별 채터 통계
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyChatterStats {
+    pub key: String,
+    /// 버린 Down 횟수
+    pub count: u64,
+    /// 직전 Up과 가장 짧았던 간격 (µs)
+    pub shortest_gap_us: u64,
+    /// 마지막 채터 시각 (epoch ms)
+    pub last_at_ms: u64,
+}
+
+#[derive(Default)]
+struct KeyTiming {
+    last_up_us: Option<u64>,
+    /// 버린 Down의 Up을 기다리는 중
+    suppressing: bool,
+}
+
+#[derive(Default)]
+struct Inner {
+    timings: HashMap<String, KeyTiming>,
+    stats: HashMap<String, KeyChatterStats>,
+}
+
+pub struct ChatterFilter {
+    /// 0이면 비활성
+    threshold_ms: AtomicU32,
+    inner: Mutex<Inner>,
+}
+
+impl ChatterFilter {
+    pub fn new(threshold_ms: u32) -> Self {
+        Self {
+            threshold_ms: AtomicU32::new(threshold_ms),
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    pub fn set_threshold_ms(&self, threshold_ms: u32) {
+        self.threshold_ms.store(threshold_ms, Ordering::Relaxed);
+        if threshold_ms == 0 {
+            self.inner.lock().timings.clear();
+        }
+    }
+
+    /// 입력을 통과시킬지 판단 (`false`면 버림)
+    pub fn accept(&self, key: &str, state: HookKeyState, timestamp_us: u64) -> bool {
+        let threshold_ms = self.threshold_ms.load(Ordering::Relaxed);
+        if threshold_ms == 0 {
+            return true;
+        }
+
+        let mut inner = self.inner.lock();
+        let timing = inner.timings.entry(key.to_string()).or_default();
+        match state {
+            HookKeyState::Up => {
+                timing.last_up_us = Some(timestamp_us);
+                !std::mem::take(&mut timing.suppressing)
+            }
+            HookKeyState::Down => {
+                if timing.suppressing {
+                    // 버린 Down의 자동 반복
+                    return false;
+                }
+                let gap_us = timing
+                    .last_up_us
+                    .map(|last_up| timestamp_us.saturating_sub(last_up));
+                let Some(gap_us) = gap_us.filter(|gap| *gap < u64::from(threshold_ms) * 1000)
+                else {
+                    return true;
+                };
+                timing.suppressing = true;
+
+                let last_at_ms =
+                    (timestamp_us as i64 + wall_clock_offset_us()).max(0) as u64 / 1000;
+                let stats = inner
+                    .stats
+                    .entry(key.to_string())
+                    .or_insert_with(|| KeyChatterStats {
+                        key: key.to_string(),
+                        count: 0,
+                        shortest_gap_us: gap_us,
+                        last_at_ms,
+                    });
+                stats.count += 1;
+                stats.shortest_gap_us = stats.shortest_gap_us.min(gap_us);
+                stats.last_at_ms = last_at_ms;
+                false
+            }
+        }
+    }
+
+    /// 채터가 많은 키부터 정렬한 통계
+    pub fn stats(&self) -> Vec<KeyChatterStats> {
+        let mut stats: Vec<KeyChatterStats> = self.inner.lock().stats.values().cloned().collect();
+        stats.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+        stats
+    }
+
+    pub fn reset_stats(&self) {
+        self.inner.lock().stats.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: u64 = 1000;
+
+    #[test]
+    fn bounce_inside_window_is_dropped_with_its_up() {
+        let filter = ChatterFilter::new(10);
+        assert!(filter.accept("A", HookKeyState::Down, 0));
+        assert!(filter.accept("A", HookKeyState::Up, 50 * MS));
+
+        // 4ms after the release: a bounce. Its repeat and its Up are dropped too.
+        assert!(!filter.accept("A", HookKeyState::Down, 54 * MS));
+        assert!(!filter.accept("A", HookKeyState::Down, 55 * MS));
+        assert!(!filter.accept("A", HookKeyState::Up, 56 * MS));
+
+        // Outside the window (measured from the dropped Up) the next press goes through.
+        assert!(filter.accept("A", HookKeyState::Down, 70 * MS));
+        assert!(filter.accept("A", HookKeyState::Up, 90 * MS));
+
+        let stats = filter.stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].count, 1);
+        assert_eq!(stats[0].shortest_gap_us, 4 * MS);
+    }
+
+    #[test]
+    fn each_key_has_its_own_window() {
+        let filter = ChatterFilter::new(10);
+        assert!(filter.accept("A", HookKeyState::Down, 0));
+        assert!(filter.accept("A", HookKeyState::Up, 20 * MS));
+        // B was never released, so nothing to bounce from.
+        assert!(filter.accept("B", HookKeyState::Down, 22 * MS));
+        assert!(!filter.accept("A", HookKeyState::Down, 23 * MS));
+        assert!(filter.accept("B", HookKeyState::Up, 40 * MS));
+
+        let stats = filter.stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].key, "A");
+    }
+
+    #[test]
+    fn zero_threshold_accepts_everything() {
+        let filter = ChatterFilter::new(0);
+        assert!(filter.accept("A", HookKeyState::Up, 0));
+        assert!(filter.accept("A", HookKeyState::Down, 1));
+        assert!(filter.stats().is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+//! 키 카운터 주기 저장 서비스
+//!
+//! 카운터는 메모리에서만 올라가고 종료·초기화 때만 저장되면 크래시나 강제 종료 시 한 세션의
+//! 카운트를 통째로 잃습니다. 입력 스레드는 증가를 알리기만 하고, 실제 저장은 워커 스레드가
+//! 다음 중 먼저 오는 시점에 합니다.
+//! - 마지막 증가 후 `IDLE_FLUSH` 동안 입력이 없을 때
+//! - 저장되지 않은 증가가 `MAX_PENDING_INCREMENTS`개 쌓였을 때
+//! - 쉬지 않고 입력이 이어져도 첫 미저장 증가 후 `MAX_FLUSH_INTERVAL`이 지났을 때
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use super::deadline_worker::{DeadlineState, DeadlineWorker};
+
+const IDLE_FLUSH: Duration = Duration::from_secs(3);
+const MAX_FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+const MAX_PENDING_INCREMENTS: u32 = 500;
+
+#[derive(Default)]
+struct State {
+    /// 마지막 저장 이후 증가 수
+    pending: u32,
+    /// 첫 미저장 증가 시각
+    first_at: Option<Instant>,
+    last_at: Option<Instant>,
+}
+
+impl State {
+    fn mark(&mut self, now: Instant) {
+        self.pending = self.pending.saturating_add(1);
+        self.first_at.get_or_insert(now);
+        self.last_at = Some(now);
+    }
+}
+
+impl DeadlineState for State {
+    type Due = ();
+
+    /// 저장할 시각 (미저장 증가가 없으면 `None`)
+    fn deadline(&self) -> Option<Instant> {
+        if self.pending >= MAX_PENDING_INCREMENTS {
+            return self.first_at;
+        }
+        let idle = self.last_at? + IDLE_FLUSH;
+        let longest = self.first_at? + MAX_FLUSH_INTERVAL;
+        Some(idle.min(longest))
+    }
+
+    fn take_due(&mut self, now: Instant) -> Option<()> {
+        if self.deadline()? > now {
+            return None;
+        }
+        *self = State::default();
+        Some(())
+    }
+
+    /// 종료 시 남은 증가는 바로 저장
+    fn take_remaining(&mut self) -> Option<()> {
+        self.deadline()?;
+        *self = State::default();
+        Some(())
+    }
+
+    /// 다음 유휴 시점에 다시 시도
+    fn retry(&mut self, now: Instant) {
+        self.mark(now);
+    }
+}
+
+/// 카운터 저장 예약기 (저장은 워커 스레드가 콜백으로 수행)
+pub struct CounterFlusher {
+    worker: DeadlineWorker<State>,
+}
+
+impl CounterFlusher {
+    pub fn spawn<F>(mut flush: F) -> Result<Self>
+    where
+        F: FnMut() -> Result<()> + Send + 'static,
+    {
+        let worker = DeadlineWorker::spawn("counter-flush", State::default(), move |()| {
+            flush().inspect_err(|err| log::warn!("failed to persist key counters: {err}"))
+        })?;
+        Ok(Self { worker })
+    }
+
+    /// 카운터가 증가했음을 알림 (잠깐 잠금만 잡고 바로 반환)
+    pub fn mark_dirty(&self) {
+        let mut state = self.worker.lock();
+        state.mark(Instant::now());
+        // 워커는 이미 잡힌 저장 시각보다 늦게 깨어나지 않으므로 저장 시각이 앞당겨질 때만 깨움
+        if state.pending == 1 || state.pending == MAX_PENDING_INCREMENTS {
+            drop(state);
+            self.worker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn flushes_after_idle_period() {
+        let start = Instant::now();
+        let mut state = State::default();
+        assert_eq!(state.deadline(), None);
+        assert_eq!(state.take_due(start + MAX_FLUSH_INTERVAL), None);
+
+        state.mark(start);
+        state.mark(start + SECOND);
+        assert_eq!(state.deadline(), Some(start + SECOND + IDLE_FLUSH));
+        assert_eq!(state.take_due(start + IDLE_FLUSH), None);
+        assert_eq!(state.take_due(start + SECOND + IDLE_FLUSH), Some(()));
+        assert_eq!(state.pending, 0);
+        assert_eq!(state.deadline(), None);
+    }
+
+    #[test]
+    fn steady_input_still_flushes_at_max_interval() {
+        let start = Instant::now();
+        let mut state = State::default();
+        let mut at = start;
+        while at < start + MAX_FLUSH_INTERVAL + 5 * SECOND {
+            state.mark(at);
+            at += SECOND;
+        }
+        assert_eq!(state.deadline(), Some(start + MAX_FLUSH_INTERVAL));
+    }
+
+    #[test]
+    fn many_pending_increments_flush_immediately() {
+        let start = Instant::now();
+        let mut state = State::default();
+        for _ in 0..MAX_PENDING_INCREMENTS - 1 {
+            state.mark(start + SECOND);
+        }
+        assert_eq!(state.deadline(), Some(start + SECOND + IDLE_FLUSH));
+        state.mark(start + SECOND);
+        assert_eq!(state.deadline(), Some(start + SECOND));
+    }
+
+    #[test]
+    fn retry_waits_another_idle_period_and_stop_takes_the_rest() {
+        let start = Instant::now();
+        let mut state = State::default();
+        assert_eq!(state.take_remaining(), None);
+
+        state.retry(start);
+        assert_eq!(state.deadline(), Some(start + IDLE_FLUSH));
+        assert_eq!(state.take_remaining(), Some(()));
+        assert_eq!(state.take_remaining(), None);
+    }
+
+    #[test]
+    fn drop_flushes_pending_increments() {
+        let flushes = Arc::new(AtomicU32::new(0));
+        let flusher = {
+            let flushes = flushes.clone();
+            CounterFlusher::spawn(move || {
+                flushes.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+            .unwrap()
+        };
+        flusher.mark_dirty();
+        drop(flusher);
+        assert_eq!(flushes.load(Ordering::SeqCst), 1);
+    }
+}
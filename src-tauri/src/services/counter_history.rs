@@ -0,0 +1,383 @@
+//! 키 카운터 기록 서비스
+//!
+//! `KeyCounters`는 키마다 누적 합계 하나뿐이라 "오늘·이번 주에 몇 번"을 알 수 없습니다.
+//! 카운터가 올라갈 때마다 로컬 시각 기준 한 시간 칸(모드 → 키 → 횟수)에 더하고,
+//! 보관 기간(`history_retention_days`)이 지난 시간 칸은 하루 칸으로 합쳐 둡니다.
+//! - `store.json`이 커지지 않도록 별도 파일(`counter_history.json`)에 저장
+//! - 저장은 카운터 주기 저장과 함께 (입력 스레드는 메모리에만 더함)
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::{Days, Local, NaiveDate};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::store::{load_json_or_quarantine, save_json};
+
+const FILE_NAME: &str = "counter_history.json";
+const DAY_FORMAT: &str = "%Y-%m-%d";
+const HOUR_FORMAT: &str = "%Y-%m-%dT%H";
+
+/// 칸 하나의 횟수 (모드 → 키 → 횟수)
+pub type HistoryCounts = BTreeMap<String, BTreeMap<String, u64>>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HistoryFile {
+    /// `YYYY-MM-DDTHH` → 횟수 (보관 기간 안의 칸만)
+    #[serde(default)]
+    hourly: BTreeMap<String, HistoryCounts>,
+    /// `YYYY-MM-DD` → 횟수 (보관 기간이 지나 합쳐진 칸)
+    #[serde(default)]
+    daily: BTreeMap<String, HistoryCounts>,
+}
+
+/// 조회 단위
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryGranularity {
+    /// 보관 기간 안의 날짜만 조회됨
+    Hour,
+    Day,
+}
+
+/// 조회 결과의 칸 하나
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryBucket {
+    /// `YYYY-MM-DD` 또는 `YYYY-MM-DDTHH` (로컬 시각)
+    pub period: String,
+    pub total: u64,
+    pub counts: HistoryCounts,
+}
+
+/// 기간 조회 결과 (입력이 없던 칸은 빠짐)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CounterHistoryRange {
+    pub from: String,
+    pub to: String,
+    pub granularity: HistoryGranularity,
+    pub buckets: Vec<HistoryBucket>,
+}
+
+impl CounterHistoryRange {
+    /// 스프레드시트용 CSV (`period,mode,key,count`)
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("period,mode,key,count\n");
+        for bucket in &self.buckets {
+            for (mode, keys) in &bucket.counts {
+                for (key, count) in keys {
+                    csv.push_str(&format!(
+                        "{},{},{},{}\n",
+                        bucket.period,
+                        csv_field(mode),
+                        csv_field(key),
+                        count
+                    ));
+                }
+            }
+        }
+        csv
+    }
+}
+
+/// CSV 칸 하나 (쉼표·따옴표·줄바꿈이 있으면 따옴표로 감쌈)
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn add_counts(target: &mut HistoryCounts, source: &HistoryCounts) {
+    for (mode, keys) in source {
+        let entry = target.entry(mode.clone()).or_default();
+        for (key, count) in keys {
+            *entry.entry(key.clone()).or_insert(0) += count;
+        }
+    }
+}
+
+struct State {
+    file: HistoryFile,
+    /// 마지막 저장 이후 바뀌었는지
+    dirty: bool,
+}
+
+/// 시간·하루 단위 카운터 기록
+pub struct CounterHistory {
+    path: PathBuf,
+    state: Mutex<State>,
+    /// 종료 시 저장과 주기 저장이 같은 임시 파일에 겹쳐 쓰지 않도록
+    write_lock: Mutex<()>,
+}
+
+impl CounterHistory {
+    /// `dir`의 기록 파일을 읽음. 읽을 수 없으면 손상 파일을 옆으로 옮기고 빈 기록으로 시작
+    pub fn load(dir: &Path, retention_days: u32) -> Self {
+        let path = dir.join(FILE_NAME);
+        let file = load_json_or_quarantine(&path, "counter history");
+
+        let history = Self {
+            path,
+            state: Mutex::new(State { file, dirty: false }),
+            write_lock: Mutex::new(()),
+        };
+        history.compact(retention_days);
+        history
+    }
+
+    /// 지금 시간 칸에 한 번 더함
+    pub fn record(&self, mode: &str, key: &str) {
+        let hour = Local::now().format(HOUR_FORMAT).to_string();
+        let mut state = self.state.lock();
+        let count = state
+            .file
+            .hourly
+            .entry(hour)
+            .or_default()
+            .entry(mode.to_string())
+            .or_default()
+            .entry(key.to_string())
+            .or_insert(0);
+        *count += 1;
+        state.dirty = true;
+    }
+
+    /// 보관 기간이 지난 시간 칸을 하루 칸으로 합침
+    fn compact(&self, retention_days: u32) {
+        let cutoff = Local::now()
+            .date_naive()
+            .checked_sub_days(Days::new(u64::from(retention_days)))
+            .map(|date| date.format(DAY_FORMAT).to_string())
+            .unwrap_or_default();
+
+        let mut state = self.state.lock();
+        let expired: Vec<String> = state
+            .file
+            .hourly
+            .keys()
+            .take_while(|hour| hour.get(..10).is_some_and(|day| day < cutoff.as_str()))
+            .cloned()
+            .collect();
+        if expired.is_empty() {
+            return;
+        }
+        for hour in expired {
+            if let Some(counts) = state.file.hourly.remove(&hour) {
+                let day = state.file.daily.entry(hour[..10].to_string()).or_default();
+                add_counts(day, &counts);
+            }
+        }
+        state.dirty = true;
+    }
+
+    /// 바뀐 내용이 있으면 합친 뒤 파일에 저장
+    pub fn save(&self, retention_days: u32) -> Result<()> {
+        self.compact(retention_days);
+        let _write = self.write_lock.lock();
+        let snapshot = {
+            let mut state = self.state.lock();
+            if !state.dirty {
+                return Ok(());
+            }
+            state.dirty = false;
+            state.file.clone()
+        };
+
+        let result = save_json(&self.path, &snapshot);
+        if result.is_err() {
+            self.state.lock().dirty = true;
+        }
+        result
+    }
+
+    /// `from`..=`to` (로컬 날짜) 기간의 칸. `mode`를 주면 그 모드만
+    pub fn query(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        granularity: HistoryGranularity,
+        mode: Option<&str>,
+    ) -> CounterHistoryRange {
+        let from = from.format(DAY_FORMAT).to_string();
+        let to = to.format(DAY_FORMAT).to_string();
+        let in_range = |period: &str| {
+            period
+                .get(..10)
+                .is_some_and(|day| day >= from.as_str() && day <= to.as_str())
+        };
+
+        let state = self.state.lock();
+        let mut periods: BTreeMap<String, HistoryCounts> = BTreeMap::new();
+        for (hour, counts) in state.file.hourly.iter().filter(|(hour, _)| in_range(hour)) {
+            let period = match granularity {
+                HistoryGranularity::Hour => hour.clone(),
+                HistoryGranularity::Day => hour[..10].to_string(),
+            };
+            add_counts(periods.entry(period).or_default(), counts);
+        }
+        if granularity == HistoryGranularity::Day {
+            for (day, counts) in state.file.daily.iter().filter(|(day, _)| in_range(day)) {
+                add_counts(periods.entry(day.clone()).or_default(), counts);
+            }
+        }
+        drop(state);
+
+        let buckets = periods
+            .into_iter()
+            .filter_map(|(period, mut counts)| {
+                if let Some(mode) = mode {
+                    counts.retain(|name, _| name == mode);
+                }
+                let total: u64 = counts.values().flat_map(|keys| keys.values()).sum();
+                (total > 0).then_some(HistoryBucket {
+                    period,
+                    total,
+                    counts,
+                })
+            })
+            .collect();
+
+        CounterHistoryRange {
+            from,
+            to,
+            granularity,
+            buckets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(mode: &str, key: &str, count: u64) -> HistoryCounts {
+        BTreeMap::from([(mode.to_string(), BTreeMap::from([(key.to_string(), count)]))])
+    }
+
+    fn history(
+        hourly: &[(&str, HistoryCounts)],
+        daily: &[(&str, HistoryCounts)],
+    ) -> CounterHistory {
+        let collect = |entries: &[(&str, HistoryCounts)]| {
+            entries
+                .iter()
+                .map(|(period, counts)| (period.to_string(), counts.clone()))
+                .collect()
+        };
+        CounterHistory {
+            path: PathBuf::new(),
+            state: Mutex::new(State {
+                file: HistoryFile {
+                    hourly: collect(hourly),
+                    daily: collect(daily),
+                },
+                dirty: false,
+            }),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, DAY_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn compaction_merges_expired_hours_into_days() {
+        let today = Local::now().format(HOUR_FORMAT).to_string();
+        let history = history(
+            &[
+                ("2020-03-01T08", counts("4k", "D", 2)),
+                ("2020-03-01T21", counts("4k", "D", 3)),
+                ("2020-03-02T00", counts("4k", "F", 1)),
+                (&today, counts("4k", "D", 7)),
+            ],
+            &[("2020-03-01", counts("4k", "D", 10))],
+        );
+        history.compact(30);
+
+        let state = history.state.lock();
+        assert!(state.dirty);
+        assert_eq!(state.file.hourly.keys().collect::<Vec<_>>(), [&today]);
+        assert_eq!(state.file.daily["2020-03-01"], counts("4k", "D", 15));
+        assert_eq!(state.file.daily["2020-03-02"], counts("4k", "F", 1));
+    }
+
+    #[test]
+    fn query_includes_both_bounds_only() {
+        let history = history(
+            &[
+                ("2026-01-01T23", counts("4k", "D", 1)),
+                ("2026-01-02T00", counts("4k", "D", 2)),
+                ("2026-01-03T09", counts("4k", "D", 4)),
+                ("2026-01-04T00", counts("4k", "D", 8)),
+            ],
+            &[("2026-01-01", counts("4k", "D", 16))],
+        );
+
+        let days = history.query(
+            date("2026-01-02"),
+            date("2026-01-03"),
+            HistoryGranularity::Day,
+            None,
+        );
+        let periods: Vec<(&str, u64)> = days
+            .buckets
+            .iter()
+            .map(|bucket| (bucket.period.as_str(), bucket.total))
+            .collect();
+        assert_eq!(periods, [("2026-01-02", 2), ("2026-01-03", 4)]);
+
+        // Compacted days are only part of daily queries.
+        let hours = history.query(
+            date("2026-01-01"),
+            date("2026-01-01"),
+            HistoryGranularity::Hour,
+            None,
+        );
+        assert_eq!(hours.buckets.len(), 1);
+        assert_eq!(hours.buckets[0].period, "2026-01-01T23");
+
+        let other_mode = history.query(
+            date("2026-01-01"),
+            date("2026-01-04"),
+            HistoryGranularity::Day,
+            Some("8k"),
+        );
+        assert!(other_mode.buckets.is_empty());
+    }
+
+    #[test]
+    fn csv_quotes_key_names_with_separators() {
+        let range = CounterHistoryRange {
+            from: "2026-01-01".into(),
+            to: "2026-01-01".into(),
+            granularity: HistoryGranularity::Day,
+            buckets: vec![HistoryBucket {
+                period: "2026-01-01".into(),
+                total: 6,
+                counts: BTreeMap::from([(
+                    "4k".to_string(),
+                    BTreeMap::from([
+                        (",".to_string(), 1),
+                        ("\"".to_string(), 2),
+                        ("A".to_string(), 3),
+                    ]),
+                )]),
+            }],
+        };
+        assert_eq!(
+            range.to_csv(),
+            "period,mode,key,count\n\
+             2026-01-01,4k,\"\"\"\",2\n\
+             2026-01-01,4k,\",\",1\n\
+             2026-01-01,4k,A,3\n"
+        );
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+    }
+}
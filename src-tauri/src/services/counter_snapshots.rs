@@ -0,0 +1,378 @@
+//! 키 카운터 스냅샷 서비스
+//!
+//! 카운터 초기화는 되돌릴 수 없어 잘못 누르면 몇 달 치 기록이 사라집니다. 초기화 직전마다
+//! 초기화되는 범위(전체·모드·키)의 값을 스냅샷으로 남겨 두고, 나중에 그 범위만 되돌립니다.
+//! 예약 초기화(매일·매주·앱 시작)도 이전 값을 지우지 않고 스냅샷으로 보관합니다.
+//!
+//! 스냅샷은 `counter_snapshots.json`에 최근 `MAX_SNAPSHOTS`개까지 저장합니다. 초기화는 리더
+//! 스레드(단축키)에서도 일어나므로 스냅샷은 메모리에만 남기고, 저장은 카운터 주기 저장과 함께 합니다.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Local};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::clock::{monotonic_us, wall_clock_offset_us};
+use crate::models::{CounterResetSchedule, KeyCounters};
+use crate::store::{load_json_or_quarantine, save_json};
+
+const FILE_NAME: &str = "counter_snapshots.json";
+const MAX_SNAPSHOTS: usize = 500;
+
+/// 스냅샷을 남긴 이유
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SnapshotReason {
+    /// 사용자가 초기화
+    Reset,
+    /// 예약 초기화로 보관
+    Scheduled,
+    /// 스냅샷 복원 직전 값 (복원도 되돌릴 수 있도록)
+    Restore,
+}
+
+/// 초기화 직전의 카운터 값
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CounterSnapshot {
+    pub id: String,
+    /// 만든 시각 (Unix ms)
+    pub created_at_ms: u64,
+    pub reason: SnapshotReason,
+    /// 특정 모드만 초기화한 경우 그 모드
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    /// 키 하나만 초기화한 경우 그 키
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub total: u64,
+    /// 범위 안의 카운터 값 (복원 시 이 값들만 되돌림)
+    pub counters: KeyCounters,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SnapshotFile {
+    /// 오래된 순
+    #[serde(default)]
+    snapshots: Vec<CounterSnapshot>,
+    /// 마지막 예약 초기화 구간 (`daily:2026-01-31`, `weekly:2026-W05`)
+    #[serde(default)]
+    last_scheduled_period: Option<String>,
+}
+
+/// 예약 초기화 구간 이름 (같은 구간 안에서는 한 번만 초기화)
+fn schedule_period(schedule: CounterResetSchedule, now: DateTime<Local>) -> Option<String> {
+    match schedule {
+        CounterResetSchedule::Daily => Some(format!("daily:{}", now.format("%Y-%m-%d"))),
+        CounterResetSchedule::Weekly => {
+            let week = now.iso_week();
+            Some(format!("weekly:{}-W{:02}", week.year(), week.week()))
+        }
+        CounterResetSchedule::Off | CounterResetSchedule::AppStart => None,
+    }
+}
+
+/// 스냅샷 값으로 되돌림 (삭제된 모드·키는 건너뜀)
+pub fn restore_counters(counters: &mut KeyCounters, snapshot: &CounterSnapshot) {
+    for (name, keys) in &snapshot.counters {
+        if let Some(entry) = counters.get_mut(name) {
+            for (label, count) in keys {
+                if let Some(value) = entry.get_mut(label) {
+                    *value = *count;
+                }
+            }
+        }
+    }
+}
+
+/// `counters`에서 범위(`mode`, `key`)에 해당하는 값만 추림
+pub fn scoped_counters(
+    counters: &KeyCounters,
+    mode: Option<&str>,
+    key: Option<&str>,
+) -> KeyCounters {
+    counters
+        .iter()
+        .filter(|(name, _)| mode.is_none_or(|mode| mode == name.as_str()))
+        .map(|(name, keys)| {
+            let keys = keys
+                .iter()
+                .filter(|(label, _)| key.is_none_or(|key| key == label.as_str()))
+                .map(|(label, count)| (label.clone(), *count))
+                .collect();
+            (name.clone(), keys)
+        })
+        .collect()
+}
+
+struct State {
+    file: SnapshotFile,
+    /// 마지막 저장 이후 바뀌었는지
+    dirty: bool,
+}
+
+/// 카운터 스냅샷 보관소
+pub struct CounterSnapshots {
+    path: PathBuf,
+    state: Mutex<State>,
+    /// 주기 저장과 삭제·종료 시 저장이 같은 임시 파일에 겹쳐 쓰지 않도록
+    write_lock: Mutex<()>,
+}
+
+impl CounterSnapshots {
+    /// `dir`의 스냅샷 파일을 읽음. 읽을 수 없으면 손상 파일을 옆으로 옮기고 빈 목록으로 시작
+    pub fn load(dir: &Path) -> Self {
+        let path = dir.join(FILE_NAME);
+        let file = load_json_or_quarantine(&path, "counter snapshots");
+        Self {
+            path,
+            state: Mutex::new(State { file, dirty: false }),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// 초기화 직전 범위 안의 값(`scoped_counters`)을 스냅샷으로 남김. 모두 0이면 남기지 않음.
+    /// 메모리에만 기록하므로 호출자가 `save`를 예약해야 함
+    pub fn archive(
+        &self,
+        counters: KeyCounters,
+        reason: SnapshotReason,
+        mode: Option<&str>,
+        key: Option<&str>,
+    ) {
+        let total: u64 = counters
+            .values()
+            .flat_map(|keys| keys.values())
+            .map(|count| u64::from(*count))
+            .sum();
+        if total == 0 {
+            return;
+        }
+        let now_us = monotonic_us() as i64 + wall_clock_offset_us();
+        let snapshot = CounterSnapshot {
+            id: uuid::Uuid::new_v4().to_string(),
+            created_at_ms: now_us.max(0) as u64 / 1000,
+            reason,
+            mode: mode.map(str::to_string),
+            key: key.map(str::to_string),
+            total,
+            counters,
+        };
+
+        let mut state = self.state.lock();
+        state.file.snapshots.push(snapshot);
+        let overflow = state.file.snapshots.len().saturating_sub(MAX_SNAPSHOTS);
+        state.file.snapshots.drain(..overflow);
+        state.dirty = true;
+    }
+
+    /// 스냅샷 목록 (최근 순)
+    pub fn list(&self) -> Vec<CounterSnapshot> {
+        self.state
+            .lock()
+            .file
+            .snapshots
+            .iter()
+            .rev()
+            .cloned()
+            .collect()
+    }
+
+    pub fn get(&self, id: &str) -> Option<CounterSnapshot> {
+        self.state
+            .lock()
+            .file
+            .snapshots
+            .iter()
+            .find(|snapshot| snapshot.id == id)
+            .cloned()
+    }
+
+    /// 지운 스냅샷이 있으면 `true`
+    pub fn delete(&self, id: &str) -> Result<bool> {
+        {
+            let mut state = self.state.lock();
+            let before = state.file.snapshots.len();
+            state.file.snapshots.retain(|snapshot| snapshot.id != id);
+            if state.file.snapshots.len() == before {
+                return Ok(false);
+            }
+            state.dirty = true;
+        }
+        self.save().map(|_| true)
+    }
+
+    /// 예약 초기화가 필요하면 `true`를 반환하고 이번 구간을 기록.
+    /// 처음 켰거나 주기를 바꾼 경우에는 구간만 기록 (바로 초기화하지 않음)
+    pub fn take_scheduled_reset(&self, schedule: CounterResetSchedule) -> bool {
+        self.take_scheduled_reset_at(schedule, Local::now())
+    }
+
+    fn take_scheduled_reset_at(
+        &self,
+        schedule: CounterResetSchedule,
+        now: DateTime<Local>,
+    ) -> bool {
+        let Some(period) = schedule_period(schedule, now) else {
+            return false;
+        };
+        let same_schedule = {
+            let mut state = self.state.lock();
+            let previous = state.file.last_scheduled_period.as_deref();
+            if previous == Some(period.as_str()) {
+                return false;
+            }
+            let same_schedule = previous
+                .and_then(|previous| previous.split_once(':'))
+                .zip(period.split_once(':'))
+                .is_some_and(|((before, _), (now, _))| before == now);
+            state.file.last_scheduled_period = Some(period);
+            state.dirty = true;
+            same_schedule
+        };
+        // 기록을 못 남겨도 이번 초기화는 진행 (다음 실행에서 한 번 더 초기화될 수 있음)
+        if let Err(err) = self.save() {
+            log::warn!("failed to record scheduled counter reset: {err:#}");
+        }
+        same_schedule
+    }
+
+    /// 바뀐 내용이 있으면 파일에 저장
+    pub fn save(&self) -> Result<()> {
+        let _write = self.write_lock.lock();
+        let file = {
+            let mut state = self.state.lock();
+            if !state.dirty {
+                return Ok(());
+            }
+            state.dirty = false;
+            state.file.clone()
+        };
+
+        let result = save_json(&self.path, &file);
+        if result.is_err() {
+            self.state.lock().dirty = true;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_support::ScratchDir;
+    use chrono::TimeZone;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, min: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(year, month, day, hour, min, 0)
+            .unwrap()
+    }
+
+    fn scratch() -> (ScratchDir, CounterSnapshots) {
+        let dir = ScratchDir::new();
+        let snapshots = CounterSnapshots::load(&dir);
+        (dir, snapshots)
+    }
+
+    fn counters(entries: &[(&str, &str, u32)]) -> KeyCounters {
+        let mut counters = KeyCounters::new();
+        for (mode, key, count) in entries {
+            counters
+                .entry(mode.to_string())
+                .or_default()
+                .insert(key.to_string(), *count);
+        }
+        counters
+    }
+
+    #[test]
+    fn period_names_follow_local_day_and_iso_week() {
+        let daily = CounterResetSchedule::Daily;
+        let weekly = CounterResetSchedule::Weekly;
+        assert_eq!(
+            schedule_period(daily, at(2026, 1, 31, 23, 59)).as_deref(),
+            Some("daily:2026-01-31")
+        );
+        assert_eq!(
+            schedule_period(daily, at(2026, 2, 1, 0, 0)).as_deref(),
+            Some("daily:2026-02-01")
+        );
+        // Sunday still belongs to the week that started on Monday.
+        assert_eq!(
+            schedule_period(weekly, at(2026, 1, 4, 23, 59)).as_deref(),
+            Some("weekly:2026-W01")
+        );
+        assert_eq!(
+            schedule_period(weekly, at(2026, 1, 5, 0, 0)).as_deref(),
+            Some("weekly:2026-W02")
+        );
+        // The first days of 2027 are in the last ISO week of 2026.
+        assert_eq!(
+            schedule_period(weekly, at(2027, 1, 1, 12, 0)).as_deref(),
+            Some("weekly:2026-W53")
+        );
+        assert_eq!(
+            schedule_period(CounterResetSchedule::AppStart, at(2026, 1, 1, 0, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn scheduled_reset_fires_once_per_new_period() {
+        let (dir, snapshots) = scratch();
+        let daily = CounterResetSchedule::Daily;
+        let weekly = CounterResetSchedule::Weekly;
+
+        // First run only records the period.
+        assert!(!snapshots.take_scheduled_reset_at(daily, at(2026, 3, 2, 23, 59)));
+        assert!(!snapshots.take_scheduled_reset_at(daily, at(2026, 3, 2, 23, 59)));
+        assert!(snapshots.take_scheduled_reset_at(daily, at(2026, 3, 3, 0, 0)));
+        assert!(!snapshots.take_scheduled_reset_at(daily, at(2026, 3, 3, 18, 0)));
+
+        // Switching schedule records the new period without resetting.
+        assert!(!snapshots.take_scheduled_reset_at(weekly, at(2026, 3, 8, 12, 0)));
+        assert!(!snapshots.take_scheduled_reset_at(weekly, at(2026, 3, 8, 23, 59)));
+        assert!(snapshots.take_scheduled_reset_at(weekly, at(2026, 3, 9, 0, 0)));
+
+        // The period survives a reload.
+        let reloaded = CounterSnapshots::load(&dir);
+        assert!(!reloaded.take_scheduled_reset_at(weekly, at(2026, 3, 10, 0, 0)));
+    }
+
+    #[test]
+    fn restore_round_trip_only_touches_the_scope() {
+        let (dir, snapshots) = scratch();
+        let mut live = counters(&[("4k", "D", 5), ("4k", "F", 3), ("8k", "A", 7)]);
+
+        let previous = scoped_counters(&live, Some("4k"), None);
+        live.get_mut("4k")
+            .unwrap()
+            .values_mut()
+            .for_each(|count| *count = 0);
+        snapshots.archive(previous, SnapshotReason::Reset, Some("4k"), None);
+        // Nothing to keep for an all-zero scope.
+        snapshots.archive(
+            scoped_counters(&live, Some("4k"), None),
+            SnapshotReason::Reset,
+            Some("4k"),
+            None,
+        );
+        snapshots.save().unwrap();
+
+        let listed = CounterSnapshots::load(&dir).list();
+        assert_eq!(listed.len(), 1);
+        let snapshot = &listed[0];
+        assert_eq!(snapshot.total, 8);
+        assert_eq!(snapshot.mode.as_deref(), Some("4k"));
+
+        // A key deleted since the reset is skipped, not recreated.
+        live.get_mut("4k").unwrap().remove("F");
+        live.get_mut("8k").unwrap().insert("A".into(), 9);
+        restore_counters(&mut live, snapshot);
+        assert_eq!(live, counters(&[("4k", "D", 5), ("8k", "A", 9)]));
+    }
+}
@@ -0,0 +1,189 @@
+//! 키보드 데몬 감시 서비스
+//!
+//! 데몬이 종료되거나 링크가 끊기면 지수 백오프로 재시작 시점을 정하고 상태를 기록합니다.
+//! - 실행/재시작 중/실패 상태와 사유
+//! - 누적 크래시 횟수와 재시작 횟수
+//! - 최근 stderr 줄 보관 (UI에서 중단 원인 표시용)
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+/// 보관할 stderr 줄 수
+const STDERR_TAIL_LINES: usize = 50;
+/// 첫 재시작 대기 시간
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+/// 재시작 대기 시간 상한
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// 연속 실패가 이 횟수를 넘으면 재시작을 포기
+const MAX_CONSECUTIVE_FAILURES: u32 = 8;
+/// 이 시간 이상 정상 동작했다면 백오프를 처음부터 다시 계산
+const STABLE_AFTER: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DaemonStatus {
+    /// 아직 시작 전
+    Stopped,
+    Running,
+    Restarting,
+    Failed,
+}
+
+/// `daemon:status` 이벤트 페이로드
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaemonStatusPayload {
+    pub status: DaemonStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub crash_count: u32,
+    /// 재시작까지 남은 시간 (재시작 중일 때만)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_in_ms: Option<u64>,
+}
+
+/// 데몬 상태 조회 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaemonHealth {
+    pub status: DaemonStatus,
+    pub reason: Option<String>,
+    pub crash_count: u32,
+    pub restart_count: u32,
+    pub consecutive_failures: u32,
+    pub stderr_tail: Vec<String>,
+}
+
+/// 크래시 처리 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrashAction {
+    /// 대기 후 재시작
+    Restart(Duration),
+    /// 재시작 포기
+    GiveUp,
+}
+
+struct Inner {
+    status: DaemonStatus,
+    reason: Option<String>,
+    crash_count: u32,
+    restart_count: u32,
+    consecutive_failures: u32,
+    running_since: Option<Instant>,
+    stderr_tail: VecDeque<String>,
+}
+
+pub struct DaemonSupervisor {
+    inner: Mutex<Inner>,
+}
+
+impl Default for DaemonSupervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DaemonSupervisor {
+    pub fn new() -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                status: DaemonStatus::Stopped,
+                reason: None,
+                crash_count: 0,
+                restart_count: 0,
+                consecutive_failures: 0,
+                running_since: None,
+                stderr_tail: VecDeque::with_capacity(STDERR_TAIL_LINES),
+            }),
+        }
+    }
+
+    /// 핸드셰이크 성공 시 호출
+    pub fn mark_running(&self) -> DaemonStatusPayload {
+        let mut inner = self.inner.lock();
+        if inner.status == DaemonStatus::Restarting {
+            inner.restart_count = inner.restart_count.saturating_add(1);
+        }
+        inner.status = DaemonStatus::Running;
+        inner.reason = None;
+        inner.running_since = Some(Instant::now());
+        Self::payload(&inner, None)
+    }
+
+    /// 예기치 않은 종료를 기록하고 다음 동작을 결정
+    pub fn record_crash(
+        &self,
+        reason: String,
+        permanent: bool,
+    ) -> (CrashAction, DaemonStatusPayload) {
+        let mut inner = self.inner.lock();
+        if inner
+            .running_since
+            .take()
+            .is_some_and(|since| since.elapsed() >= STABLE_AFTER)
+        {
+            inner.consecutive_failures = 0;
+        }
+        inner.crash_count = inner.crash_count.saturating_add(1);
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        inner.reason = Some(reason);
+
+        let action = if permanent || inner.consecutive_failures > MAX_CONSECUTIVE_FAILURES {
+            inner.status = DaemonStatus::Failed;
+            CrashAction::GiveUp
+        } else {
+            inner.status = DaemonStatus::Restarting;
+            CrashAction::Restart(backoff(inner.consecutive_failures))
+        };
+        let retry_in_ms = match action {
+            CrashAction::Restart(delay) => Some(delay.as_millis() as u64),
+            CrashAction::GiveUp => None,
+        };
+        (action, Self::payload(&inner, retry_in_ms))
+    }
+
+    /// 수동 재시작 전에 연속 실패 횟수를 초기화
+    pub fn reset_backoff(&self) {
+        self.inner.lock().consecutive_failures = 0;
+    }
+
+    pub fn record_stderr(&self, line: String) {
+        let mut inner = self.inner.lock();
+        if inner.stderr_tail.len() == STDERR_TAIL_LINES {
+            inner.stderr_tail.pop_front();
+        }
+        inner.stderr_tail.push_back(line);
+    }
+
+    pub fn health(&self) -> DaemonHealth {
+        let inner = self.inner.lock();
+        DaemonHealth {
+            status: inner.status,
+            reason: inner.reason.clone(),
+            crash_count: inner.crash_count,
+            restart_count: inner.restart_count,
+            consecutive_failures: inner.consecutive_failures,
+            stderr_tail: inner.stderr_tail.iter().cloned().collect(),
+        }
+    }
+
+    fn payload(inner: &Inner, retry_in_ms: Option<u64>) -> DaemonStatusPayload {
+        DaemonStatusPayload {
+            status: inner.status,
+            reason: inner.reason.clone(),
+            crash_count: inner.crash_count,
+            retry_in_ms,
+        }
+    }
+}
+
+/// 연속 실패 횟수에 따른 대기 시간 (0.5s, 1s, 2s, ... 최대 30s)
+fn backoff(failures: u32) -> Duration {
+    let exponent = failures.saturating_sub(1).min(16);
+    INITIAL_BACKOFF
+        .saturating_mul(1u32 << exponent)
+        .min(MAX_BACKOFF)
+}
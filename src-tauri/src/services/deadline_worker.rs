@@ -0,0 +1,216 @@
+//! 기한 워커
+//!
+//! 입력 스레드가 상태에 할 일을 넣으면, 워커 스레드가 가장 이른 기한에 깨어나 할 일을 꺼내
+//! 콜백으로 처리합니다. 카운터 저장·휠 펄스·제스처·이벤트 묶음이 같은 스레드 구조를 씁니다.
+//! - 상태는 잠금 하나로 보호, 기한이 앞당겨질 때만 호출자가 깨움
+//! - 콜백은 잠금 없이 실행 (입력 스레드가 파일 쓰기·이벤트 전달을 기다리지 않도록)
+//! - 드롭하면 워커를 멈추고 합류
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Instant;
+
+use anyhow::{anyhow, Result};
+use parking_lot::{Condvar, Mutex, MutexGuard};
+
+/// 워커가 다루는 상태
+pub trait DeadlineState: Send + 'static {
+    /// 기한이 된 할 일
+    type Due;
+
+    /// 다음에 깨어날 시각 (할 일이 없으면 `None`)
+    fn deadline(&self) -> Option<Instant>;
+
+    /// `now`까지 기한이 된 할 일을 꺼냄
+    fn take_due(&mut self, now: Instant) -> Option<Self::Due>;
+
+    /// 종료 시 마저 처리할 할 일 (기본: 버림)
+    fn take_remaining(&mut self) -> Option<Self::Due> {
+        None
+    }
+
+    /// 콜백이 실패했을 때 다시 예약 (기본: 버림)
+    fn retry(&mut self, _now: Instant) {}
+}
+
+struct Shared<S> {
+    state: Mutex<S>,
+    wake: Condvar,
+    running: AtomicBool,
+}
+
+/// 기한 워커 스레드와 그 상태
+pub struct DeadlineWorker<S: DeadlineState> {
+    shared: Arc<Shared<S>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl<S: DeadlineState> DeadlineWorker<S> {
+    /// `name` 스레드를 띄움. 콜백이 `Err`를 반환하면 실행 중일 때만 `retry`로 다시 예약
+    pub fn spawn<F>(name: &str, state: S, mut on_due: F) -> Result<Self>
+    where
+        F: FnMut(S::Due) -> Result<()> + Send + 'static,
+    {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(state),
+            wake: Condvar::new(),
+            running: AtomicBool::new(true),
+        });
+
+        let worker = shared.clone();
+        let handle = thread::Builder::new()
+            .name(name.into())
+            .spawn(move || {
+                let mut state = worker.state.lock();
+                loop {
+                    let running = worker.running.load(Ordering::SeqCst);
+                    let due = if running {
+                        state.take_due(Instant::now())
+                    } else {
+                        state.take_remaining()
+                    };
+
+                    if let Some(due) = due {
+                        // 콜백은 잠금 없이 실행
+                        drop(state);
+                        let result = on_due(due);
+                        state = worker.state.lock();
+                        if result.is_err() && running {
+                            state.retry(Instant::now());
+                        }
+                        continue;
+                    }
+                    if !running {
+                        break;
+                    }
+
+                    match state.deadline() {
+                        Some(deadline) => {
+                            worker.wake.wait_until(&mut state, deadline);
+                        }
+                        None => worker.wake.wait(&mut state),
+                    }
+                }
+            })
+            .map_err(|err| anyhow!("failed to spawn {name} thread: {err}"))?;
+
+        Ok(Self {
+            shared,
+            handle: Some(handle),
+        })
+    }
+
+    /// 상태 잠금. 기한을 앞당겼으면 잠금을 푼 뒤 `wake` 호출
+    pub fn lock(&self) -> MutexGuard<'_, S> {
+        self.shared.state.lock()
+    }
+
+    pub fn wake(&self) {
+        self.shared.wake.notify_one();
+    }
+}
+
+impl<S: DeadlineState> Drop for DeadlineWorker<S> {
+    fn drop(&mut self) {
+        {
+            // 잠금 안에서 내려야 대기 직전의 워커가 깨우기 신호를 놓치지 않음
+            let _state = self.shared.state.lock();
+            self.shared.running.store(false, Ordering::SeqCst);
+        }
+        self.shared.wake.notify_one();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Numbered jobs with their due time; whatever is left is handed over on stop.
+    #[derive(Default)]
+    struct Jobs {
+        pending: Vec<(Instant, u32)>,
+        retried: u32,
+    }
+
+    impl DeadlineState for Jobs {
+        type Due = Vec<u32>;
+
+        fn deadline(&self) -> Option<Instant> {
+            self.pending.iter().map(|(at, _)| *at).min()
+        }
+
+        fn take_due(&mut self, now: Instant) -> Option<Vec<u32>> {
+            let (due, rest): (Vec<_>, Vec<_>) =
+                self.pending.drain(..).partition(|(at, _)| *at <= now);
+            self.pending = rest;
+            (!due.is_empty()).then(|| due.into_iter().map(|(_, id)| id).collect())
+        }
+
+        fn take_remaining(&mut self) -> Option<Vec<u32>> {
+            (!self.pending.is_empty()).then(|| self.pending.drain(..).map(|(_, id)| id).collect())
+        }
+
+        fn retry(&mut self, now: Instant) {
+            self.retried += 1;
+            self.pending.push((now, 99));
+        }
+    }
+
+    fn spawn_jobs(fail: u32) -> (DeadlineWorker<Jobs>, mpsc::Receiver<u32>) {
+        let (tx, rx) = mpsc::channel();
+        let worker = DeadlineWorker::spawn("deadline-test", Jobs::default(), move |ids| {
+            for id in ids {
+                tx.send(id).unwrap();
+                if id == fail {
+                    return Err(anyhow!("job {id} failed"));
+                }
+            }
+            Ok(())
+        })
+        .unwrap();
+        (worker, rx)
+    }
+
+    fn push(worker: &DeadlineWorker<Jobs>, after: Duration, id: u32) {
+        worker.lock().pending.push((Instant::now() + after, id));
+        worker.wake();
+    }
+
+    #[test]
+    fn runs_jobs_in_deadline_order() {
+        let (worker, rx) = spawn_jobs(0);
+        let started = Instant::now();
+        push(&worker, Duration::from_millis(60), 2);
+        push(&worker, Duration::from_millis(20), 1);
+
+        assert_eq!(rx.recv_timeout(TIMEOUT), Ok(1));
+        assert_eq!(rx.recv_timeout(TIMEOUT), Ok(2));
+        assert!(started.elapsed() >= Duration::from_millis(60));
+    }
+
+    #[test]
+    fn drop_hands_remaining_jobs_to_the_callback() {
+        let (worker, rx) = spawn_jobs(0);
+        push(&worker, Duration::from_secs(3600), 7);
+        drop(worker);
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), [7]);
+    }
+
+    #[test]
+    fn failed_job_is_retried_while_running() {
+        let (worker, rx) = spawn_jobs(1);
+        push(&worker, Duration::ZERO, 1);
+
+        assert_eq!(rx.recv_timeout(TIMEOUT), Ok(1));
+        assert_eq!(rx.recv_timeout(TIMEOUT), Ok(99));
+        assert_eq!(worker.lock().retried, 1);
+    }
+}
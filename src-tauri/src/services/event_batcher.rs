@@ -0,0 +1,100 @@
+//! 키 이벤트 묶음 전달 서비스
+//!
+//! 입력마다 `input:raw`·`keys:counter`·`keys:state`를 따로 내보내면 8키 연타처럼 이벤트가
+//! 몰릴 때 직렬화·전달 비용이 커집니다. 묶음 모드에서는 첫 이벤트 이후 설정한 시간(한 프레임
+//! 정도) 동안 들어온 이벤트를 순서대로 모아 `keys:batch` 하나로 보냅니다.
+//! 각 항목의 payload는 개별 이벤트와 같으므로 타임스탬프도 그대로 유지됩니다.
+//! 묶음 모드를 끈 직후에도 남은 묶음이 다 나갈 때까지는 묶음으로 보내 순서를 지킵니다 (`is_busy`).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::deadline_worker::{DeadlineState, DeadlineWorker};
+
+/// 묶음에 들어간 개별 이벤트
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchEntry {
+    /// 개별 전달 시의 이벤트 이름 (`keys:state` 등)
+    pub event: &'static str,
+    pub payload: Value,
+}
+
+#[derive(Default)]
+struct State {
+    entries: Vec<BatchEntry>,
+    /// 현재 묶음을 내보낼 시각 (비어 있으면 `None`)
+    deadline: Option<Instant>,
+    /// 꺼낸 묶음을 콜백이 전달하는 중
+    flushing: Arc<AtomicBool>,
+}
+
+impl State {
+    fn take_entries(&mut self) -> Vec<BatchEntry> {
+        self.deadline = None;
+        self.flushing.store(true, Ordering::SeqCst);
+        std::mem::take(&mut self.entries)
+    }
+}
+
+impl DeadlineState for State {
+    type Due = Vec<BatchEntry>;
+
+    fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    fn take_due(&mut self, now: Instant) -> Option<Vec<BatchEntry>> {
+        if self.deadline? > now {
+            return None;
+        }
+        Some(self.take_entries())
+    }
+
+    /// 종료(데몬 재시작 등) 시 남은 묶음도 전달 (떼기 이벤트가 빠지지 않도록)
+    fn take_remaining(&mut self) -> Option<Vec<BatchEntry>> {
+        (!self.entries.is_empty()).then(|| self.take_entries())
+    }
+}
+
+/// 이벤트 묶음기 (묶음은 워커 스레드가 콜백으로 전달)
+pub struct EventBatcher {
+    worker: DeadlineWorker<State>,
+}
+
+impl EventBatcher {
+    pub fn spawn<F>(mut on_flush: F) -> Result<Self>
+    where
+        F: FnMut(Vec<BatchEntry>) + Send + 'static,
+    {
+        let state = State::default();
+        let flushing = state.flushing.clone();
+        let worker = DeadlineWorker::spawn("key-event-batch", state, move |entries| {
+            on_flush(entries);
+            flushing.store(false, Ordering::SeqCst);
+            Ok(())
+        })?;
+        Ok(Self { worker })
+    }
+
+    /// 아직 전달하지 않았거나 전달 중인 묶음이 있는지
+    pub fn is_busy(&self) -> bool {
+        let state = self.worker.lock();
+        !state.entries.is_empty() || state.flushing.load(Ordering::SeqCst)
+    }
+
+    /// 이벤트를 현재 묶음에 추가. 묶음의 첫 이벤트면 `window` 뒤에 내보내도록 예약
+    pub fn push(&self, event: &'static str, payload: Value, window: Duration) {
+        let mut state = self.worker.lock();
+        state.entries.push(BatchEntry { event, payload });
+        if state.deadline.is_none() {
+            state.deadline = Some(Instant::now() + window);
+            drop(state);
+            self.worker.wake();
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! 시간 값(µs) 히스토그램
+//!
+//! 샘플을 모두 저장하지 않고 로그-선형 칸(2배 구간마다 32칸, 오차 약 3%)에 누적하므로
+//! 오래 켜 두어도 메모리가 늘지 않습니다. 지연 시간 진단과 키 입력 타이밍 분석이 함께 씁니다.
+
+/// 2배 구간 하나를 나누는 칸 수
+const SUB_BUCKETS: u64 = 32;
+const SUB_BUCKET_BITS: u32 = 5;
+/// 이보다 큰 값은 이 값으로 기록 (10초)
+pub const MAX_VALUE_US: u64 = 10_000_000;
+/// `MAX_VALUE_US`(2^23 초과)까지 담는 칸 수
+const BUCKETS: usize = (SUB_BUCKETS as usize) * 20;
+
+fn bucket_of(value_us: u64) -> usize {
+    if value_us < SUB_BUCKETS {
+        return value_us as usize;
+    }
+    let exponent = 63 - value_us.leading_zeros();
+    let shift = exponent - SUB_BUCKET_BITS;
+    (SUB_BUCKETS * (u64::from(shift) + 1) + ((value_us >> shift) - SUB_BUCKETS)) as usize
+}
+
+/// 칸에 들어가는 가장 큰 값
+fn bucket_upper_us(index: usize) -> u64 {
+    let index = index as u64;
+    if index < SUB_BUCKETS {
+        return index;
+    }
+    let shift = index / SUB_BUCKETS - 1;
+    let mantissa = index % SUB_BUCKETS + SUB_BUCKETS;
+    ((mantissa + 1) << shift) - 1
+}
+
+pub struct Histogram {
+    buckets: Vec<u64>,
+    count: u64,
+    sum_us: u64,
+    /// 표준편차용 제곱합
+    sum_sq_us: f64,
+    min_us: u64,
+    max_us: u64,
+}
+
+impl Histogram {
+    pub fn new() -> Self {
+        Self {
+            buckets: vec![0; BUCKETS],
+            count: 0,
+            sum_us: 0,
+            sum_sq_us: 0.0,
+            min_us: u64::MAX,
+            max_us: 0,
+        }
+    }
+
+    pub fn record(&mut self, value_us: u64) {
+        let value_us = value_us.min(MAX_VALUE_US);
+        self.buckets[bucket_of(value_us)] += 1;
+        self.count += 1;
+        self.sum_us = self.sum_us.saturating_add(value_us);
+        self.sum_sq_us += (value_us as f64) * (value_us as f64);
+        self.min_us = self.min_us.min(value_us);
+        self.max_us = self.max_us.max(value_us);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// 샘플이 없으면 0
+    pub fn min_us(&self) -> u64 {
+        if self.count == 0 {
+            0
+        } else {
+            self.min_us
+        }
+    }
+
+    pub fn max_us(&self) -> u64 {
+        self.max_us
+    }
+
+    pub fn mean_us(&self) -> u64 {
+        self.sum_us.checked_div(self.count).unwrap_or(0)
+    }
+
+    /// 모표준편차
+    pub fn stddev_us(&self) -> u64 {
+        if self.count == 0 {
+            return 0;
+        }
+        let count = self.count as f64;
+        let mean = self.sum_us as f64 / count;
+        (self.sum_sq_us / count - mean * mean)
+            .max(0.0)
+            .sqrt()
+            .round() as u64
+    }
+
+    /// 샘플의 `quantile` 비율 이상이 이하인 값 (칸의 상한, 실제 최댓값을 넘지 않음)
+    pub fn percentile(&self, quantile: f64) -> u64 {
+        if self.count == 0 {
+            return 0;
+        }
+        let rank = ((self.count as f64) * quantile).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (index, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return bucket_upper_us(index).clamp(self.min_us, self.max_us);
+            }
+        }
+        self.max_us
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_cover_their_values() {
+        // Below 64 every value has its own bucket.
+        for value in 0..64 {
+            assert_eq!(bucket_upper_us(bucket_of(value)), value);
+        }
+        // Above that each bucket spans ~3% and its upper bound maps back to it.
+        for value in [64, 65, 100, 1_000, 12_345, 999_999, MAX_VALUE_US] {
+            let bucket = bucket_of(value);
+            let upper = bucket_upper_us(bucket);
+            assert!(bucket < BUCKETS);
+            assert!(upper >= value);
+            assert!(upper - value <= value / SUB_BUCKETS);
+            assert_eq!(bucket_of(upper), bucket);
+            assert_eq!(bucket_of(upper + 1), bucket + 1);
+        }
+        assert_eq!(bucket_of(64), bucket_of(65));
+        assert_ne!(bucket_of(65), bucket_of(66));
+    }
+
+    #[test]
+    fn percentiles_use_bucket_upper_bound_within_min_max() {
+        let mut histogram = Histogram::new();
+        for value in 1..=100 {
+            histogram.record(value);
+        }
+        assert_eq!(histogram.count(), 100);
+        assert_eq!(histogram.percentile(0.0), 1);
+        assert_eq!(histogram.percentile(0.50), 50);
+        // 98 and 99 share a bucket whose upper bound is 99.
+        assert_eq!(histogram.percentile(0.98), 99);
+        // The last bucket reaches 101 but is clamped to the real maximum.
+        assert_eq!(histogram.percentile(1.0), 100);
+        assert_eq!(histogram.mean_us(), 50);
+        assert_eq!(histogram.stddev_us(), 29);
+    }
+
+    #[test]
+    fn empty_histogram_reports_zeros() {
+        let histogram = Histogram::new();
+        assert_eq!(histogram.count(), 0);
+        assert_eq!(histogram.min_us(), 0);
+        assert_eq!(histogram.max_us(), 0);
+        assert_eq!(histogram.mean_us(), 0);
+        assert_eq!(histogram.stddev_us(), 0);
+        assert_eq!(histogram.percentile(0.5), 0);
+    }
+
+    #[test]
+    fn values_above_max_are_capped() {
+        let mut histogram = Histogram::new();
+        histogram.record(MAX_VALUE_US * 3);
+        assert_eq!(histogram.max_us(), MAX_VALUE_US);
+        assert_eq!(histogram.percentile(0.99), MAX_VALUE_US);
+    }
+}
@@ -0,0 +1,129 @@
+//! 키 제스처 서비스
+//!
+//! 눌림/뗌 타이밍에서 길게 누르기와 두 번 누르기를 찾아 플러그인·CSS가 각자 타이머를
+//! 구현하지 않아도 되게 합니다.
+//! - 길게 누르기: 새로 눌린 키가 설정 시간 동안 유지되면 워커 스레드가 콜백 호출
+//! - 두 번 누르기: 직전 누름 후 설정 시간 안에 같은 키가 다시 눌리면 `press`가 알려줌
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use super::deadline_worker::{DeadlineState, DeadlineWorker};
+
+/// 길게 누르기가 확정된 키
+#[derive(Debug, Clone)]
+pub struct KeyHold {
+    pub mode: String,
+    pub key: String,
+    /// 눌린 시각 (monotonic µs)
+    pub pressed_at_us: u64,
+}
+
+struct PendingHold {
+    deadline: Instant,
+    hold: KeyHold,
+}
+
+#[derive(Default)]
+struct State {
+    /// 모드·키별 대기 중인 길게 누르기
+    holds: HashMap<(String, String), PendingHold>,
+    /// 모드·키별 마지막 누름 시각 (두 번 누르기 판정용, 판정되면 제거)
+    last_press_us: HashMap<(String, String), u64>,
+}
+
+impl DeadlineState for State {
+    type Due = Vec<KeyHold>;
+
+    fn deadline(&self) -> Option<Instant> {
+        self.holds.values().map(|pending| pending.deadline).min()
+    }
+
+    fn take_due(&mut self, now: Instant) -> Option<Vec<KeyHold>> {
+        let expired: Vec<(String, String)> = self
+            .holds
+            .iter()
+            .filter(|(_, pending)| pending.deadline <= now)
+            .map(|(id, _)| id.clone())
+            .collect();
+        if expired.is_empty() {
+            return None;
+        }
+        let holds = expired
+            .iter()
+            .filter_map(|id| self.holds.remove(id))
+            .map(|pending| pending.hold)
+            .collect();
+        Some(holds)
+    }
+}
+
+/// 제스처 판정기 (길게 누르기는 콜백으로 전달)
+pub struct KeyGestures {
+    worker: DeadlineWorker<State>,
+}
+
+impl KeyGestures {
+    pub fn spawn<F>(mut on_hold: F) -> Result<Self>
+    where
+        F: FnMut(KeyHold) + Send + 'static,
+    {
+        let worker = DeadlineWorker::spawn("key-gestures", State::default(), move |holds| {
+            for hold in holds {
+                on_hold(hold);
+            }
+            Ok(())
+        })?;
+        Ok(Self { worker })
+    }
+
+    /// 새로 눌린 키 등록. 두 번 누르기면 직전 누름과의 간격(µs)을 반환
+    pub fn press(
+        &self,
+        mode: &str,
+        key: &str,
+        timestamp_us: u64,
+        hold: Duration,
+        double_tap: Duration,
+    ) -> Option<u64> {
+        let id = (mode.to_string(), key.to_string());
+        let mut state = self.worker.lock();
+
+        let interval_us = state
+            .last_press_us
+            .get(&id)
+            .map(|last| timestamp_us.saturating_sub(*last))
+            .filter(|interval| *interval <= double_tap.as_micros() as u64);
+        if interval_us.is_some() {
+            // 세 번째 누름이 다시 두 번 누르기로 잡히지 않도록 초기화
+            state.last_press_us.remove(&id);
+        } else {
+            state.last_press_us.insert(id.clone(), timestamp_us);
+        }
+
+        state.holds.insert(
+            id.clone(),
+            PendingHold {
+                deadline: Instant::now() + hold,
+                hold: KeyHold {
+                    mode: id.0,
+                    key: id.1,
+                    pressed_at_us: timestamp_us,
+                },
+            },
+        );
+        drop(state);
+        self.worker.wake();
+        interval_us
+    }
+
+    /// 키가 떼어지면 대기 중인 길게 누르기 취소
+    pub fn release(&self, mode: &str, key: &str) {
+        self.worker
+            .lock()
+            .holds
+            .remove(&(mode.to_string(), key.to_string()));
+    }
+}
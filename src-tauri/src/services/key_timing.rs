@@ -0,0 +1,263 @@
+//! 키 입력 타이밍 분석 서비스
+//!
+//! 리듬 게임 연습용으로 키마다 두 가지 분포를 세션(앱 실행 또는 초기화 이후) 단위로 모읍니다.
+//! - 누름 시간: Down → Up (끈적이는 키, 길게 눌리는 손가락 확인)
+//! - 누름 간격: 같은 키의 Down → 다음 Down (느린 손가락 확인)
+//!
+//! 같은 값을 모드 전체 분포에도 쌓습니다. 간격이 `MAX_INTERVAL_US`보다 길면 쉬었다가 다시
+//! 친 것으로 보고 간격 분포에서 뺍니다.
+
+use std::collections::{BTreeMap, HashMap};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::clock::{monotonic_us, wall_clock_offset_us};
+use crate::services::histogram::Histogram;
+
+/// 이보다 긴 누름 간격은 연주 중 간격으로 보지 않음 (2초)
+const MAX_INTERVAL_US: u64 = 2_000_000;
+
+/// 분포 요약 (µs, 샘플이 없으면 모두 0)
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimingSummary {
+    pub count: u64,
+    pub mean_us: u64,
+    pub stddev_us: u64,
+    pub min_us: u64,
+    pub p50_us: u64,
+    pub p90_us: u64,
+    pub p99_us: u64,
+    pub max_us: u64,
+}
+
+impl From<&Histogram> for TimingSummary {
+    fn from(histogram: &Histogram) -> Self {
+        Self {
+            count: histogram.count(),
+            mean_us: histogram.mean_us(),
+            stddev_us: histogram.stddev_us(),
+            min_us: histogram.min_us(),
+            p50_us: histogram.percentile(0.50),
+            p90_us: histogram.percentile(0.90),
+            p99_us: histogram.percentile(0.99),
+            max_us: histogram.max_us(),
+        }
+    }
+}
+
+/// 키 하나(또는 모드 전체)의 누름 시간·간격 분포
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyTiming {
+    pub hold: TimingSummary,
+    pub interval: TimingSummary,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModeTiming {
+    /// 모드의 모든 키를 합친 분포
+    pub overall: KeyTiming,
+    pub keys: BTreeMap<String, KeyTiming>,
+}
+
+/// 타이밍 분석 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyTimingReport {
+    /// 세션 시작 시각 (Unix ms)
+    pub started_at_ms: u64,
+    /// 입력이 있었던 모드별 분포
+    pub modes: BTreeMap<String, ModeTiming>,
+}
+
+#[derive(Default)]
+struct Track {
+    hold: Histogram,
+    interval: Histogram,
+}
+
+impl Track {
+    fn summary(&self) -> KeyTiming {
+        KeyTiming {
+            hold: TimingSummary::from(&self.hold),
+            interval: TimingSummary::from(&self.interval),
+        }
+    }
+}
+
+#[derive(Default)]
+struct ModeTracks {
+    overall: Track,
+    keys: HashMap<String, Track>,
+}
+
+impl ModeTracks {
+    fn record(&mut self, key: &str, record: impl Fn(&mut Track)) {
+        record(&mut self.overall);
+        record(self.keys.entry(key.to_string()).or_default());
+    }
+}
+
+struct Session {
+    started_at_ms: u64,
+    modes: HashMap<String, ModeTracks>,
+    /// 눌려 있는 키의 Down 시각 ((모드, 키) → µs)
+    held: HashMap<(String, String), u64>,
+    /// 키마다 마지막 Down 시각
+    last_press: HashMap<(String, String), u64>,
+}
+
+impl Session {
+    fn new() -> Self {
+        let now_us = monotonic_us() as i64 + wall_clock_offset_us();
+        Self {
+            started_at_ms: now_us.max(0) as u64 / 1000,
+            modes: HashMap::new(),
+            held: HashMap::new(),
+            last_press: HashMap::new(),
+        }
+    }
+}
+
+/// 키별 누름 시간·간격 히스토그램
+pub struct KeyTimingStats {
+    session: Mutex<Session>,
+}
+
+impl KeyTimingStats {
+    pub fn new() -> Self {
+        Self {
+            session: Mutex::new(Session::new()),
+        }
+    }
+
+    /// 새 눌림 (`timestamp_us`: 캡처 시각)
+    pub fn press(&self, mode: &str, key: &str, timestamp_us: u64) {
+        let mut session = self.session.lock();
+        let id = (mode.to_string(), key.to_string());
+        let interval = session
+            .last_press
+            .insert(id.clone(), timestamp_us)
+            .map(|previous| timestamp_us.saturating_sub(previous))
+            .filter(|interval| *interval <= MAX_INTERVAL_US);
+        session.held.insert(id, timestamp_us);
+        if let Some(interval) = interval {
+            let tracks = session.modes.entry(mode.to_string()).or_default();
+            tracks.record(key, |track| track.interval.record(interval));
+        }
+    }
+
+    /// 떼기. 이 세션에서 눌림을 보지 못한 키는 무시
+    pub fn release(&self, mode: &str, key: &str, timestamp_us: u64) {
+        let mut session = self.session.lock();
+        let Some(pressed_at) = session.held.remove(&(mode.to_string(), key.to_string())) else {
+            return;
+        };
+        let hold = timestamp_us.saturating_sub(pressed_at);
+        let tracks = session.modes.entry(mode.to_string()).or_default();
+        tracks.record(key, |track| track.hold.record(hold));
+    }
+
+    /// 입력 스트림이 끊겼을 때 (데몬 재시작 등) 진행 중인 누름과 간격 기준을 버림
+    pub fn clear_pending(&self) {
+        let mut session = self.session.lock();
+        session.held.clear();
+        session.last_press.clear();
+    }
+
+    pub fn report(&self) -> KeyTimingReport {
+        let session = self.session.lock();
+        KeyTimingReport {
+            started_at_ms: session.started_at_ms,
+            modes: session
+                .modes
+                .iter()
+                .map(|(mode, tracks)| {
+                    let timing = ModeTiming {
+                        overall: tracks.overall.summary(),
+                        keys: tracks
+                            .keys
+                            .iter()
+                            .map(|(key, track)| (key.clone(), track.summary()))
+                            .collect(),
+                    };
+                    (mode.clone(), timing)
+                })
+                .collect(),
+        }
+    }
+
+    /// 한 모드만 초기화하거나, `None`이면 새 세션 시작
+    pub fn reset(&self, mode: Option<&str>) {
+        let mut session = self.session.lock();
+        match mode {
+            Some(mode) => {
+                session.modes.remove(mode);
+                session.held.retain(|(held_mode, _), _| held_mode != mode);
+                session
+                    .last_press
+                    .retain(|(key_mode, _), _| key_mode != mode);
+            }
+            None => *session = Session::new(),
+        }
+    }
+}
+
+impl Default for KeyTimingStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: u64 = 1000;
+
+    #[test]
+    fn records_hold_and_interval_per_key_and_mode() {
+        let stats = KeyTimingStats::new();
+        stats.press("4k", "D", 0);
+        stats.release("4k", "D", 40 * MS);
+        stats.press("4k", "D", 100 * MS);
+        stats.release("4k", "D", 160 * MS);
+        stats.press("4k", "F", 120 * MS);
+        stats.release("4k", "F", 150 * MS);
+
+        let report = stats.report();
+        let mode = &report.modes["4k"];
+        let d = &mode.keys["D"];
+        assert_eq!(d.hold.count, 2);
+        assert_eq!(d.hold.min_us, 40 * MS);
+        assert_eq!(d.hold.max_us, 60 * MS);
+        assert_eq!(d.interval.count, 1);
+        assert_eq!(d.interval.p50_us, 100 * MS);
+        assert_eq!(mode.keys["F"].interval.count, 0);
+        assert_eq!(mode.overall.hold.count, 3);
+        assert_eq!(mode.overall.interval.count, 1);
+    }
+
+    #[test]
+    fn long_pauses_and_unseen_presses_are_ignored() {
+        let stats = KeyTimingStats::new();
+        // Up without a Down in this session.
+        stats.release("4k", "D", 10 * MS);
+        stats.press("4k", "D", 20 * MS);
+        stats.press("4k", "D", 20 * MS + MAX_INTERVAL_US + 1);
+
+        assert!(stats.report().modes.is_empty());
+
+        stats.release("4k", "D", 30 * MS + MAX_INTERVAL_US);
+        let report = stats.report();
+        let d = &report.modes["4k"].keys["D"];
+        assert_eq!(d.hold.count, 1);
+        assert_eq!(d.interval.count, 0);
+
+        stats.reset(Some("4k"));
+        assert!(stats.report().modes.is_empty());
+    }
+}
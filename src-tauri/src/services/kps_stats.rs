@@ -0,0 +1,252 @@
+//! KPS / APM 통계 서비스
+//!
+//! 카운터와 같은 입력 경로에서 모드별 눌림을 받아 집계하므로 오버레이가 숨겨지거나
+//! 웹뷰가 느려져도, 새로고침해도 값이 유지됩니다.
+//! - KPS: 최근 1초 눌림 수 (캡처 시각 기준 슬라이딩 윈도우)
+//! - APM: 최근 1분 눌림 수
+//! - 평균: 입력이 있던 동안 `TICK_INTERVAL`마다 잰 KPS의 평균
+//! - 최고: 세션 중 가장 높았던 KPS (눌림마다 계산하므로 표본 간격에 영향 없음)
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::clock::{monotonic_us, wall_clock_offset_us};
+
+/// 평균 표본 및 `stats:kps` 갱신 주기
+pub const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+const KPS_WINDOW_US: u64 = 1_000_000;
+const APM_WINDOW_US: u64 = 60_000_000;
+
+/// 모드 하나의 통계
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModeKps {
+    pub kps: u32,
+    pub apm: u32,
+    pub avg_kps: f64,
+    pub peak_kps: u32,
+    /// 세션 동안의 눌림 수
+    pub presses: u64,
+}
+
+/// 통계 조회 결과 (`stats:kps` 이벤트와 동일)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KpsReport {
+    /// 세션 시작 시각 (Unix ms)
+    pub started_at_ms: u64,
+    /// 현재 키 모드
+    pub mode: String,
+    /// 입력이 있었던 모드별 통계
+    pub modes: BTreeMap<String, ModeKps>,
+}
+
+#[derive(Default)]
+struct ModeWindow {
+    /// 최근 1분 눌림의 캡처 시각 (µs, 오래된 순)
+    presses: VecDeque<u64>,
+    total: u64,
+    peak_kps: u32,
+    kps_sum: u64,
+    kps_samples: u64,
+    /// 마지막 표본의 (KPS, APM)
+    last: (u32, u32),
+}
+
+impl ModeWindow {
+    fn trim(&mut self, now_us: u64) {
+        let oldest = now_us.saturating_sub(APM_WINDOW_US);
+        while self.presses.front().is_some_and(|&at| at <= oldest) {
+            self.presses.pop_front();
+        }
+    }
+
+    fn kps_at(&self, now_us: u64) -> u32 {
+        let oldest = now_us.saturating_sub(KPS_WINDOW_US);
+        self.presses
+            .iter()
+            .rev()
+            .take_while(|&&at| at > oldest)
+            .count() as u32
+    }
+
+    fn summary(&self, now_us: u64) -> ModeKps {
+        let oldest = now_us.saturating_sub(APM_WINDOW_US);
+        ModeKps {
+            kps: self.kps_at(now_us),
+            apm: self.presses.iter().filter(|&&at| at > oldest).count() as u32,
+            avg_kps: if self.kps_samples == 0 {
+                0.0
+            } else {
+                self.kps_sum as f64 / self.kps_samples as f64
+            },
+            peak_kps: self.peak_kps,
+            presses: self.total,
+        }
+    }
+}
+
+struct Session {
+    started_at_ms: u64,
+    modes: HashMap<String, ModeWindow>,
+}
+
+impl Session {
+    fn new() -> Self {
+        let now_us = monotonic_us() as i64 + wall_clock_offset_us();
+        Self {
+            started_at_ms: now_us.max(0) as u64 / 1000,
+            modes: HashMap::new(),
+        }
+    }
+}
+
+/// 세션 단위 KPS / APM 집계
+pub struct KpsStats {
+    session: Mutex<Session>,
+}
+
+impl KpsStats {
+    pub fn new() -> Self {
+        Self {
+            session: Mutex::new(Session::new()),
+        }
+    }
+
+    /// 새 눌림 기록 (`timestamp_us`: 캡처 시각)
+    pub fn record_press(&self, mode: &str, timestamp_us: u64) {
+        let mut session = self.session.lock();
+        let window = session.modes.entry(mode.to_string()).or_default();
+        window.trim(timestamp_us);
+        window.presses.push_back(timestamp_us);
+        window.total += 1;
+        window.peak_kps = window.peak_kps.max(window.kps_at(timestamp_us));
+    }
+
+    /// 주기 표본 수집. 어느 모드든 KPS·APM이 바뀌었으면 `true`
+    pub fn tick(&self, now_us: u64) -> bool {
+        let mut session = self.session.lock();
+        let mut changed = false;
+        for window in session.modes.values_mut() {
+            window.trim(now_us);
+            let kps = window.kps_at(now_us);
+            if kps > 0 {
+                window.kps_sum += u64::from(kps);
+                window.kps_samples += 1;
+            }
+            let current = (kps, window.presses.len() as u32);
+            changed |= current != window.last;
+            window.last = current;
+        }
+        changed
+    }
+
+    pub fn report(&self, now_us: u64, mode: String) -> KpsReport {
+        let session = self.session.lock();
+        KpsReport {
+            started_at_ms: session.started_at_ms,
+            mode,
+            modes: session
+                .modes
+                .iter()
+                .map(|(name, window)| (name.clone(), window.summary(now_us)))
+                .collect(),
+        }
+    }
+
+    /// 한 모드만 초기화하거나, `None`이면 새 세션 시작
+    pub fn reset(&self, mode: Option<&str>) {
+        let mut session = self.session.lock();
+        match mode {
+            Some(mode) => {
+                session.modes.remove(mode);
+            }
+            None => *session = Session::new(),
+        }
+    }
+}
+
+impl Default for KpsStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: u64 = 1000;
+    /// Keeps window arithmetic away from zero.
+    const T: u64 = 100_000 * MS;
+
+    fn mode(stats: &KpsStats, now_us: u64, name: &str) -> ModeKps {
+        stats
+            .report(now_us, name.to_string())
+            .modes
+            .remove(name)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn windows_drop_presses_exactly_at_their_length() {
+        let stats = KpsStats::new();
+        stats.record_press("4k", T);
+        stats.record_press("4k", T + 500 * MS);
+
+        let at = |now_us| {
+            let kps = mode(&stats, now_us, "4k");
+            (kps.kps, kps.apm)
+        };
+        assert_eq!(at(T + 999 * MS), (2, 2));
+        assert_eq!(at(T + 1000 * MS), (1, 2));
+        assert_eq!(at(T + 1500 * MS), (0, 2));
+        assert_eq!(at(T + 60_000 * MS), (0, 1));
+        assert_eq!(at(T + 60_500 * MS), (0, 0));
+
+        // A press 60 s later trims the first one from the stored window.
+        stats.record_press("4k", T + 60_000 * MS);
+        let session = stats.session.lock();
+        assert_eq!(session.modes["4k"].presses.len(), 2);
+    }
+
+    #[test]
+    fn average_samples_only_active_ticks_and_peak_is_per_press() {
+        let stats = KpsStats::new();
+        for offset in [0, 100, 200] {
+            stats.record_press("4k", T + offset * MS);
+        }
+        assert!(stats.tick(T + 300 * MS));
+        // Same KPS and APM: nothing to announce.
+        assert!(!stats.tick(T + 400 * MS));
+        // Idle ticks change the KPS but are not averaged.
+        assert!(stats.tick(T + 5_000 * MS));
+        assert!(!stats.tick(T + 6_000 * MS));
+        stats.record_press("4k", T + 10_000 * MS);
+        assert!(stats.tick(T + 10_100 * MS));
+
+        let kps = mode(&stats, T + 10_100 * MS, "4k");
+        assert_eq!(kps.avg_kps, (3.0 + 3.0 + 1.0) / 3.0);
+        assert_eq!(kps.peak_kps, 3);
+        assert_eq!(kps.presses, 4);
+    }
+
+    #[test]
+    fn reset_clears_one_mode_or_the_whole_session() {
+        let stats = KpsStats::new();
+        stats.record_press("4k", T);
+        stats.record_press("8k", T);
+
+        stats.reset(Some("4k"));
+        let report = stats.report(T, "8k".into());
+        assert_eq!(report.modes.keys().collect::<Vec<_>>(), ["8k"]);
+        assert_eq!(report.modes["8k"].presses, 1);
+
+        stats.reset(None);
+        assert!(stats.report(T, "8k".into()).modes.is_empty());
+    }
+}
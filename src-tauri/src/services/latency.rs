@@ -0,0 +1,116 @@
+//! 입력 지연 시간 진단 서비스
+//!
+//! 데몬 캡처 시각부터 두 지점까지의 지연을 세션(앱 실행 또는 초기화 이후) 단위로 모읍니다.
+//! - emit: 앱이 `keys:state`를 내보낸 시각 (같은 monotonic 시계로 앱에서 측정)
+//! - render: 오버레이가 그 입력을 반영한 프레임을 그린 시각 (오버레이가 모아서 보고)
+//!
+//! 샘플은 히스토그램(`services::histogram`)에 누적하므로 오래 켜 두어도 메모리가 늘지 않습니다.
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::clock::{monotonic_us, wall_clock_offset_us};
+use crate::services::histogram::Histogram;
+
+/// 지연 시간 요약 (µs, 샘플이 없으면 모두 0)
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencySummary {
+    pub count: u64,
+    pub min_us: u64,
+    pub mean_us: u64,
+    pub p50_us: u64,
+    pub p95_us: u64,
+    pub p99_us: u64,
+    pub max_us: u64,
+}
+
+impl From<&Histogram> for LatencySummary {
+    fn from(histogram: &Histogram) -> Self {
+        Self {
+            count: histogram.count(),
+            min_us: histogram.min_us(),
+            mean_us: histogram.mean_us(),
+            p50_us: histogram.percentile(0.50),
+            p95_us: histogram.percentile(0.95),
+            p99_us: histogram.percentile(0.99),
+            max_us: histogram.max_us(),
+        }
+    }
+}
+
+struct Session {
+    started_at_ms: u64,
+    emit: Histogram,
+    render: Histogram,
+}
+
+impl Session {
+    fn new() -> Self {
+        let now_us = monotonic_us() as i64 + wall_clock_offset_us();
+        Self {
+            started_at_ms: now_us.max(0) as u64 / 1000,
+            emit: Histogram::new(),
+            render: Histogram::new(),
+        }
+    }
+}
+
+/// 진단 결과
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyReport {
+    /// 세션 시작 시각 (Unix ms)
+    pub started_at_ms: u64,
+    /// 캡처 → `keys:state` 발생
+    pub emit: LatencySummary,
+    /// 캡처 → 오버레이 프레임 렌더 (진단 패널이 켜져 있는 동안만 수집)
+    pub render: LatencySummary,
+    /// 측정 당시 렌더러 백엔드 (`angle_mode`)
+    pub angle_mode: String,
+}
+
+/// 세션 단위 지연 시간 히스토그램
+pub struct LatencyStats {
+    session: Mutex<Session>,
+}
+
+impl LatencyStats {
+    pub fn new() -> Self {
+        Self {
+            session: Mutex::new(Session::new()),
+        }
+    }
+
+    pub fn record_emit(&self, latency_us: u64) {
+        self.session.lock().emit.record(latency_us);
+    }
+
+    pub fn record_render(&self, samples_us: &[u64]) {
+        let mut session = self.session.lock();
+        for sample in samples_us {
+            session.render.record(*sample);
+        }
+    }
+
+    pub fn report(&self, angle_mode: String) -> LatencyReport {
+        let session = self.session.lock();
+        LatencyReport {
+            started_at_ms: session.started_at_ms,
+            emit: LatencySummary::from(&session.emit),
+            render: LatencySummary::from(&session.render),
+            angle_mode,
+        }
+    }
+
+    /// 새 세션 시작 (렌더러 설정을 바꿔 비교할 때 사용)
+    pub fn reset(&self) {
+        *self.session.lock() = Session::new();
+    }
+}
+
+impl Default for LatencyStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
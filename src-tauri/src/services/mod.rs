@@ -1,2 +1,18 @@
+pub mod chatter_filter;
+pub mod counter_flush;
+pub mod counter_history;
+pub mod counter_snapshots;
 pub mod css_watcher;
+pub mod daemon_supervisor;
+pub mod deadline_worker;
+pub mod event_batcher;
+pub mod histogram;
+pub mod key_gestures;
+pub mod key_timing;
+pub mod kps_stats;
+pub mod latency;
+pub mod play_sessions;
 pub mod settings;
+#[cfg(test)]
+mod test_support;
+pub mod wheel_pulse;
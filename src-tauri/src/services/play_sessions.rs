@@ -0,0 +1,269 @@
+//! 플레이 세션 기록 서비스
+//!
+//! 카운터 경로의 눌림으로 플레이 세션을 자동으로 나눕니다.
+//! - 진행 중인 세션이 없을 때의 첫 눌림이 세션 시작
+//! - 마지막 눌림 후 `session_idle_minutes` 동안 입력이 없으면 마지막 눌림 시각에 세션 종료
+//! - 눌림이 `MIN_SESSION_PRESSES`보다 적은 세션은 우연한 입력으로 보고 버림
+//!
+//! 끝난 세션은 `play_sessions.json`에 최근 `MAX_SESSIONS`개까지 저장합니다.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::clock::wall_clock_offset_us;
+use crate::services::counter_history::csv_field;
+use crate::store::{load_json_or_quarantine, save_json};
+
+const FILE_NAME: &str = "play_sessions.json";
+const MIN_SESSION_PRESSES: u64 = 20;
+const MAX_SESSIONS: usize = 1000;
+const KPS_WINDOW_US: u64 = 1_000_000;
+
+/// 끝난 플레이 세션 요약
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaySession {
+    pub id: String,
+    /// 첫 눌림 시각 (Unix ms)
+    pub started_at_ms: u64,
+    /// 마지막 눌림 시각 (Unix ms)
+    pub ended_at_ms: u64,
+    pub duration_ms: u64,
+    /// 가장 많이 누른 키 모드
+    pub mode: String,
+    pub presses: u64,
+    pub peak_kps: u32,
+    /// 모드 → 키 → 횟수
+    pub counts: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+impl PlaySession {
+    /// CSV 머리글 (키별 횟수는 JSON 내보내기에만 포함)
+    pub const CSV_HEADER: &'static str = "id,startedAtMs,endedAtMs,durationMs,mode,presses,peakKps";
+
+    pub fn csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{}",
+            self.id,
+            self.started_at_ms,
+            self.ended_at_ms,
+            self.duration_ms,
+            csv_field(&self.mode),
+            self.presses,
+            self.peak_kps
+        )
+    }
+}
+
+struct ActiveSession {
+    started_us: u64,
+    last_press_us: u64,
+    presses: u64,
+    peak_kps: u32,
+    /// 최근 1초 눌림 시각 (최고 KPS 계산용)
+    recent: VecDeque<u64>,
+    counts: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+impl ActiveSession {
+    fn new(timestamp_us: u64) -> Self {
+        Self {
+            started_us: timestamp_us,
+            last_press_us: timestamp_us,
+            presses: 0,
+            peak_kps: 0,
+            recent: VecDeque::new(),
+            counts: BTreeMap::new(),
+        }
+    }
+
+    fn press(&mut self, mode: &str, key: &str, timestamp_us: u64) {
+        self.last_press_us = self.last_press_us.max(timestamp_us);
+        self.presses += 1;
+        let oldest = timestamp_us.saturating_sub(KPS_WINDOW_US);
+        while self.recent.front().is_some_and(|&at| at <= oldest) {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(timestamp_us);
+        self.peak_kps = self.peak_kps.max(self.recent.len() as u32);
+        *self
+            .counts
+            .entry(mode.to_string())
+            .or_default()
+            .entry(key.to_string())
+            .or_insert(0) += 1;
+    }
+
+    fn finish(self) -> PlaySession {
+        let to_wall_ms =
+            |monotonic: u64| (monotonic as i64 + wall_clock_offset_us()).max(0) as u64 / 1000;
+        let mode = self
+            .counts
+            .iter()
+            .map(|(mode, keys)| (mode, keys.values().sum::<u64>()))
+            .max_by_key(|(_, presses)| *presses)
+            .map(|(mode, _)| mode.clone())
+            .unwrap_or_default();
+        PlaySession {
+            id: uuid::Uuid::new_v4().to_string(),
+            started_at_ms: to_wall_ms(self.started_us),
+            ended_at_ms: to_wall_ms(self.last_press_us),
+            duration_ms: (self.last_press_us - self.started_us) / 1000,
+            mode,
+            presses: self.presses,
+            peak_kps: self.peak_kps,
+            counts: self.counts,
+        }
+    }
+}
+
+/// 플레이 세션 감지 및 저장
+pub struct PlaySessions {
+    path: PathBuf,
+    /// 입력 스레드가 잡는 잠금 (파일 쓰기와 분리)
+    active: Mutex<Option<ActiveSession>>,
+    /// 끝난 세션 (오래된 순)
+    saved: Mutex<Vec<PlaySession>>,
+}
+
+impl PlaySessions {
+    /// `dir`의 세션 파일을 읽음. 읽을 수 없으면 손상 파일을 옆으로 옮기고 빈 목록으로 시작
+    pub fn load(dir: &Path) -> Self {
+        let path = dir.join(FILE_NAME);
+        let saved = load_json_or_quarantine(&path, "play sessions");
+        Self {
+            path,
+            active: Mutex::new(None),
+            saved: Mutex::new(saved),
+        }
+    }
+
+    /// 카운터 경로의 새 눌림 (`timestamp_us`: 캡처 시각)
+    pub fn press(&self, mode: &str, key: &str, timestamp_us: u64) {
+        self.active
+            .lock()
+            .get_or_insert_with(|| ActiveSession::new(timestamp_us))
+            .press(mode, key, timestamp_us);
+    }
+
+    /// 마지막 눌림 후 `idle_us`가 지났으면 세션을 끝내고 저장. 저장된 세션을 반환
+    pub fn end_if_idle(&self, now_us: u64, idle_us: u64) -> Result<Option<PlaySession>> {
+        let finished = {
+            let mut active = self.active.lock();
+            match active.as_ref() {
+                Some(session) if now_us.saturating_sub(session.last_press_us) >= idle_us => {
+                    active.take()
+                }
+                _ => None,
+            }
+        };
+        self.store(finished)
+    }
+
+    /// 진행 중인 세션을 바로 끝냄 (앱 종료 시)
+    pub fn end_now(&self) -> Result<Option<PlaySession>> {
+        let finished = self.active.lock().take();
+        self.store(finished)
+    }
+
+    fn store(&self, finished: Option<ActiveSession>) -> Result<Option<PlaySession>> {
+        let Some(session) = finished.filter(|session| session.presses >= MIN_SESSION_PRESSES)
+        else {
+            return Ok(None);
+        };
+        let session = session.finish();
+        let mut saved = self.saved.lock();
+        saved.push(session.clone());
+        let overflow = saved.len().saturating_sub(MAX_SESSIONS);
+        saved.drain(..overflow);
+        self.write(&saved)?;
+        Ok(Some(session))
+    }
+
+    /// 끝난 세션 (최근 순)
+    pub fn list(&self) -> Vec<PlaySession> {
+        self.saved.lock().iter().rev().cloned().collect()
+    }
+
+    /// 지운 세션이 있으면 `true`
+    pub fn delete(&self, id: &str) -> Result<bool> {
+        let mut saved = self.saved.lock();
+        let before = saved.len();
+        saved.retain(|session| session.id != id);
+        if saved.len() == before {
+            return Ok(false);
+        }
+        self.write(&saved)?;
+        Ok(true)
+    }
+
+    fn write(&self, sessions: &[PlaySession]) -> Result<()> {
+        save_json(&self.path, sessions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::test_support::ScratchDir;
+
+    const MS: u64 = 1000;
+    const IDLE_US: u64 = 60_000 * MS;
+
+    fn press_many(sessions: &PlaySessions, count: u64, from_us: u64) -> u64 {
+        let mut at = from_us;
+        for _ in 0..count {
+            at += 100 * MS;
+            sessions.press("4k", "D", at);
+        }
+        at
+    }
+
+    #[test]
+    fn session_ends_after_idle_timeout_and_next_press_starts_another() {
+        let dir = ScratchDir::new();
+        let sessions = PlaySessions::load(&dir);
+
+        let last = press_many(&sessions, MIN_SESSION_PRESSES, 0);
+        assert!(sessions
+            .end_if_idle(last + IDLE_US - 1, IDLE_US)
+            .unwrap()
+            .is_none());
+        let first = sessions
+            .end_if_idle(last + IDLE_US, IDLE_US)
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.presses, MIN_SESSION_PRESSES);
+        assert_eq!(first.duration_ms, (MIN_SESSION_PRESSES - 1) * 100);
+        assert_eq!(first.mode, "4k");
+        assert_eq!(first.peak_kps, 10);
+
+        let later = last + 2 * IDLE_US;
+        press_many(&sessions, MIN_SESSION_PRESSES + 5, later);
+        let second = sessions.end_now().unwrap().unwrap();
+        assert_eq!(second.presses, MIN_SESSION_PRESSES + 5);
+
+        let listed: Vec<String> = sessions.list().into_iter().map(|s| s.id).collect();
+        assert_eq!(listed, [second.id, first.id]);
+        // A reload reads back what was written.
+        assert_eq!(PlaySessions::load(&dir).list().len(), 2);
+    }
+
+    #[test]
+    fn short_sessions_are_dropped() {
+        let dir = ScratchDir::new();
+        let sessions = PlaySessions::load(&dir);
+
+        let last = press_many(&sessions, MIN_SESSION_PRESSES - 1, 0);
+        assert!(sessions
+            .end_if_idle(last + IDLE_US, IDLE_US)
+            .unwrap()
+            .is_none());
+        assert!(sessions.list().is_empty());
+        assert!(!dir.join(FILE_NAME).exists());
+    }
+}
@@ -4,7 +4,8 @@ use anyhow::Result;
 
 use crate::models::{
     CustomCss, CustomCssPatch, CustomJs, CustomJsPatch, NoteSettings, NoteSettingsPatch,
-    SettingsDiff, SettingsPatch, SettingsPatchInput, SettingsState, ShortcutsState,
+    SettingsDiff, SettingsPatch, SettingsPatchInput, SettingsState, ShortcutBinding,
+    ShortcutsState,
 };
 use crate::store::AppStore;
 
@@ -45,6 +46,7 @@ impl SettingsService {
             state.overlay_resize_anchor = next.overlay_resize_anchor.clone();
             state.key_counter_enabled = next.key_counter_enabled;
             state.grid_settings = next.grid_settings.clone();
+            state.input_settings = next.input_settings.clone();
             state.shortcuts = next.shortcuts.clone();
         })?;
 
@@ -116,50 +118,99 @@ fn normalize_patch(patch: &SettingsPatchInput, current: &SettingsState) -> Setti
     if let Some(value) = patch.grid_settings.as_ref() {
         normalized.grid_settings = Some(value.clone());
     }
+    if let Some(value) = patch.input_settings.as_ref() {
+        let mut value = value.clone();
+        value.wheel_pulse_ms = value.wheel_pulse_ms.clamp(10, 1000);
+        value.debounce_ms = value.debounce_ms.min(100);
+        value.hold_ms = value.hold_ms.clamp(100, 5000);
+        value.double_tap_ms = value.double_tap_ms.clamp(100, 1000);
+        value.batch_window_ms = value.batch_window_ms.min(50);
+        value.history_retention_days = value.history_retention_days.clamp(1, 365);
+        value.session_idle_minutes = value.session_idle_minutes.clamp(1, 120);
+        normalized.input_settings = Some(value);
+    }
     if let Some(value) = patch.shortcuts.as_ref() {
-        let normalize_binding = |binding: &crate::models::ShortcutBinding| {
-            if binding.key.trim().is_empty() {
-                crate::models::ShortcutBinding {
-                    key: String::new(),
-                    ctrl: false,
-                    shift: false,
-                    alt: false,
-                    meta: false,
-                }
-            } else {
-                binding.clone()
-            }
-        };
         let mut merged: ShortcutsState = current.shortcuts.clone();
         if let Some(binding) = value.toggle_overlay.as_ref() {
-            merged.toggle_overlay = normalize_binding(binding);
+            merged.toggle_overlay = normalize_shortcut_binding(binding);
         }
         if let Some(binding) = value.toggle_overlay_lock.as_ref() {
-            merged.toggle_overlay_lock = normalize_binding(binding);
+            merged.toggle_overlay_lock = normalize_shortcut_binding(binding);
         }
         if let Some(binding) = value.toggle_always_on_top.as_ref() {
-            merged.toggle_always_on_top = normalize_binding(binding);
+            merged.toggle_always_on_top = normalize_shortcut_binding(binding);
         }
         if let Some(binding) = value.switch_key_mode.as_ref() {
-            merged.switch_key_mode = normalize_binding(binding);
+            merged.switch_key_mode = normalize_shortcut_binding(binding);
         }
         if let Some(binding) = value.toggle_settings_panel.as_ref() {
-            merged.toggle_settings_panel = normalize_binding(binding);
+            merged.toggle_settings_panel = normalize_shortcut_binding(binding);
         }
         if let Some(binding) = value.zoom_in.as_ref() {
-            merged.zoom_in = normalize_binding(binding);
+            merged.zoom_in = normalize_shortcut_binding(binding);
         }
         if let Some(binding) = value.zoom_out.as_ref() {
-            merged.zoom_out = normalize_binding(binding);
+            merged.zoom_out = normalize_shortcut_binding(binding);
         }
         if let Some(binding) = value.reset_zoom.as_ref() {
-            merged.reset_zoom = normalize_binding(binding);
+            merged.reset_zoom = normalize_shortcut_binding(binding);
+        }
+        if let Some(binding) = value.reset_mode_counters.as_ref() {
+            merged.reset_mode_counters = normalize_shortcut_binding(binding);
+        }
+        if let Some(binding) = value.toggle_key_counter.as_ref() {
+            merged.toggle_key_counter = normalize_shortcut_binding(binding);
+        }
+        if let Some(binding) = value.toggle_capture_pause.as_ref() {
+            merged.toggle_capture_pause = normalize_shortcut_binding(binding);
+        }
+        if let Some(bindings) = value.key_mode_shortcuts.as_ref() {
+            merged.key_mode_shortcuts = bindings
+                .iter()
+                .filter(|(mode, binding)| !mode.is_empty() && !binding.key.trim().is_empty())
+                .map(|(mode, binding)| (mode.clone(), normalize_shortcut_binding(binding)))
+                .collect();
         }
         normalized.shortcuts = Some(merged);
     }
     normalized
 }
 
+/// 빈 키는 미지정으로, 코드 키는 중복 없이, 시퀀스는 두 단계까지만 유지
+fn normalize_shortcut_binding(binding: &ShortcutBinding) -> ShortcutBinding {
+    if binding.key.trim().is_empty() {
+        return ShortcutBinding::default();
+    }
+    let mut chord: Vec<String> = Vec::new();
+    for key in &binding.chord {
+        let key = key.trim();
+        if !key.is_empty() && key != binding.key && !chord.iter().any(|existing| existing == key) {
+            chord.push(key.to_string());
+        }
+    }
+    let leader = binding
+        .leader
+        .as_deref()
+        .filter(|leader| !leader.key.trim().is_empty())
+        .map(|leader| {
+            Box::new(ShortcutBinding {
+                leader: None,
+                timeout_ms: None,
+                ..normalize_shortcut_binding(leader)
+            })
+        });
+    let timeout_ms = leader
+        .as_ref()
+        .and(binding.timeout_ms)
+        .map(|ms| ms.clamp(200, 5000));
+    ShortcutBinding {
+        chord,
+        leader,
+        timeout_ms,
+        ..binding.clone()
+    }
+}
+
 fn apply_changes(mut current: SettingsState, patch: &SettingsPatch) -> SettingsState {
     if let Some(value) = patch.hardware_acceleration {
         current.hardware_acceleration = value;
@@ -212,6 +263,9 @@ fn apply_changes(mut current: SettingsState, patch: &SettingsPatch) -> SettingsS
     if let Some(value) = patch.grid_settings.as_ref() {
         current.grid_settings = value.clone();
     }
+    if let Some(value) = patch.input_settings.as_ref() {
+        current.input_settings = value.clone();
+    }
     if let Some(value) = patch.shortcuts.as_ref() {
         current.shortcuts = value.clone();
     }
@@ -0,0 +1,29 @@
+//! Shared fixtures for service tests.
+
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// Empty directory under the system temp dir, removed on drop (also when an assertion fails).
+pub struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    pub fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!("dm-note-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+}
+
+impl Deref for ScratchDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
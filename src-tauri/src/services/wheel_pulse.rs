@@ -0,0 +1,123 @@
+//! 휠 가상 키 펄스 서비스
+//!
+//! 데몬은 휠 노치마다 `WHEEL UP` 등의 Down만 보내므로, 여기서 일정 시간 뒤 Up을 만들어
+//! 오버레이에 짧은 펄스로 표시되게 합니다.
+//! - 라벨별 해제 시각 관리
+//! - 펄스 도중 들어온 노치는 합치거나(연장) 새 펄스로 다시 시작
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use super::deadline_worker::{DeadlineState, DeadlineWorker};
+use crate::clock::monotonic_us;
+use crate::ipc::{HookKeyState, HookMessage};
+
+/// 휠 가상 키 라벨 여부
+pub fn is_wheel_label(label: &str) -> bool {
+    matches!(
+        label,
+        "WHEEL UP" | "WHEEL DOWN" | "WHEEL LEFT" | "WHEEL RIGHT"
+    )
+}
+
+/// 노치 하나를 받았을 때 호출자가 내보내야 할 이벤트
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WheelPress {
+    /// 새 펄스 시작: Down 전송
+    Start,
+    /// 진행 중인 펄스 연장: 상태 변화 없이 카운트만
+    Extend,
+    /// 진행 중인 펄스를 끊고 다시 시작: Up 후 Down 전송
+    Restart,
+}
+
+struct Pending {
+    deadline: Instant,
+    release: HookMessage,
+}
+
+/// 라벨별 대기 중인 해제
+#[derive(Default)]
+struct State {
+    pending: HashMap<String, Pending>,
+}
+
+impl DeadlineState for State {
+    type Due = Vec<HookMessage>;
+
+    fn deadline(&self) -> Option<Instant> {
+        self.pending.values().map(|entry| entry.deadline).min()
+    }
+
+    fn take_due(&mut self, now: Instant) -> Option<Vec<HookMessage>> {
+        let expired: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, entry)| entry.deadline <= now)
+            .map(|(label, _)| label.clone())
+            .collect();
+        if expired.is_empty() {
+            return None;
+        }
+        let releases = expired
+            .iter()
+            .filter_map(|label| self.pending.remove(label))
+            .map(|entry| entry.release)
+            .collect();
+        Some(releases)
+    }
+}
+
+/// 휠 펄스 스케줄러 (해제 시각이 되면 콜백으로 Up 메시지 전달)
+pub struct WheelPulses {
+    worker: DeadlineWorker<State>,
+}
+
+impl WheelPulses {
+    pub fn spawn<F>(mut on_release: F) -> Result<Self>
+    where
+        F: FnMut(HookMessage) + Send + 'static,
+    {
+        let worker = DeadlineWorker::spawn("wheel-pulse", State::default(), move |releases| {
+            for mut release in releases {
+                release.timestamp_us = Some(monotonic_us());
+                on_release(release);
+            }
+            Ok(())
+        })?;
+        Ok(Self { worker })
+    }
+
+    /// 노치 하나를 등록하고 호출자가 내보낼 이벤트 종류를 반환
+    pub fn press(&self, message: &HookMessage, duration: Duration, coalesce: bool) -> WheelPress {
+        let Some(label) = message.labels.first() else {
+            return WheelPress::Start;
+        };
+        let deadline = Instant::now() + duration;
+
+        let mut state = self.worker.lock();
+        let result = match state.pending.get_mut(label) {
+            Some(entry) => {
+                entry.deadline = deadline;
+                if coalesce {
+                    WheelPress::Extend
+                } else {
+                    WheelPress::Restart
+                }
+            }
+            None => {
+                let mut release = message.clone();
+                release.state = HookKeyState::Up;
+                state
+                    .pending
+                    .insert(label.clone(), Pending { deadline, release });
+                WheelPress::Start
+            }
+        };
+        drop(state);
+        self.worker.wake();
+        result
+    }
+}
@@ -1,22 +1,24 @@
 use std::{
     collections::HashSet,
     fs,
+    io::Write,
     path::{Path, PathBuf},
 };
 
 use anyhow::{Context, Result};
 use dirs_next::config_dir;
 use parking_lot::RwLock;
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 use tauri::path::PathResolver;
 use tauri::Runtime;
 
 use crate::{
     defaults::{default_keys, default_positions},
+    key_codes,
     models::{
-        AppStoreData, KeyCounters, KeyMappings, KeyPositions, NoteSettings, OverlayBounds,
-        SettingsState,
+        AppStoreData, KeyCounters, KeyMappings, KeyModeDevices, KeyPositions, NoteSettings,
+        OverlayBounds, SettingsState,
     },
 };
 
@@ -75,6 +77,11 @@ impl AppStore {
         Ok(store)
     }
 
+    /// 앱 데이터 디렉터리 (`store.json`과 별도 데이터 파일을 두는 곳)
+    pub fn data_dir(&self) -> &Path {
+        self.path.parent().unwrap_or_else(|| Path::new("."))
+    }
+
     pub fn snapshot(&self) -> AppStoreData {
         self.state.read().clone()
     }
@@ -117,6 +124,15 @@ impl AppStore {
         Ok(guard.key_counters.clone())
     }
 
+    /// 모드의 장치 필터 교체 (빈 목록이면 필터 해제)
+    pub fn set_key_mode_devices(&self, mode: &str, devices: Vec<String>) -> Result<KeyModeDevices> {
+        let mut guard = self.state.write();
+        guard.key_mode_devices.insert(mode.to_string(), devices);
+        *guard = normalize_state(guard.clone());
+        self.persist_locked(&guard)?;
+        Ok(guard.key_mode_devices.clone())
+    }
+
     pub fn set_selected_key_type(&self, key: impl Into<String>) -> Result<String> {
         let key = key.into();
         let mut guard = self.state.write();
@@ -201,11 +217,49 @@ impl AppStore {
         }
 
         let json = serde_json::to_string_pretty(&root)?;
-        fs::write(&self.path, json)
+        write_atomic(&self.path, json.as_bytes())
             .with_context(|| format!("failed to write store file at {}", self.path.display()))
     }
 }
 
+/// 임시 파일에 쓰고 디스크에 반영한 뒤 교체 (중간에 꺼져도 이전 파일 또는 새 파일이 온전히 남음)
+pub(crate) fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// JSON 파일을 읽음. 파일이 없으면 기본값, 읽을 수 없으면 손상 파일을 `.json.corrupt`로
+/// 옮기고 기본값으로 시작 (`what`은 로그용 이름)
+pub(crate) fn load_json_or_quarantine<T: DeserializeOwned + Default>(path: &Path, what: &str) -> T {
+    if !path.exists() {
+        return T::default();
+    }
+    let loaded = fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))
+        .and_then(|content| {
+            serde_json::from_str(&content).with_context(|| format!("invalid {}", path.display()))
+        });
+    loaded.unwrap_or_else(|err| {
+        log::warn!("{what} unreadable, starting over: {err:#}");
+        let _ = fs::rename(path, path.with_extension("json.corrupt"));
+        T::default()
+    })
+}
+
+/// `value`를 JSON으로 `write_atomic` 저장
+pub(crate) fn save_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<()> {
+    let json = serde_json::to_vec(value)?;
+    write_atomic(path, &json).with_context(|| format!("failed to write {}", path.display()))
+}
+
 fn load_store_from_path(path: &Path) -> Result<AppStoreData> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("failed to read store file at {}", path.display()))?;
@@ -256,7 +310,13 @@ fn normalize_state(mut data: AppStoreData) -> AppStoreData {
         }
     }
 
+    // Legacy migration: platform key labels -> canonical key ids (KeyboardEvent.code)
+    key_codes::migrate_mappings(&mut data.keys);
+    key_codes::migrate_counters(&mut data.key_counters);
+
     merge_default_counters(&mut data.key_counters, &data.keys);
+    data.key_mode_devices
+        .retain(|mode, devices| !devices.is_empty() && data.keys.contains_key(mode));
 
     if !data.keys.contains_key(&data.selected_key_type) {
         data.selected_key_type = "4key".to_string();
@@ -316,6 +376,7 @@ fn settings_from_store(store: &AppStoreData) -> SettingsState {
         overlay_resize_anchor: store.overlay_resize_anchor.clone(),
         key_counter_enabled: store.key_counter_enabled,
         grid_settings: store.grid_settings.clone(),
+        input_settings: store.input_settings.clone(),
         shortcuts: store.shortcuts.clone(),
     }
 }
@@ -19,6 +19,11 @@ import type {
   CssTogglePayload,
   CustomTabDeleteResult,
   CustomTabResult,
+  DaemonCaptureFilters,
+  DaemonCaptureState,
+  DaemonHealth,
+  DaemonReplaySource,
+  DaemonStatusPayload,
   DMNoteAPI,
   KeyCounterUpdate,
   KeysModeResponse,
@@ -31,6 +36,7 @@ import type {
   OverlayVisibilityPayload,
   Unsubscribe,
   ModeChangePayload,
+  ShortcutTriggeredPayload,
   CustomTabsChangePayload,
   KeyStatePayload,
   PresetOperationResult,
@@ -52,6 +58,21 @@ import type {
   PluginDisplayElementConfig,
   PluginDisplayElementInternal,
   RawInputPayload,
+  InputDeviceInfo,
+  ImeState,
+  CounterHistoryQuery,
+  CounterHistoryRange,
+  CounterSnapshot,
+  KeyBatchEntry,
+  KeyBatchPayload,
+  KeyTimingReport,
+  KpsReport,
+  LatencyReport,
+  KeyChatterStats,
+  KeyHoldEvent,
+  KeyDoubleTapEvent,
+  PlaySession,
+  StatsExportResult,
 } from "@src/types/api";
 import type { BootstrapPayload } from "@src/types/app";
 import type { CustomCss } from "@src/types/css";
@@ -61,6 +82,7 @@ import type {
   KeyMappings,
   KeyPositions,
   KeyCounters,
+  KeyModeDevices,
 } from "@src/types/keys";
 import type {
   SettingsState,
@@ -118,11 +140,33 @@ function subscribe<T>(
   };
 }
 
+// 키 이벤트는 묶음 전달 모드에서 keys:batch 안에 들어오므로 둘 다 구독
+function subscribeKeyEvent<T>(
+  event: KeyBatchEntry["event"],
+  listener: (payload: T) => void
+): Unsubscribe {
+  const unsubscribeSingle = subscribe<T>(event, listener);
+  const unsubscribeBatch = subscribe<KeyBatchPayload>(
+    "keys:batch",
+    ({ entries }) => {
+      entries.forEach((entry) => {
+        if (entry.event === event) listener(entry.payload as T);
+      });
+    }
+  );
+  return () => {
+    unsubscribeSingle();
+    unsubscribeBatch();
+  };
+}
+
 const api: DMNoteAPI = {
   app: {
     bootstrap: () => invoke<BootstrapPayload>("app_bootstrap"),
     openExternal: (url: string) => invoke("app_open_external", { url }),
     restart: () => invoke("app_restart"),
+    onShortcut: (listener: (payload: ShortcutTriggeredPayload) => void) =>
+      subscribe<ShortcutTriggeredPayload>("shortcuts:triggered", listener),
   },
   window: {
     type: (window as any).__dmn_window_type as "main" | "overlay",
@@ -156,6 +200,12 @@ const api: DMNoteAPI = {
       invoke<KeyCounters>("keys_reset_counters_mode", { mode }),
     resetSingleCounter: (mode: string, key: string) =>
       invoke<KeyCounters>("keys_reset_single_counter", { mode, key }),
+    listCounterSnapshots: () =>
+      invoke<CounterSnapshot[]>("keys_list_counter_snapshots"),
+    restoreCounterSnapshot: (id: string) =>
+      invoke<KeyCounters>("keys_restore_counter_snapshot", { id }),
+    deleteCounterSnapshot: (id: string) =>
+      invoke<boolean>("keys_delete_counter_snapshot", { id }),
     onChanged: (listener: (keys: KeyMappings) => void) =>
       subscribe<KeyMappings>("keys:changed", listener),
     onPositionsChanged: (listener: (positions: KeyPositions) => void) =>
@@ -163,7 +213,9 @@ const api: DMNoteAPI = {
     onModeChanged: (listener: (payload: ModeChangePayload) => void) =>
       subscribe<ModeChangePayload>("keys:mode-changed", listener),
     onKeyState: (listener: (payload: KeyStatePayload) => void) =>
-      subscribe<KeyStatePayload>("keys:state", listener),
+      subscribeKeyEvent<KeyStatePayload>("keys:state", listener),
+    onBatch: (listener: (payload: KeyBatchPayload) => void) =>
+      subscribe<KeyBatchPayload>("keys:batch", listener),
     onRawInput: (listener: (payload: RawInputPayload) => void): Unsubscribe => {
       // rawKeyEventBus를 통해 구독 - 구독자가 있을 때만 백엔드가 emit
       let unsubscribeFn: (() => void) | null = null;
@@ -183,10 +235,27 @@ const api: DMNoteAPI = {
         }
       };
     },
+    grantRawInput: () => invoke<void>("raw_input_grant"),
+    revokeRawInput: () => invoke<void>("raw_input_revoke"),
     onCounterChanged: (listener: (payload: KeyCounterUpdate) => void) =>
-      subscribe<KeyCounterUpdate>("keys:counter", listener),
+      subscribeKeyEvent<KeyCounterUpdate>("keys:counter", listener),
     onCountersChanged: (listener: (payload: KeyCounters) => void) =>
       subscribe<KeyCounters>("keys:counters", listener),
+    onHold: (listener: (payload: KeyHoldEvent) => void) =>
+      subscribe<KeyHoldEvent>("keys:hold", listener),
+    onDoubleTap: (listener: (payload: KeyDoubleTapEvent) => void) =>
+      subscribe<KeyDoubleTapEvent>("keys:double-tap", listener),
+    listDevices: () => invoke<InputDeviceInfo[]>("input_devices_list"),
+    getDeviceFilters: () => invoke<KeyModeDevices>("keys_get_device_filters"),
+    setDeviceFilter: (mode: string, devices: string[]) =>
+      invoke<KeyModeDevices>("keys_set_device_filter", { mode, devices }),
+    onDeviceFiltersChanged: (listener: (filters: KeyModeDevices) => void) =>
+      subscribe<KeyModeDevices>("keys:device-filters-changed", listener),
+    getImeState: () => invoke<ImeState | null>("input_get_ime_state"),
+    onImeChanged: (listener: (state: ImeState) => void) =>
+      subscribe<ImeState>("input:ime", listener),
+    getChatterStats: () => invoke<KeyChatterStats[]>("keys_get_chatter_stats"),
+    resetChatterStats: () => invoke<void>("keys_reset_chatter_stats"),
     customTabs: {
       list: () => invoke<CustomTab[]>("custom_tabs_list"),
       create: (name: string) =>
@@ -221,6 +290,62 @@ const api: DMNoteAPI = {
     onResized: (listener: (payload: OverlayResizePayload) => void) =>
       subscribe<OverlayResizePayload>("overlay:resized", listener),
   },
+  daemon: {
+    ping: () => invoke<number>("daemon_ping"),
+    getCapture: () => invoke<DaemonCaptureState>("daemon_get_capture"),
+    setPaused: (paused: boolean) => invoke("daemon_set_paused", { paused }),
+    setFilters: (filters: DaemonCaptureFilters) =>
+      invoke("daemon_set_filters", { filters }),
+    getHealth: () => invoke<DaemonHealth>("daemon_get_health"),
+    restart: () => invoke("daemon_restart"),
+    getReplay: () => invoke<DaemonReplaySource | null>("daemon_get_replay"),
+    setReplay: (source: DaemonReplaySource | null) =>
+      invoke("daemon_set_replay", { source }),
+    onStatus: (listener: (payload: DaemonStatusPayload) => void) =>
+      subscribe<DaemonStatusPayload>("daemon:status", listener),
+    onCaptureChanged: (listener: (payload: DaemonCaptureState) => void) =>
+      subscribe<DaemonCaptureState>("daemon:capture-changed", listener),
+  },
+  diagnostics: {
+    getLatency: () => invoke<LatencyReport>("diagnostics_get_latency"),
+    resetLatency: () => invoke<void>("diagnostics_reset_latency"),
+    reportRenderLatency: (samplesUs: number[]) =>
+      invoke<void>("diagnostics_report_render_latency", { samplesUs }),
+  },
+  stats: {
+    getKps: () => invoke<KpsReport>("stats_get_kps"),
+    resetKps: (mode?: string) =>
+      invoke<KpsReport>("stats_reset_kps", { mode: mode ?? null }),
+    onKps: (listener: (payload: KpsReport) => void) =>
+      subscribe<KpsReport>("stats:kps", listener),
+    getKeyTiming: () => invoke<KeyTimingReport>("stats_get_key_timing"),
+    resetKeyTiming: (mode?: string) =>
+      invoke<KeyTimingReport>("stats_reset_key_timing", {
+        mode: mode ?? null,
+      }),
+    exportKeyTiming: () => invoke<StatsExportResult>("stats_export_key_timing"),
+    getCounterHistory: ({ mode, ...query }: CounterHistoryQuery) =>
+      invoke<CounterHistoryRange>("stats_get_counter_history", {
+        ...query,
+        mode: mode ?? null,
+      }),
+    exportCounterHistory: (
+      { mode, ...query }: CounterHistoryQuery,
+      format: "csv" | "json"
+    ) =>
+      invoke<StatsExportResult>("stats_export_counter_history", {
+        ...query,
+        mode: mode ?? null,
+        format,
+      }),
+    listSessions: () => invoke<PlaySession[]>("stats_list_sessions"),
+    deleteSession: (id: string) =>
+      invoke<boolean>("stats_delete_session", { id }),
+    exportSessions: (format: "csv" | "json") =>
+      invoke<StatsExportResult>("stats_export_sessions", { format }),
+    onSessionEnded: (listener: (payload: PlaySession) => void) =>
+      subscribe<PlaySession>("session:ended", listener),
+  },
   css: {
     get: () => invoke<CustomCss>("css_get"),
     getUse: () => invoke<boolean>("css_get_use"),
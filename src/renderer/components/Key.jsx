@@ -1,6 +1,6 @@
 import React, { memo, useMemo, useCallback, useRef, useEffect } from "react";
 import { getCurrentWindow } from "@tauri-apps/api/window";
-import { getKeySignal } from "@stores/keySignals";
+import { getKeySignal, getKeyGestureSignal } from "@stores/keySignals";
 import { getKeyCounterSignal } from "@stores/keyCounterSignals";
 import { useSignals } from "@preact/signals-react/runtime";
 import { isMac } from "@utils/platform";
@@ -648,6 +648,7 @@ export const Key = memo(
     // 각 Key는 자신의 활성 상태 신호를 직접 구독
     const selectorKey = globalKey || keyName;
     const active = getKeySignal(selectorKey).value;
+    const gesture = getKeyGestureSignal(selectorKey).value;
     const {
       dx,
       dy,
@@ -931,6 +932,7 @@ export const Key = memo(
         className={`absolute ${className || ""}`}
         style={keyStyle}
         data-state={active ? "active" : "inactive"}
+        data-gesture={gesture ?? undefined}
         onMouseDown={handleKeyMouseDown}
       >
         {currentImage ? (
@@ -563,9 +563,12 @@ const PropertiesPanel: React.FC<PropertiesPanelProps> = ({
       return undefined;
     }
 
+    // mappedKeysOnly여도 아직 매핑되지 않은 키를 받을 수 있도록
+    window.api.keys.grantRawInput?.().catch(() => {});
     const unsubscribe = window.api.keys.onRawInput((payload: any) => {
       if (!payload || payload.state !== "DOWN") return;
       const targetLabel =
+        payload.code ||
         payload.label ||
         (Array.isArray(payload.labels) ? payload.labels[0] : null);
       if (!targetLabel) return;
@@ -585,6 +588,7 @@ const PropertiesPanel: React.FC<PropertiesPanelProps> = ({
     });
 
     return () => {
+      window.api.keys.revokeRawInput?.().catch(() => {});
       try {
         unsubscribe?.();
       } catch (error) {
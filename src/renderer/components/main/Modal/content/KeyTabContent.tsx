@@ -88,9 +88,12 @@ const KeyTabContent = forwardRef<KeyTabContentRef, KeyTabContentProps>(
         return undefined;
       }
 
+      // mappedKeysOnly여도 아직 매핑되지 않은 키를 받을 수 있도록
+      window.api.keys.grantRawInput?.().catch(() => {});
       const unsubscribe = window.api.keys.onRawInput((payload: any) => {
         if (!payload || payload.state !== "DOWN") return;
         const targetLabel =
+          payload.code ||
           payload.label ||
           (Array.isArray(payload.labels) ? payload.labels[0] : null);
         if (!targetLabel) return;
@@ -112,6 +115,7 @@ const KeyTabContent = forwardRef<KeyTabContentRef, KeyTabContentProps>(
       });
 
       return () => {
+        window.api.keys.revokeRawInput?.().catch(() => {});
         try {
           unsubscribe?.();
         } catch (error) {
@@ -12,7 +12,7 @@ import {
   type ShortcutsState,
 } from "@src/types/shortcuts";
 
-type ShortcutKey = keyof ShortcutsState;
+type ShortcutKey = Exclude<keyof ShortcutsState, "keyModeShortcuts">;
 
 interface ShortcutSettingsModalProps {
   isOpen: boolean;
@@ -32,6 +32,17 @@ const MODIFIER_CODES = new Set([
   "MetaRight",
 ]);
 
+// 첫 조합 입력 후 시퀀스의 두 번째 조합을 기다리는 시간
+const SEQUENCE_CAPTURE_MS = 1000;
+
+function formatKeyCode(key: string): string {
+  return key.startsWith("Key") && key.length === 4
+    ? key.slice(3)
+    : key.startsWith("Digit") && key.length === 6
+      ? key.slice(5)
+      : key;
+}
+
 function formatShortcut(binding: ShortcutBinding, macOS: boolean): string {
   if (!binding?.key) return "";
 
@@ -41,27 +52,30 @@ function formatShortcut(binding: ShortcutBinding, macOS: boolean): string {
   if (binding.alt) parts.push("Alt");
   if (binding.shift) parts.push("Shift");
 
-  const key = binding.key;
-  const displayKey =
-    key.startsWith("Key") && key.length === 4
-      ? key.slice(3)
-      : key.startsWith("Digit") && key.length === 6
-        ? key.slice(5)
-        : key === "Space"
-          ? "Space"
-          : key;
-
-  parts.push(displayKey);
-  return parts.join(" + ");
+  parts.push(formatKeyCode(binding.key));
+  (binding.chord ?? []).forEach((key) => parts.push(formatKeyCode(key)));
+  const combo = parts.join(" + ");
+
+  return binding.leader?.key
+    ? `${formatShortcut(binding.leader, macOS)}, ${combo}`
+    : combo;
 }
 
 function isSameShortcut(a: ShortcutBinding, b: ShortcutBinding): boolean {
+  const chordA = [...(a.chord ?? [])].sort().join(",");
+  const chordB = [...(b.chord ?? [])].sort().join(",");
+  const sameLeader =
+    a.leader?.key && b.leader?.key
+      ? isSameShortcut(a.leader, b.leader)
+      : !a.leader?.key && !b.leader?.key;
   return (
     a.key === b.key &&
     !!a.ctrl === !!b.ctrl &&
     !!a.shift === !!b.shift &&
     !!a.alt === !!b.alt &&
-    !!a.meta === !!b.meta
+    !!a.meta === !!b.meta &&
+    chordA === chordB &&
+    sameLeader
   );
 }
 
@@ -208,9 +222,21 @@ export default function ShortcutSettingsModal({
       e.stopPropagation();
     };
 
+    // 누르고 있는 키들이 하나의 조합(동시 입력)이 되고, 키를 떼면 확정됩니다.
+    // 확정 후 잠시 더 입력을 받아 두 번째 조합이 들어오면 시퀀스로 저장합니다.
+    const held: string[] = [];
+    let combo: ShortcutBinding | null = null;
+    let leader: ShortcutBinding | null = null;
+    let sequenceTimer: number | undefined;
+
+    const commit = (binding: ShortcutBinding) => {
+      setDraft((prev) => ({ ...prev, [listeningKey!]: binding }));
+    };
+
     const onKeyDown = (e: KeyboardEvent) => {
       e.preventDefault();
       e.stopPropagation();
+      if (e.repeat) return;
 
       const code = e.code || "";
       const mods = {
@@ -219,49 +245,61 @@ export default function ShortcutSettingsModal({
         alt: e.altKey,
         meta: e.metaKey,
       };
+      const noMods = !mods.ctrl && !mods.shift && !mods.alt && !mods.meta;
 
-      if (
-        code === "Escape" &&
-        !mods.ctrl &&
-        !mods.shift &&
-        !mods.alt &&
-        !mods.meta
-      ) {
+      if (code === "Escape" && noMods && held.length === 0) {
         setListeningKey(null);
         return;
       }
 
-      if (
-        code === "Backspace" &&
-        !mods.ctrl &&
-        !mods.shift &&
-        !mods.alt &&
-        !mods.meta
-      ) {
-        setDraft((prev) => ({ ...prev, [listeningKey!]: { key: "" } }));
+      if (code === "Backspace" && noMods && held.length === 0) {
+        commit({ key: "" });
         setListeningKey(null);
         return;
       }
 
       if (!code || MODIFIER_CODES.has(code)) return;
 
-      const nextBinding: ShortcutBinding = {
-        key: code,
+      window.clearTimeout(sequenceTimer);
+      if (!held.includes(code)) held.push(code);
+      combo = {
+        key: held[0],
         ...mods,
+        ...(held.length > 1 ? { chord: held.slice(1) } : {}),
       };
+    };
 
-      setDraft((prev) => ({ ...prev, [listeningKey!]: nextBinding }));
-      setListeningKey(null);
+    const onKeyUp = (e: KeyboardEvent) => {
+      block(e);
+      if (!held.includes(e.code)) return;
+
+      held.length = 0;
+      const captured = combo;
+      combo = null;
+      if (!captured) return;
+
+      if (leader) {
+        commit({ ...captured, leader });
+        setListeningKey(null);
+        return;
+      }
+      commit(captured);
+      leader = captured;
+      sequenceTimer = window.setTimeout(
+        () => setListeningKey(null),
+        SEQUENCE_CAPTURE_MS,
+      );
     };
 
     window.addEventListener("keydown", onKeyDown, true);
-    window.addEventListener("keyup", block, true);
+    window.addEventListener("keyup", onKeyUp, true);
     window.addEventListener("keypress", block, true);
 
     return () => {
       (window as any).__dmn_isKeyListening = false;
+      window.clearTimeout(sequenceTimer);
       window.removeEventListener("keydown", onKeyDown, true);
-      window.removeEventListener("keyup", block, true);
+      window.removeEventListener("keyup", onKeyUp, true);
       window.removeEventListener("keypress", block, true);
     };
   }, [isListening, listeningKey]);
@@ -288,6 +326,24 @@ export default function ShortcutSettingsModal({
           label: t("shortcutSetting.toggleAlwaysOnTop"),
           help: t("shortcutSetting.toggleAlwaysOnTopHint"),
         },
+        {
+          section: "overlay" as const,
+          key: "resetModeCounters" as const,
+          label: t("shortcutSetting.resetModeCounters"),
+          help: t("shortcutSetting.resetModeCountersHint"),
+        },
+        {
+          section: "overlay" as const,
+          key: "toggleKeyCounter" as const,
+          label: t("shortcutSetting.toggleKeyCounter"),
+          help: t("shortcutSetting.toggleKeyCounterHint"),
+        },
+        {
+          section: "overlay" as const,
+          key: "toggleCapturePause" as const,
+          label: t("shortcutSetting.toggleCapturePause"),
+          help: t("shortcutSetting.toggleCapturePauseHint"),
+        },
 
         // Canvas
         {
@@ -493,6 +549,9 @@ export default function ShortcutSettingsModal({
                   </p>
                   <div className="flex-1 h-[1px] bg-[#2A2A30]" />
                 </div>
+                <p className="text-[11px] leading-[15px] text-[#6F7280] -mt-[9px]">
+                  {t("shortcutSetting.canvasLocalNote")}
+                </p>
                 <div className="flex flex-col gap-[19px]">
                   {canvasActions.map((action) => {
                     const binding = safeDraft[action.key];
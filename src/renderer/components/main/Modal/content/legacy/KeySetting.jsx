@@ -49,6 +49,7 @@ export default function KeySetting({
     const unsubscribe = window.api.keys.onRawInput((payload) => {
       if (!payload || payload.state !== "DOWN") return;
       const targetLabel =
+        payload.code ||
         payload.label ||
         (Array.isArray(payload.labels) ? payload.labels[0] : null);
       if (!targetLabel) return;
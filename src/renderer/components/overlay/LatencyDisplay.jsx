@@ -1,49 +1,66 @@
 import React from "react";
+import { useTranslation } from "@contexts/I18nContext";
+
+const formatMs = (us) => (us / 1000).toFixed(2);
+
+function LatencyRow({ label, summary }) {
+  return (
+    <tr>
+      <td className="pr-2">{label}</td>
+      {summary.count > 0 ? (
+        <>
+          <td className="pr-2 text-right">{formatMs(summary.p50Us)}</td>
+          <td className="pr-2 text-right">{formatMs(summary.p95Us)}</td>
+          <td className="pr-2 text-right">{formatMs(summary.p99Us)}</td>
+          <td className="pr-2 text-right">{formatMs(summary.maxUs)}</td>
+        </>
+      ) : (
+        <td colSpan={4} className="text-center">
+          -
+        </td>
+      )}
+      <td className="text-right">{summary.count}</td>
+    </tr>
+  );
+}
+
+// 지연 시간 진단 패널 (inputSettings.latencyPanel)
+export function LatencyDisplay({ report, onReset }) {
+  const { t } = useTranslation();
+  if (!report) return null;
 
-export function LatencyDisplay({
-  stats,
-  recentLatencies,
-  isMeasuring,
-  onToggle,
-  onReset,
-}) {
   return (
     <div
-      className="absolute top-0 left-0 p-2 bg-black bg-opacity-60 text-white text-xs rounded-md font-mono"
-      style={{ appRegion: "no-drag" }} // 이 영역은 클릭 가능하도록 설정
+      className="latency-panel absolute bottom-[6px] left-[6px] p-2 bg-black/60 text-white text-[11px] rounded-md font-mono [app-region:no-drag]"
+      data-latency-panel
     >
-      <div className="flex items-center gap-2 mb-2">
-        <button
-          onClick={onToggle}
-          className={`px-2 py-1 text-white rounded ${
-            isMeasuring ? "bg-red-500" : "bg-green-500"
-          }`}
-        >
-          {isMeasuring ? "측정 중단" : "측정 시작"}
-        </button>
+      <div className="flex items-center justify-between gap-3 mb-1">
+        <span>
+          {t("latency.title")} ({report.angleMode})
+        </span>
         <button
           onClick={onReset}
-          className="px-2 py-1 text-white rounded bg-gray-500"
+          className="px-2 py-[1px] text-white rounded bg-gray-500"
         >
-          초기화
+          {t("latency.reset")}
         </button>
       </div>
-      <div className="border-b border-gray-500 my-1"></div>
-      <div>상태: {isMeasuring ? "측정 중..." : "중단됨"}</div>
-      <div className="mt-1">
-        <div>측정 횟수: {stats.count}</div>
-        <div>평균 (최근 100회): {stats.avg} ms</div>
-        <div>최소: {stats.min > 0 ? `${stats.min} ms` : "-"}</div>
-        <div>최대: {stats.max > 0 ? `${stats.max} ms` : "-"}</div>
-      </div>
-      <div className="mt-2">
-        <div>최근 측정값:</div>
-        <ul className="pl-2">
-          {recentLatencies.map((latency, index) => (
-            <li key={index}>- {latency} ms</li>
-          ))}
-        </ul>
-      </div>
+      <table>
+        <thead>
+          <tr className="text-gray-300">
+            <th className="pr-2 text-left font-normal">ms</th>
+            <th className="pr-2 text-right font-normal">p50</th>
+            <th className="pr-2 text-right font-normal">p95</th>
+            <th className="pr-2 text-right font-normal">p99</th>
+            <th className="pr-2 text-right font-normal">max</th>
+            <th className="text-right font-normal">n</th>
+          </tr>
+        </thead>
+        <tbody>
+          <LatencyRow label={t("latency.emit")} summary={report.emit} />
+          <LatencyRow label={t("latency.render")} summary={report.render} />
+        </tbody>
+      </table>
     </div>
   );
 }
@@ -186,6 +186,8 @@ export function useGridZoomPan({
 
       const matchesShortcut = (event: KeyboardEvent, binding?: ShortcutBinding) => {
         if (!binding?.key) return false;
+        // 동시 입력/시퀀스 단축키는 데몬이 전역으로 처리
+        if (binding.chord?.length || binding.leader?.key) return false;
         const ctrl = !!binding.ctrl;
         const shift = !!binding.shift;
         const alt = !!binding.alt;
@@ -307,6 +309,15 @@ export function useGridZoomPan({
     };
   }, [handleKeyDown]);
 
+  // 게임 포커스 중 전역 단축키로 들어온 줌 동작
+  useEffect(() => {
+    return window.api.app.onShortcut(({ action }) => {
+      if (action === "zoomIn") zoomIn();
+      else if (action === "zoomOut") zoomOut();
+      else if (action === "resetZoom") resetZoom();
+    });
+  }, [resetZoom, zoomIn, zoomOut]);
+
   return {
     // 현재 상태
     zoom,
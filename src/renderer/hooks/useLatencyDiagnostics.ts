@@ -0,0 +1,64 @@
+import { useEffect, useState } from "react";
+import type { LatencyReport } from "@src/types/api";
+
+const REPORT_INTERVAL_MS = 1000;
+
+/**
+ * 오버레이 렌더 지연 측정 훅
+ * 켜져 있는 동안 눌림 이벤트가 반영된 프레임 시각을 재서 백엔드에 모아 보내고,
+ * 세션 진단 결과를 주기적으로 받아옵니다.
+ * 웹뷰의 performance 시계는 앱과 다르므로 벽시계(캡처 시각 + wallClockOffsetUs)로 비교합니다.
+ */
+export function useLatencyDiagnostics(enabled: boolean) {
+  const [report, setReport] = useState<LatencyReport | null>(null);
+
+  useEffect(() => {
+    if (!enabled) {
+      setReport(null);
+      return;
+    }
+
+    let pending: number[] = [];
+    const unsubscribe = window.api.keys.onKeyState(
+      ({ state, timestampUs, wallClockOffsetUs }) => {
+        if (state !== "DOWN") return;
+        const capturedAtUs = timestampUs + wallClockOffsetUs;
+        // 이 콜백 다음 프레임에 키 상태가 그려짐
+        requestAnimationFrame(() => {
+          const renderedAtUs =
+            (performance.timeOrigin + performance.now()) * 1000;
+          pending.push(Math.max(0, Math.round(renderedAtUs - capturedAtUs)));
+        });
+      }
+    );
+
+    const refresh = () => {
+      const samples = pending;
+      pending = [];
+      const sent = samples.length
+        ? window.api.diagnostics.reportRenderLatency(samples)
+        : Promise.resolve();
+      sent
+        .then(() => window.api.diagnostics.getLatency())
+        .then(setReport)
+        .catch(() => {});
+    };
+    refresh();
+    const timer = window.setInterval(refresh, REPORT_INTERVAL_MS);
+
+    return () => {
+      unsubscribe();
+      window.clearInterval(timer);
+    };
+  }, [enabled]);
+
+  const reset = () => {
+    window.api.diagnostics
+      .resetLatency()
+      .then(() => window.api.diagnostics.getLatency())
+      .then(setReport)
+      .catch(() => {});
+  };
+
+  return { report, reset };
+}
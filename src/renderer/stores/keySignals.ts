@@ -3,6 +3,12 @@ import { signal, type Signal } from "@preact/signals-react";
 // Map of globalKey -> Signal<boolean>
 const keySignals = new Map<string, Signal<boolean>>();
 
+/** 눌린 키에서 감지된 제스처 (키를 떼면 해제) */
+export type KeyGesture = "hold" | "double-tap";
+
+// Map of globalKey -> Signal<KeyGesture | null>
+const keyGestureSignals = new Map<string, Signal<KeyGesture | null>>();
+
 export function getKeySignal(key: string): Signal<boolean> {
   let s = keySignals.get(key);
   if (!s) {
@@ -16,6 +22,20 @@ export function setKeyActive(key: string, isActive: boolean) {
   getKeySignal(key).value = isActive;
 }
 
+export function getKeyGestureSignal(key: string): Signal<KeyGesture | null> {
+  let s = keyGestureSignals.get(key);
+  if (!s) {
+    s = signal(null);
+    keyGestureSignals.set(key, s);
+  }
+  return s;
+}
+
+export function setKeyGesture(key: string, gesture: KeyGesture | null) {
+  getKeyGestureSignal(key).value = gesture;
+}
+
 export function resetAllKeySignals() {
   for (const s of keySignals.values()) s.value = false;
+  for (const s of keyGestureSignals.values()) s.value = null;
 }
@@ -1,125 +1,140 @@
-export const keyMaps = {
+// 키 ID는 KeyboardEvent.code 기준 (legacyKey: 이전 버전이 저장하던 플랫폼 라벨)
+const keyTable = {
   // 알파벳
-  KeyA: { browserKey: 'a', globalKey: 'A', displayName: 'A' },
-  KeyB: { browserKey: 'b', globalKey: 'B', displayName: 'B' },
-  KeyC: { browserKey: 'c', globalKey: 'C', displayName: 'C' },
-  KeyD: { browserKey: 'd', globalKey: 'D', displayName: 'D' },
-  KeyE: { browserKey: 'e', globalKey: 'E', displayName: 'E' },
-  KeyF: { browserKey: 'f', globalKey: 'F', displayName: 'F' },
-  KeyG: { browserKey: 'g', globalKey: 'G', displayName: 'G' },
-  KeyH: { browserKey: 'h', globalKey: 'H', displayName: 'H' },
-  KeyI: { browserKey: 'i', globalKey: 'I', displayName: 'I' },
-  KeyJ: { browserKey: 'j', globalKey: 'J', displayName: 'J' },
-  KeyK: { browserKey: 'k', globalKey: 'K', displayName: 'K' },
-  KeyL: { browserKey: 'l', globalKey: 'L', displayName: 'L' },
-  KeyM: { browserKey: 'm', globalKey: 'M', displayName: 'M' },
-  KeyN: { browserKey: 'n', globalKey: 'N', displayName: 'N' },
-  KeyO: { browserKey: 'o', globalKey: 'O', displayName: 'O' },
-  KeyP: { browserKey: 'p', globalKey: 'P', displayName: 'P' },
-  KeyQ: { browserKey: 'q', globalKey: 'Q', displayName: 'Q' },
-  KeyR: { browserKey: 'r', globalKey: 'R', displayName: 'R' },
-  KeyS: { browserKey: 's', globalKey: 'S', displayName: 'S' },
-  KeyT: { browserKey: 't', globalKey: 'T', displayName: 'T' },
-  KeyU: { browserKey: 'u', globalKey: 'U', displayName: 'U' },
-  KeyV: { browserKey: 'v', globalKey: 'V', displayName: 'V' },
-  KeyW: { browserKey: 'w', globalKey: 'W', displayName: 'W' },
-  KeyX: { browserKey: 'x', globalKey: 'X', displayName: 'X' },
-  KeyY: { browserKey: 'y', globalKey: 'Y', displayName: 'Y' },
-  KeyZ: { browserKey: 'z', globalKey: 'Z', displayName: 'Z' },
+  KeyA: { browserKey: 'a', legacyKey: 'A', displayName: 'A' },
+  KeyB: { browserKey: 'b', legacyKey: 'B', displayName: 'B' },
+  KeyC: { browserKey: 'c', legacyKey: 'C', displayName: 'C' },
+  KeyD: { browserKey: 'd', legacyKey: 'D', displayName: 'D' },
+  KeyE: { browserKey: 'e', legacyKey: 'E', displayName: 'E' },
+  KeyF: { browserKey: 'f', legacyKey: 'F', displayName: 'F' },
+  KeyG: { browserKey: 'g', legacyKey: 'G', displayName: 'G' },
+  KeyH: { browserKey: 'h', legacyKey: 'H', displayName: 'H' },
+  KeyI: { browserKey: 'i', legacyKey: 'I', displayName: 'I' },
+  KeyJ: { browserKey: 'j', legacyKey: 'J', displayName: 'J' },
+  KeyK: { browserKey: 'k', legacyKey: 'K', displayName: 'K' },
+  KeyL: { browserKey: 'l', legacyKey: 'L', displayName: 'L' },
+  KeyM: { browserKey: 'm', legacyKey: 'M', displayName: 'M' },
+  KeyN: { browserKey: 'n', legacyKey: 'N', displayName: 'N' },
+  KeyO: { browserKey: 'o', legacyKey: 'O', displayName: 'O' },
+  KeyP: { browserKey: 'p', legacyKey: 'P', displayName: 'P' },
+  KeyQ: { browserKey: 'q', legacyKey: 'Q', displayName: 'Q' },
+  KeyR: { browserKey: 'r', legacyKey: 'R', displayName: 'R' },
+  KeyS: { browserKey: 's', legacyKey: 'S', displayName: 'S' },
+  KeyT: { browserKey: 't', legacyKey: 'T', displayName: 'T' },
+  KeyU: { browserKey: 'u', legacyKey: 'U', displayName: 'U' },
+  KeyV: { browserKey: 'v', legacyKey: 'V', displayName: 'V' },
+  KeyW: { browserKey: 'w', legacyKey: 'W', displayName: 'W' },
+  KeyX: { browserKey: 'x', legacyKey: 'X', displayName: 'X' },
+  KeyY: { browserKey: 'y', legacyKey: 'Y', displayName: 'Y' },
+  KeyZ: { browserKey: 'z', legacyKey: 'Z', displayName: 'Z' },
 
   // 숫자
-  Digit0: { browserKey: '0', globalKey: '0', displayName: '0' },
-  Digit1: { browserKey: '1', globalKey: '1', displayName: '1' },
-  Digit2: { browserKey: '2', globalKey: '2', displayName: '2' },
-  Digit3: { browserKey: '3', globalKey: '3', displayName: '3' },
-  Digit4: { browserKey: '4', globalKey: '4', displayName: '4' },
-  Digit5: { browserKey: '5', globalKey: '5', displayName: '5' },
-  Digit6: { browserKey: '6', globalKey: '6', displayName: '6' },
-  Digit7: { browserKey: '7', globalKey: '7', displayName: '7' },
-  Digit8: { browserKey: '8', globalKey: '8', displayName: '8' },
-  Digit9: { browserKey: '9', globalKey: '9', displayName: '9' },
+  Digit0: { browserKey: '0', legacyKey: '0', displayName: '0' },
+  Digit1: { browserKey: '1', legacyKey: '1', displayName: '1' },
+  Digit2: { browserKey: '2', legacyKey: '2', displayName: '2' },
+  Digit3: { browserKey: '3', legacyKey: '3', displayName: '3' },
+  Digit4: { browserKey: '4', legacyKey: '4', displayName: '4' },
+  Digit5: { browserKey: '5', legacyKey: '5', displayName: '5' },
+  Digit6: { browserKey: '6', legacyKey: '6', displayName: '6' },
+  Digit7: { browserKey: '7', legacyKey: '7', displayName: '7' },
+  Digit8: { browserKey: '8', legacyKey: '8', displayName: '8' },
+  Digit9: { browserKey: '9', legacyKey: '9', displayName: '9' },
 
   // 특수키
-  ShiftLeft: { browserKey: 'Shift', globalKey: 'LEFT SHIFT', displayName: 'LShift' },
-  ShiftRight: { browserKey: 'Shift', globalKey: 'RIGHT SHIFT', displayName: 'RShift' },
-  ControlLeft: { browserKey: 'Control', globalKey: 'LEFT CTRL', displayName: 'LCtrl' },
-  ControlRight: { browserKey: 'Control', globalKey: '25', displayName: 'RCtrl' },
-  AltLeft: { browserKey: 'Alt', globalKey: 'LEFT ALT', displayName: 'LAlt' },
-  AltRight: { browserKey: 'Alt', globalKey: '21', displayName: 'RAlt' },
-  Space: { browserKey: ' ', globalKey: 'SPACE', displayName: 'Space' },
-  Enter: { browserKey: 'Enter', globalKey: 'RETURN', displayName: 'Enter' },
-  Tab: { browserKey: 'Tab', globalKey: 'TAB', displayName: 'Tab' },
-  Backspace: { browserKey: 'Backspace', globalKey: 'BACKSPACE', displayName: 'Back' },
-  CapsLock: { browserKey: 'CapsLock', globalKey: 'CAPS LOCK', displayName: 'Caps' },
-  MetaLeft: { browserKey: 'Meta', globalKey: '91', displayName: 'LWin' },
-  MetaRight: { browserKey: 'Meta', globalKey: '92', displayName: 'RWin' },
-  Fn: { browserKey: 'Fn', globalKey: 'FN', displayName: 'Fn' },
+  ShiftLeft: { browserKey: 'Shift', legacyKey: 'LEFT SHIFT', displayName: 'LShift' },
+  ShiftRight: { browserKey: 'Shift', legacyKey: 'RIGHT SHIFT', displayName: 'RShift' },
+  ControlLeft: { browserKey: 'Control', legacyKey: 'LEFT CTRL', displayName: 'LCtrl' },
+  ControlRight: { browserKey: 'Control', legacyKey: '25', displayName: 'RCtrl' },
+  AltLeft: { browserKey: 'Alt', legacyKey: 'LEFT ALT', displayName: 'LAlt' },
+  AltRight: { browserKey: 'Alt', legacyKey: '21', displayName: 'RAlt' },
+  Space: { browserKey: ' ', legacyKey: 'SPACE', displayName: 'Space' },
+  Enter: { browserKey: 'Enter', legacyKey: 'RETURN', displayName: 'Enter' },
+  Tab: { browserKey: 'Tab', legacyKey: 'TAB', displayName: 'Tab' },
+  Backspace: { browserKey: 'Backspace', legacyKey: 'BACKSPACE', displayName: 'Back' },
+  CapsLock: { browserKey: 'CapsLock', legacyKey: 'CAPS LOCK', displayName: 'Caps' },
+  MetaLeft: { browserKey: 'Meta', legacyKey: '91', displayName: 'LWin' },
+  MetaRight: { browserKey: 'Meta', legacyKey: '92', displayName: 'RWin' },
+  Fn: { browserKey: 'Fn', legacyKey: 'FN', displayName: 'Fn' },
 
   // 기능키
-  F1: { browserKey: 'F1', globalKey: 'F1', displayName: 'F1' },
-  F2: { browserKey: 'F2', globalKey: 'F2', displayName: 'F2' },
-  F3: { browserKey: 'F3', globalKey: 'F3', displayName: 'F3' },
-  F4: { browserKey: 'F4', globalKey: 'F4', displayName: 'F4' },
-  F5: { browserKey: 'F5', globalKey: 'F5', displayName: 'F5' },
-  F6: { browserKey: 'F6', globalKey: 'F6', displayName: 'F6' },
-  F7: { browserKey: 'F7', globalKey: 'F7', displayName: 'F7' },
-  F8: { browserKey: 'F8', globalKey: 'F8', displayName: 'F8' },
-  F9: { browserKey: 'F9', globalKey: 'F9', displayName: 'F9' },
-  F10: { browserKey: 'F10', globalKey: 'F10', displayName: 'F10' },
-  F11: { browserKey: 'F11', globalKey: 'F11', displayName: 'F11' },
-  F12: { browserKey: 'F12', globalKey: 'F12', displayName: 'F12' },
+  F1: { browserKey: 'F1', legacyKey: 'F1', displayName: 'F1' },
+  F2: { browserKey: 'F2', legacyKey: 'F2', displayName: 'F2' },
+  F3: { browserKey: 'F3', legacyKey: 'F3', displayName: 'F3' },
+  F4: { browserKey: 'F4', legacyKey: 'F4', displayName: 'F4' },
+  F5: { browserKey: 'F5', legacyKey: 'F5', displayName: 'F5' },
+  F6: { browserKey: 'F6', legacyKey: 'F6', displayName: 'F6' },
+  F7: { browserKey: 'F7', legacyKey: 'F7', displayName: 'F7' },
+  F8: { browserKey: 'F8', legacyKey: 'F8', displayName: 'F8' },
+  F9: { browserKey: 'F9', legacyKey: 'F9', displayName: 'F9' },
+  F10: { browserKey: 'F10', legacyKey: 'F10', displayName: 'F10' },
+  F11: { browserKey: 'F11', legacyKey: 'F11', displayName: 'F11' },
+  F12: { browserKey: 'F12', legacyKey: 'F12', displayName: 'F12' },
 
   // 방향키
-  ArrowUp: { browserKey: 'ArrowUp', globalKey: 'UP ARROW', displayName: '↑' },
-  ArrowDown: { browserKey: 'ArrowDown', globalKey: 'DOWN ARROW', displayName: '↓' },
-  ArrowLeft: { browserKey: 'ArrowLeft', globalKey: 'LEFT ARROW', displayName: '←' },
-  ArrowRight: { browserKey: 'ArrowRight', globalKey: 'RIGHT ARROW', displayName: '→' },
+  ArrowUp: { browserKey: 'ArrowUp', legacyKey: 'UP ARROW', displayName: '↑' },
+  ArrowDown: { browserKey: 'ArrowDown', legacyKey: 'DOWN ARROW', displayName: '↓' },
+  ArrowLeft: { browserKey: 'ArrowLeft', legacyKey: 'LEFT ARROW', displayName: '←' },
+  ArrowRight: { browserKey: 'ArrowRight', legacyKey: 'RIGHT ARROW', displayName: '→' },
 
   // 특수문자
-  Minus: { browserKey: '-', globalKey: 'MINUS', displayName: '-' },
-  Equal: { browserKey: '=', globalKey: 'EQUALS', displayName: '=' },
-  BracketLeft: { browserKey: '[', globalKey: 'SQUARE BRACKET OPEN', displayName: '[' },
-  BracketRight: { browserKey: ']', globalKey: 'SQUARE BRACKET CLOSE', displayName: ']' },
-  Semicolon: { browserKey: ';', globalKey: 'SEMICOLON', displayName: ';' },
-  Quote: { browserKey: "'", globalKey: 'QUOTE', displayName: "'" },
-  Backquote: { browserKey: '`', globalKey: 'SECTION', displayName: '`' },
-  Backslash: { browserKey: '\\', globalKey: 'BACKSLASH', displayName: '\\' },
-  Comma: { browserKey: ',', globalKey: 'COMMA', displayName: ',' },
-  Period: { browserKey: '.', globalKey: 'DOT', displayName: '.' },
-  Slash: { browserKey: '/', globalKey: 'FORWARD SLASH', displayName: '/' },
+  Minus: { browserKey: '-', legacyKey: 'MINUS', displayName: '-' },
+  Equal: { browserKey: '=', legacyKey: 'EQUALS', displayName: '=' },
+  BracketLeft: { browserKey: '[', legacyKey: 'SQUARE BRACKET OPEN', displayName: '[' },
+  BracketRight: { browserKey: ']', legacyKey: 'SQUARE BRACKET CLOSE', displayName: ']' },
+  Semicolon: { browserKey: ';', legacyKey: 'SEMICOLON', displayName: ';' },
+  Quote: { browserKey: "'", legacyKey: 'QUOTE', displayName: "'" },
+  Backquote: { browserKey: '`', legacyKey: 'SECTION', displayName: '`' },
+  Backslash: { browserKey: '\\', legacyKey: 'BACKSLASH', displayName: '\\' },
+  Comma: { browserKey: ',', legacyKey: 'COMMA', displayName: ',' },
+  Period: { browserKey: '.', legacyKey: 'DOT', displayName: '.' },
+  Slash: { browserKey: '/', legacyKey: 'FORWARD SLASH', displayName: '/' },
+
+  // 한국어·일본어 자판
+  Lang1: { browserKey: 'HangulMode', legacyKey: 'HANGUL', displayName: '한/영' },
+  Lang2: { browserKey: 'HanjaMode', legacyKey: 'HANJA', displayName: '한자' },
+  Convert: { browserKey: 'Convert', legacyKey: 'CONVERT', displayName: '変換' },
+  NonConvert: { browserKey: 'NonConvert', legacyKey: 'NONCONVERT', displayName: '無変換' },
+  KanaMode: { browserKey: 'KanaMode', legacyKey: 'KANA', displayName: 'かな' },
+  IntlRo: { browserKey: '\\', legacyKey: 'RO', displayName: 'ろ' },
+  IntlYen: { browserKey: '\\', legacyKey: 'YEN', displayName: '¥' },
+  IntlBackslash: { browserKey: '\\', legacyKey: '226', displayName: '\\' },
 
   // 넘패드
-  Numpad0: { browserKey: '0', globalKey: 'NUMPAD 0', displayName: 'Num0' },
-  Numpad1: { browserKey: '1', globalKey: 'NUMPAD 1', displayName: 'Num1' },
-  Numpad2: { browserKey: '2', globalKey: 'NUMPAD 2', displayName: 'Num2' },
-  Numpad3: { browserKey: '3', globalKey: 'NUMPAD 3', displayName: 'Num3' },
-  Numpad4: { browserKey: '4', globalKey: 'NUMPAD 4', displayName: 'Num4' },
-  Numpad5: { browserKey: '5', globalKey: 'NUMPAD 5', displayName: 'Num5' },
-  Numpad6: { browserKey: '6', globalKey: 'NUMPAD 6', displayName: 'Num6' },
-  Numpad7: { browserKey: '7', globalKey: 'NUMPAD 7', displayName: 'Num7' },
-  Numpad8: { browserKey: '8', globalKey: 'NUMPAD 8', displayName: 'Num8' },
-  Numpad9: { browserKey: '9', globalKey: 'NUMPAD 9', displayName: 'Num9' },
-  NumpadMultiply: { browserKey: '*', globalKey: 'NUMPAD MULTIPLY', displayName: 'Num*' },
-  NumpadAdd: { browserKey: '+', globalKey: 'NUMPAD PLUS', displayName: 'Num+' },
-  NumpadSubtract: { browserKey: '-', globalKey: 'NUMPAD MINUS', displayName: 'Num-' },
-  NumpadDecimal: { browserKey: '.', globalKey: 'NUMPAD DELETE', displayName: 'Num.' },
-  NumpadDivide: { browserKey: '/', globalKey: 'NUMPAD DIVIDE', displayName: 'Num/' },
-  NumpadEnter: { browserKey: 'NumpadEnter', globalKey: 'NUMPAD RETURN', displayName: 'NEnt' },
+  Numpad0: { browserKey: '0', legacyKey: 'NUMPAD 0', displayName: 'Num0' },
+  Numpad1: { browserKey: '1', legacyKey: 'NUMPAD 1', displayName: 'Num1' },
+  Numpad2: { browserKey: '2', legacyKey: 'NUMPAD 2', displayName: 'Num2' },
+  Numpad3: { browserKey: '3', legacyKey: 'NUMPAD 3', displayName: 'Num3' },
+  Numpad4: { browserKey: '4', legacyKey: 'NUMPAD 4', displayName: 'Num4' },
+  Numpad5: { browserKey: '5', legacyKey: 'NUMPAD 5', displayName: 'Num5' },
+  Numpad6: { browserKey: '6', legacyKey: 'NUMPAD 6', displayName: 'Num6' },
+  Numpad7: { browserKey: '7', legacyKey: 'NUMPAD 7', displayName: 'Num7' },
+  Numpad8: { browserKey: '8', legacyKey: 'NUMPAD 8', displayName: 'Num8' },
+  Numpad9: { browserKey: '9', legacyKey: 'NUMPAD 9', displayName: 'Num9' },
+  NumpadMultiply: { browserKey: '*', legacyKey: 'NUMPAD MULTIPLY', displayName: 'Num*' },
+  NumpadAdd: { browserKey: '+', legacyKey: 'NUMPAD PLUS', displayName: 'Num+' },
+  NumpadSubtract: { browserKey: '-', legacyKey: 'NUMPAD MINUS', displayName: 'Num-' },
+  NumpadDecimal: { browserKey: '.', legacyKey: 'NUMPAD DELETE', displayName: 'Num.' },
+  NumpadDivide: { browserKey: '/', legacyKey: 'NUMPAD DIVIDE', displayName: 'Num/' },
+  NumpadEnter: { browserKey: 'NumpadEnter', legacyKey: 'NUMPAD RETURN', displayName: 'NEnt' },
 
   // 기타
-  Escape: { browserKey: 'Escape', globalKey: 'ESCAPE', displayName: 'Esc' },
-  PrintScreen: { browserKey: 'PrintScreen', globalKey: 'PRINT SCREEN', displayName: 'PrtSc' },
-  ScrollLock: { browserKey: 'ScrollLock', globalKey: 'SCROLL LOCK', displayName: 'ScrLk' },
-  Pause: { browserKey: 'Pause', globalKey: '19', displayName: 'Pause' },
-  Insert: { browserKey: 'Insert', globalKey: 'INS', displayName: 'Ins' },
-  Home: { browserKey: 'Home', globalKey: 'HOME', displayName: 'Home' },
-  PageUp: { browserKey: 'PageUp', globalKey: 'PAGE UP', displayName: 'PgUp' },
-  Delete: { browserKey: 'Delete', globalKey: 'DELETE', displayName: 'Del' },
-  End: { browserKey: 'End', globalKey: 'END', displayName: 'End' },
-  PageDown: { browserKey: 'PageDown', globalKey: 'PAGE DOWN', displayName: 'PgDn' },
-  ContextMenu: { browserKey: 'ContextMenu', globalKey: 'CONTEXT MENU', displayName: 'Menu' },
+  Escape: { browserKey: 'Escape', legacyKey: 'ESCAPE', displayName: 'Esc' },
+  PrintScreen: { browserKey: 'PrintScreen', legacyKey: 'PRINT SCREEN', displayName: 'PrtSc' },
+  ScrollLock: { browserKey: 'ScrollLock', legacyKey: 'SCROLL LOCK', displayName: 'ScrLk' },
+  Pause: { browserKey: 'Pause', legacyKey: '19', displayName: 'Pause' },
+  Insert: { browserKey: 'Insert', legacyKey: 'INS', displayName: 'Ins' },
+  Home: { browserKey: 'Home', legacyKey: 'HOME', displayName: 'Home' },
+  PageUp: { browserKey: 'PageUp', legacyKey: 'PAGE UP', displayName: 'PgUp' },
+  Delete: { browserKey: 'Delete', legacyKey: 'DELETE', displayName: 'Del' },
+  End: { browserKey: 'End', legacyKey: 'END', displayName: 'End' },
+  PageDown: { browserKey: 'PageDown', legacyKey: 'PAGE DOWN', displayName: 'PgDn' },
+  ContextMenu: { browserKey: 'ContextMenu', legacyKey: 'CONTEXT MENU', displayName: 'Menu' },
 };
 
+export const keyMaps = Object.fromEntries(
+  Object.entries(keyTable).map(([code, item]) => [code, { ...item, globalKey: code }])
+);
+
 // 마우스 / 게임패드
 const extraGlobalKeyMap = {
   MOUSE1: { browserKey: 'MouseLeft', globalKey: 'MOUSE1', displayName: 'Mouse Left' },
@@ -149,6 +164,7 @@ const globalKeyLookup = (() => {
   const map = { ...extraGlobalKeyMap };
   Object.values(keyMaps).forEach((item) => {
     map[item.globalKey] = item;
+    map[item.legacyKey] = item;
   });
   return map;
 })();
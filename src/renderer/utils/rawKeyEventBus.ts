@@ -12,6 +12,8 @@ export type RawInputPayload = {
   label: string;
   labels: string[];
   state: string;
+  timestampUs: number;
+  wallClockOffsetUs: number;
 };
 
 export type RawKeyEventListener = (payload: RawInputPayload) => void;
@@ -29,23 +31,35 @@ class RawKeyEventBus {
     if (this.initialized) return;
     this.initialized = true;
 
+    // 모든 리스너에게 브로드캐스트
+    const broadcast = (payload: RawInputPayload) => {
+      this.listeners.forEach((listener) => {
+        try {
+          listener(payload);
+        } catch (error) {
+          console.error("[RawKeyEventBus] Listener error:", error);
+        }
+      });
+    };
+
     try {
-      // 백엔드 이벤트 리스너 등록
-      const unlisten = await listen<RawInputPayload>(
+      // 백엔드 이벤트 리스너 등록 (묶음 전달 모드에서는 keys:batch 안에 들어옴)
+      const unlistenRaw = await listen<RawInputPayload>(
         "input:raw",
-        ({ payload }) => {
-          // 모든 리스너에게 브로드캐스트
-          this.listeners.forEach((listener) => {
-            try {
-              listener(payload);
-            } catch (error) {
-              console.error("[RawKeyEventBus] Listener error:", error);
-            }
-          });
-        }
+        ({ payload }) => broadcast(payload)
       );
-
-      this.unlistenFn = unlisten;
+      const unlistenBatch = await listen<{
+        entries: { event: string; payload: RawInputPayload }[];
+      }>("keys:batch", ({ payload }) => {
+        payload.entries.forEach((entry) => {
+          if (entry.event === "input:raw") broadcast(entry.payload);
+        });
+      });
+
+      this.unlistenFn = () => {
+        unlistenRaw();
+        unlistenBatch();
+      };
     } catch (error) {
       console.error("[RawKeyEventBus] Failed to initialize:", error);
       this.initialized = false;
@@ -147,6 +147,8 @@ export default function App() {
   const matchesShortcut = React.useCallback(
     (event: KeyboardEvent, binding?: ShortcutBinding) => {
       if (!binding?.key) return false;
+      // 동시 입력/시퀀스 단축키는 데몬이 전역으로 처리
+      if (binding.chord?.length || binding.leader?.key) return false;
       const ctrl = !!binding.ctrl;
       const shift = !!binding.shift;
       const alt = !!binding.alt;
@@ -301,6 +303,14 @@ export default function App() {
     isSettingsOpen,
   ]);
 
+  // 게임 포커스 중 전역 단축키로 들어온 사이드 패널 토글
+  useEffect(() => {
+    return window.api.app.onShortcut(({ action }) => {
+      if (action !== "toggleSettingsPanel" || isSettingsOpen) return;
+      usePropertiesPanelStore.getState().requestCanvasPanelToggle();
+    });
+  }, [isSettingsOpen]);
+
   const showAlert = (message: string, confirmText?: string) => {
     setAlertState({
       isOpen: true,
@@ -9,6 +9,7 @@ import React, {
 } from "react";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { isMac } from "@utils/platform";
+import { useTranslation } from "@contexts/I18nContext";
 import { Key } from "@components/Key";
 import {
   DEFAULT_NOTE_BORDER_RADIUS,
@@ -18,10 +19,12 @@ import { useCustomCssInjection } from "@hooks/useCustomCssInjection";
 import { useCustomJsInjection } from "@hooks/useCustomJsInjection";
 import { useBlockBrowserShortcuts } from "@hooks/useBlockBrowserShortcuts";
 import { useNoteSystem } from "@hooks/useNoteSystem";
+import { useLatencyDiagnostics } from "@hooks/useLatencyDiagnostics";
 import { useAppBootstrap } from "@hooks/useAppBootstrap";
 import { useKeyStore } from "@stores/useKeyStore";
 import {
   setKeyActive as setKeyActiveSignal,
+  setKeyGesture,
   resetAllKeySignals,
 } from "@stores/keySignals";
 import { useSettingsStore } from "@stores/useSettingsStore";
@@ -31,8 +34,10 @@ import {
   type KeyPosition,
 } from "@src/types/keys";
 import KeyCounterLayer from "@components/overlay/KeyCounterLayer";
+import { LatencyDisplay } from "@components/overlay/LatencyDisplay";
 import { PluginElementsRenderer } from "@components/PluginElementsRenderer";
 import { usePluginDisplayElementStore } from "@stores/usePluginDisplayElementStore";
+import type { ImeState } from "@src/types/api";
 
 const FALLBACK_POSITION: KeyPosition = {
   dx: 0,
@@ -76,6 +81,7 @@ export default function App() {
   useAppBootstrap();
   useBlockBrowserShortcuts();
   const macOS = isMac();
+  const { t } = useTranslation();
   const developerModeEnabled = useSettingsStore(
     (state) => state.developerModeEnabled
   );
@@ -229,6 +235,7 @@ export default function App() {
     const unsubscribe = import("@utils/keyEventBus").then(({ keyEventBus }) => {
       return keyEventBus.subscribe(({ key, state }) => {
         const isDown = state === "DOWN";
+        if (!isDown) setKeyGesture(key, null);
         // 키 UI 업데이트 (딜레이 적용)
         updateKeySignalWithDelay(key, isDown);
         // 노트 이펙트는 즉시 처리 (딜레이 없음)
@@ -277,6 +284,59 @@ export default function App() {
     selectedKeyType,
   ]);
 
+  // 길게 누르기/두 번 누르기를 키 요소의 data-gesture 속성으로 노출 (CSS용)
+  useEffect(() => {
+    const unsubHold = window.api.keys.onHold(({ key }) =>
+      setKeyGesture(key, "hold")
+    );
+    const unsubDoubleTap = window.api.keys.onDoubleTap(({ key }) =>
+      setKeyGesture(key, "double-tap")
+    );
+    return () => {
+      unsubHold();
+      unsubDoubleTap();
+    };
+  }, []);
+
+  // 입력 캡처 일시정지 표시 (전역 단축키/설정에서 전환)
+  const [capturePaused, setCapturePaused] = useState(false);
+  useEffect(() => {
+    window.api.daemon
+      .getCapture()
+      .then(({ paused }) => setCapturePaused(paused))
+      .catch(() => {});
+    return window.api.daemon.onCaptureChanged(({ paused }) =>
+      setCapturePaused(paused)
+    );
+  }, []);
+
+  // 포커스된 앱의 IME 상태 (한글/かな 입력 중이면 게임에서 키가 먹힐 수 있어 표시)
+  const [imeState, setImeState] = useState<ImeState | null>(null);
+  useEffect(() => {
+    window.api.keys
+      .getImeState()
+      .then(setImeState)
+      .catch(() => {});
+    return window.api.keys.onImeChanged(setImeState);
+  }, []);
+  const imeComposing =
+    imeState?.conversion === "native" || imeState?.conversion === "katakana";
+
+  // 지연 시간 진단 패널 (inputSettings.latencyPanel)
+  const [latencyPanel, setLatencyPanel] = useState(false);
+  useEffect(() => {
+    window.api.settings
+      .get()
+      .then(({ inputSettings }) =>
+        setLatencyPanel(inputSettings?.latencyPanel ?? false)
+      )
+      .catch(() => {});
+    return window.api.settings.onChanged(({ full }) =>
+      setLatencyPanel(full.inputSettings?.latencyPanel ?? false)
+    );
+  }, []);
+  const latency = useLatencyDiagnostics(latencyPanel);
+
   const currentKeys = useMemo(
     () => keyMappings[selectedKeyType] ?? [],
     [keyMappings, selectedKeyType]
@@ -515,6 +575,8 @@ export default function App() {
             }),
       }}
       onMouseDown={handleOverlayMouseDown}
+      data-ime-conversion={imeState?.conversion}
+      data-ime-language={imeState?.language ?? undefined}
     >
       {noteEffect && (
         <Suspense fallback={null}>
@@ -566,6 +628,25 @@ export default function App() {
         windowType="overlay"
         positionOffset={positionOffset}
       />
+      {capturePaused ? (
+        <div
+          className="capture-paused-indicator absolute top-[6px] left-[6px] px-[6px] py-[2px] rounded-[4px] bg-black/60 text-white text-[11px] pointer-events-none select-none"
+          data-capture-paused
+        >
+          {t("common.capturePaused")}
+        </div>
+      ) : null}
+      {imeComposing ? (
+        <div
+          className="ime-indicator absolute top-[6px] right-[6px] px-[6px] py-[2px] rounded-[4px] bg-black/60 text-white text-[11px] pointer-events-none select-none"
+          data-ime-conversion={imeState?.conversion}
+        >
+          {t("common.imeComposing")}
+        </div>
+      ) : null}
+      {latencyPanel ? (
+        <LatencyDisplay report={latency.report} onReset={latency.reset} />
+      ) : null}
     </div>
   );
 }
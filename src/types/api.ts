@@ -6,25 +6,236 @@ import {
   KeyMappings,
   KeyPositions,
   KeyCounters,
+  KeyModeDevices,
 } from "@src/types/keys";
 import {
   SettingsDiff,
   SettingsPatchInput,
   SettingsState,
 } from "@src/types/settings";
+import { CanvasShortcutAction } from "@src/types/shortcuts";
 
 export type ModeChangePayload = { mode: string };
+/** 게임 포커스 중 전역 단축키로 들어온 캔버스 동작 */
+export type ShortcutTriggeredPayload = { action: CanvasShortcutAction };
 export type CustomTabsChangePayload = {
   customTabs: CustomTab[];
   selectedKeyType: string;
 };
-export type KeyStatePayload = { key: string; state: string; mode: string };
+/**
+ * 입력 캡처 시각 (데몬의 모노토닉 클록, µs)
+ * 벽시계 시각(ms) = (timestampUs + wallClockOffsetUs) / 1000
+ */
+export type InputTimestamp = {
+  timestampUs: number;
+  wallClockOffsetUs: number;
+};
+export type KeyStatePayload = {
+  key: string;
+  state: string;
+  mode: string;
+} & InputTimestamp;
 export type InputDevice = "keyboard" | "mouse" | "gamepad" | "unknown";
 export type RawInputPayload = {
   device: InputDevice;
   label: string;
   labels: string[];
+  /** 플랫폼 공통 키 ID (KeyboardEvent.code 기준, 마우스/게임패드는 라벨 그대로) */
+  code?: string | null;
   state: string;
+  /** 입력 장치 식별자 (장치를 구분할 수 없는 환경에서는 null) */
+  deviceId?: string | null;
+  deviceName?: string | null;
+  /** 소프트웨어가 주입한 입력 (감지 가능한 플랫폼에서만) */
+  injected: boolean;
+  /** 이미 눌린 키의 자동 반복 */
+  repeat: boolean;
+} & InputTimestamp;
+/** 연결된 입력 장치 */
+export type InputDeviceInfo = {
+  id: string;
+  name: string;
+  kind: InputDevice;
+  /** 소프트웨어가 만든 가상 장치 (입력이 주입된 것으로 표시됨) */
+  synthetic: boolean;
+};
+/** IME 변환 모드 (alphanumeric: 영문/英数 또는 IME 꺼짐, native: 한글/히라가나) */
+export type ImeConversion = "unknown" | "alphanumeric" | "native" | "katakana";
+/** 포커스된 앱의 입력 언어·IME 상태 (Windows·macOS에서만 보고됨) */
+export type ImeState = {
+  /** BCP 47 언어 태그 (예: ko-KR) */
+  language: string | null;
+  /** 플랫폼 입력 소스 ID (Windows HKL, macOS 입력 소스) */
+  source: string | null;
+  conversion: ImeConversion;
+};
+/** 디바운스 필터가 버린 키별 채터 통계 */
+export type KeyChatterStats = {
+  key: string;
+  count: number;
+  shortestGapUs: number;
+  lastAtMs: number;
+};
+/** 데몬이 앱으로 전달할 입력 장치 종류 */
+export type DaemonCaptureFilters = {
+  keyboard: boolean;
+  mouse: boolean;
+  gamepad: boolean;
+};
+export type DaemonCaptureState = {
+  paused: boolean;
+  filters: DaemonCaptureFilters;
+};
+export type DaemonStatus = "stopped" | "running" | "restarting" | "failed";
+export type DaemonStatusPayload = {
+  status: DaemonStatus;
+  reason?: string;
+  crashCount: number;
+  /** 재시작까지 남은 시간 (restarting일 때만) */
+  retryInMs?: number;
+};
+export type DaemonHealth = {
+  status: DaemonStatus;
+  reason: string | null;
+  crashCount: number;
+  restartCount: number;
+  consecutiveFailures: number;
+  /** 데몬 stderr 최근 줄 */
+  stderrTail: string[];
+};
+/** 지연 시간 요약 (µs, 샘플이 없으면 모두 0) */
+export type LatencySummary = {
+  count: number;
+  minUs: number;
+  meanUs: number;
+  p50Us: number;
+  p95Us: number;
+  p99Us: number;
+  maxUs: number;
+};
+/** 세션(앱 실행 또는 초기화 이후) 단위 입력 지연 진단 */
+export type LatencyReport = {
+  startedAtMs: number;
+  /** 데몬 캡처 → keys:state 발생 */
+  emit: LatencySummary;
+  /** 데몬 캡처 → 오버레이 프레임 렌더 (진단 패널이 켜져 있는 동안만 수집) */
+  render: LatencySummary;
+  angleMode: string;
+};
+/** 모드 하나의 KPS / APM 통계 */
+export type ModeKps = {
+  /** 최근 1초 눌림 수 */
+  kps: number;
+  /** 최근 1분 눌림 수 */
+  apm: number;
+  /** 입력이 있던 동안의 평균 KPS */
+  avgKps: number;
+  peakKps: number;
+  /** 세션 동안의 눌림 수 */
+  presses: number;
+};
+/** 세션 단위 KPS / APM 통계 (stats:kps) */
+export type KpsReport = {
+  startedAtMs: number;
+  /** 현재 키 모드 */
+  mode: string;
+  /** 입력이 있었던 모드별 통계 */
+  modes: Record<string, ModeKps>;
+};
+/** 분포 요약 (µs, 샘플이 없으면 모두 0) */
+export type TimingSummary = {
+  count: number;
+  meanUs: number;
+  stddevUs: number;
+  minUs: number;
+  p50Us: number;
+  p90Us: number;
+  p99Us: number;
+  maxUs: number;
+};
+/** 누름 시간(Down → Up)과 같은 키의 누름 간격(Down → Down, 2초 초과 제외) */
+export type KeyTiming = {
+  hold: TimingSummary;
+  interval: TimingSummary;
+};
+export type ModeTiming = {
+  /** 모드의 모든 키를 합친 분포 */
+  overall: KeyTiming;
+  keys: Record<string, KeyTiming>;
+};
+/** 세션 단위 키 입력 타이밍 분석 */
+export type KeyTimingReport = {
+  startedAtMs: number;
+  modes: Record<string, ModeTiming>;
+};
+/** 모드 → 키 → 횟수 */
+export type HistoryCounts = Record<string, Record<string, number>>;
+/** hour는 보관 기간(inputSettings.historyRetentionDays) 안의 날짜만 조회됨 */
+export type HistoryGranularity = "hour" | "day";
+export type HistoryBucket = {
+  /** YYYY-MM-DD 또는 YYYY-MM-DDTHH (로컬 시각) */
+  period: string;
+  total: number;
+  counts: HistoryCounts;
+};
+/** 기간별 카운터 기록 (입력이 없던 칸은 빠짐) */
+export type CounterHistoryRange = {
+  from: string;
+  to: string;
+  granularity: HistoryGranularity;
+  buckets: HistoryBucket[];
+};
+export type CounterHistoryQuery = {
+  /** YYYY-MM-DD (로컬 날짜, 포함) */
+  from: string;
+  /** YYYY-MM-DD (로컬 날짜, 포함) */
+  to: string;
+  granularity: HistoryGranularity;
+  /** 생략하면 모든 모드 */
+  mode?: string;
+};
+/** 자동 감지한 플레이 세션 (session:ended) */
+export type PlaySession = {
+  id: string;
+  /** 첫 눌림 시각 (Unix ms) */
+  startedAtMs: number;
+  /** 마지막 눌림 시각 (Unix ms) */
+  endedAtMs: number;
+  durationMs: number;
+  /** 가장 많이 누른 키 모드 */
+  mode: string;
+  presses: number;
+  peakKps: number;
+  /** 모드 → 키 → 횟수 */
+  counts: HistoryCounts;
+};
+/** 스냅샷을 남긴 이유 (restore=스냅샷 복원 직전 값) */
+export type CounterSnapshotReason = "reset" | "scheduled" | "restore";
+/** 카운터 초기화 직전 값 */
+export type CounterSnapshot = {
+  id: string;
+  /** 만든 시각 (Unix ms) */
+  createdAtMs: number;
+  reason: CounterSnapshotReason;
+  /** 특정 모드만 초기화한 경우 */
+  mode?: string;
+  /** 키 하나만 초기화한 경우 */
+  key?: string;
+  total: number;
+  /** 초기화된 범위의 값 (복원 시 이 값들만 되돌림) */
+  counters: KeyCounters;
+};
+/** 통계 파일 내보내기 결과 (대화상자를 취소하면 success: false) */
+export type StatsExportResult = {
+  success: boolean;
+  path?: string;
+};
+/** 하드웨어 입력 대신 재생할 녹화 파일 (JSON Lines) */
+export type DaemonReplaySource = {
+  path: string;
+  /** 재생 속도 배율 (기본 1) */
+  speed?: number;
+  loop?: boolean;
 };
 export type OverlayBounds = {
   x: number;
@@ -109,7 +320,32 @@ export type CustomTabDeleteResult = {
   selected: string;
   error?: string;
 };
-export type KeyCounterUpdate = { mode: string; key: string; count: number };
+export type KeyCounterUpdate = {
+  mode: string;
+  key: string;
+  count: number;
+} & InputTimestamp;
+/** 묶음 전달 모드(inputSettings.batchWindowMs > 0)에서 keys:batch에 담기는 개별 이벤트 */
+export type KeyBatchEntry =
+  | { event: "input:raw"; payload: RawInputPayload }
+  | { event: "keys:counter"; payload: KeyCounterUpdate }
+  | { event: "keys:state"; payload: KeyStatePayload };
+/** 한 프레임 동안 들어온 키 이벤트 (발생 순서대로) */
+export type KeyBatchPayload = {
+  entries: KeyBatchEntry[];
+};
+/** 설정 시간 이상 눌린 키 (`keys:hold`) */
+export type KeyHoldEvent = {
+  mode: string;
+  key: string;
+  heldMs: number;
+} & InputTimestamp;
+/** 짧은 간격으로 두 번 눌린 키 (`keys:double-tap`) */
+export type KeyDoubleTapEvent = {
+  mode: string;
+  key: string;
+  intervalMs: number;
+} & InputTimestamp;
 
 export type PresetOperationResult = { success: boolean; error?: string };
 
@@ -563,6 +799,9 @@ export interface DMNoteAPI {
     bootstrap(): Promise<BootstrapPayload>;
     openExternal(url: string): Promise<void>;
     restart(): Promise<void>;
+    onShortcut(
+      listener: (payload: ShortcutTriggeredPayload) => void
+    ): Unsubscribe;
   };
   window: {
     type: "main" | "overlay";
@@ -589,15 +828,39 @@ export interface DMNoteAPI {
       listener: (positions: KeyPositions) => void
     ): Unsubscribe;
     onModeChanged(listener: (payload: ModeChangePayload) => void): Unsubscribe;
+    /** 묶음 전달 모드에서도 keys:batch를 풀어 개별 이벤트로 전달 */
     onKeyState(listener: (payload: KeyStatePayload) => void): Unsubscribe;
+    /** 묶음 전달 모드에서만 발생 */
+    onBatch(listener: (payload: KeyBatchPayload) => void): Unsubscribe;
     onRawInput(listener: (payload: RawInputPayload) => void): Unsubscribe;
+    /** 키 할당 중에는 매핑되지 않은 키도 전달받음 (mappedKeysOnly 무시) */
+    grantRawInput(): Promise<void>;
+    revokeRawInput(): Promise<void>;
     resetCounters(): Promise<KeyCounters>;
     resetCountersMode(mode: string): Promise<KeyCounters>;
     resetSingleCounter(mode: string, key: string): Promise<KeyCounters>;
+    /** 초기화 전 스냅샷 (최근 순) */
+    listCounterSnapshots(): Promise<CounterSnapshot[]>;
+    /** 스냅샷 범위의 카운터를 되돌림 (지금 값은 새 스냅샷으로 남음) */
+    restoreCounterSnapshot(id: string): Promise<KeyCounters>;
+    deleteCounterSnapshot(id: string): Promise<boolean>;
     onCounterChanged(
       listener: (payload: KeyCounterUpdate) => void
     ): Unsubscribe;
     onCountersChanged(listener: (payload: KeyCounters) => void): Unsubscribe;
+    onHold(listener: (payload: KeyHoldEvent) => void): Unsubscribe;
+    onDoubleTap(listener: (payload: KeyDoubleTapEvent) => void): Unsubscribe;
+    listDevices(): Promise<InputDeviceInfo[]>;
+    getDeviceFilters(): Promise<KeyModeDevices>;
+    setDeviceFilter(mode: string, devices: string[]): Promise<KeyModeDevices>;
+    onDeviceFiltersChanged(
+      listener: (filters: KeyModeDevices) => void
+    ): Unsubscribe;
+    /** 아직 보고되지 않았거나 지원하지 않는 플랫폼이면 null */
+    getImeState(): Promise<ImeState | null>;
+    onImeChanged(listener: (state: ImeState) => void): Unsubscribe;
+    getChatterStats(): Promise<KeyChatterStats[]>;
+    resetChatterStats(): Promise<void>;
     customTabs: {
       list(): Promise<CustomTab[]>;
       create(name: string): Promise<CustomTabResult>;
@@ -626,6 +889,55 @@ export interface DMNoteAPI {
     onAnchor(listener: (payload: OverlayAnchorPayload) => void): Unsubscribe;
     onResized(listener: (payload: OverlayResizePayload) => void): Unsubscribe;
   };
+  daemon: {
+    /** 왕복 시간(ms) */
+    ping(): Promise<number>;
+    getCapture(): Promise<DaemonCaptureState>;
+    setPaused(paused: boolean): Promise<void>;
+    setFilters(filters: DaemonCaptureFilters): Promise<void>;
+    getHealth(): Promise<DaemonHealth>;
+    restart(): Promise<void>;
+    getReplay(): Promise<DaemonReplaySource | null>;
+    /** null이면 실제 입력으로 복귀 */
+    setReplay(source: DaemonReplaySource | null): Promise<void>;
+    onStatus(listener: (payload: DaemonStatusPayload) => void): Unsubscribe;
+    onCaptureChanged(
+      listener: (payload: DaemonCaptureState) => void
+    ): Unsubscribe;
+  };
+  diagnostics: {
+    getLatency(): Promise<LatencyReport>;
+    /** 새 측정 세션 시작 (렌더러 설정 비교용) */
+    resetLatency(): Promise<void>;
+    /** 오버레이 렌더 확인: 캡처 → 프레임 지연 (µs) */
+    reportRenderLatency(samplesUs: number[]): Promise<void>;
+  };
+  stats: {
+    getKps(): Promise<KpsReport>;
+    /** mode를 생략하면 모든 모드를 지우고 새 세션 시작 */
+    resetKps(mode?: string): Promise<KpsReport>;
+    /** 입력이 있는 동안 100ms마다 발생 */
+    onKps(listener: (payload: KpsReport) => void): Unsubscribe;
+    getKeyTiming(): Promise<KeyTimingReport>;
+    /** mode를 생략하면 모든 모드를 지우고 새 세션 시작 */
+    resetKeyTiming(mode?: string): Promise<KeyTimingReport>;
+    /** 저장 대화상자로 JSON 파일 저장 */
+    exportKeyTiming(): Promise<StatsExportResult>;
+    getCounterHistory(query: CounterHistoryQuery): Promise<CounterHistoryRange>;
+    /** 저장 대화상자로 CSV(period,mode,key,count) 또는 JSON 파일 저장 */
+    exportCounterHistory(
+      query: CounterHistoryQuery,
+      format: "csv" | "json"
+    ): Promise<StatsExportResult>;
+    /** 끝난 플레이 세션 (최근 순) */
+    listSessions(): Promise<PlaySession[]>;
+    /** 해당 id의 세션이 없으면 false */
+    deleteSession(id: string): Promise<boolean>;
+    /** 저장 대화상자로 CSV(세션별 요약) 또는 JSON(키별 횟수 포함) 파일 저장 */
+    exportSessions(format: "csv" | "json"): Promise<StatsExportResult>;
+    /** 입력 없이 inputSettings.sessionIdleMinutes가 지나 세션이 끝나면 발생 */
+    onSessionEnded(listener: (payload: PlaySession) => void): Unsubscribe;
+  };
   css: {
     get(): Promise<CustomCss>;
     getUse(): Promise<boolean>;
@@ -160,6 +160,8 @@ export type KeyMode = z.infer<typeof keyModeSchema> | string;
 
 export const keyMappingSchema = z.record(z.string(), z.array(keySchema));
 export type KeyMappings = Record<string, string[]>;
+/** 키 모드별 입력을 받을 장치 id (없거나 비어 있으면 모든 장치) */
+export type KeyModeDevices = Record<string, string[]>;
 
 const gradientNoteColorSchema = z.object({
   type: z.literal("gradient"),
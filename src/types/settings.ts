@@ -30,6 +30,42 @@ export const DEFAULT_GRID_SETTINGS: GridSettings = {
   gridSnapSize: 5,
 };
 
+/** 주입된 입력: count=실제 입력처럼 카운트, show=표시만, ignore=무시 */
+export type InjectedInputPolicy = "count" | "show" | "ignore";
+
+/** 카운터 자동 초기화 주기 (초기화 전 값은 스냅샷으로 보관, app-start=앱을 켤 때마다) */
+export type CounterResetSchedule = "off" | "daily" | "weekly" | "app-start";
+
+export interface InputSettings {
+  wheelPulseMs: number; // 휠 가상 키 펄스 길이 (10-1000ms)
+  wheelCoalesce: boolean; // 펄스 중 들어온 휠 노치를 하나로 합침
+  debounceMs: number; // 직전 Up 후 이 시간 안의 Down을 채터로 버림 (0-100ms, 0이면 비활성)
+  injectedInput: InjectedInputPolicy; // 매크로/리매퍼가 만든 입력 처리 방식
+  holdMs: number; // 이 시간 이상 누르면 keys:hold (100-5000ms)
+  doubleTapMs: number; // 이 간격 안에 다시 누르면 keys:double-tap (100-1000ms)
+  mappedKeysOnly: boolean; // 매핑된 키만 데몬에서 전달 (나머지 입력은 앱에 오지 않음)
+  latencyPanel: boolean; // 오버레이에 지연 시간 진단 패널 표시
+  batchWindowMs: number; // 이 시간 안의 키 이벤트를 keys:batch로 묶어 전달 (0-50ms, 0이면 이벤트마다)
+  historyRetentionDays: number; // 카운터 기록의 시간 단위 칸 보관 기간 (1-365일, 지나면 하루 단위로 합침)
+  sessionIdleMinutes: number; // 이 시간 동안 입력이 없으면 플레이 세션 종료 (1-120분)
+  counterResetSchedule: CounterResetSchedule; // 카운터 자동 초기화 주기 (daily/weekly는 로컬 날짜·ISO 주 기준)
+}
+
+export const DEFAULT_INPUT_SETTINGS: InputSettings = {
+  wheelPulseMs: 80,
+  wheelCoalesce: true,
+  debounceMs: 0,
+  injectedInput: "count",
+  holdMs: 500,
+  doubleTapMs: 300,
+  mappedKeysOnly: false,
+  latencyPanel: false,
+  batchWindowMs: 0,
+  historyRetentionDays: 7,
+  sessionIdleMinutes: 5,
+  counterResetSchedule: "off",
+};
+
 export interface SettingsState {
   hardwareAcceleration: boolean;
   alwaysOnTop: boolean;
@@ -48,6 +84,7 @@ export interface SettingsState {
   overlayResizeAnchor: OverlayResizeAnchor;
   keyCounterEnabled: boolean;
   gridSettings: GridSettings;
+  inputSettings: InputSettings;
   shortcuts: ShortcutsState;
 }
 
@@ -69,32 +106,45 @@ export const DEFAULT_SETTINGS_STATE: SettingsState = {
   overlayResizeAnchor: "top-left",
   keyCounterEnabled: false,
   gridSettings: DEFAULT_GRID_SETTINGS,
+  inputSettings: DEFAULT_INPUT_SETTINGS,
   shortcuts: DEFAULT_SHORTCUTS,
 };
 
 export type SettingsPatchInput = Partial<
   Omit<
     SettingsState,
-    "noteSettings" | "customCSS" | "customJS" | "gridSettings" | "shortcuts"
+    | "noteSettings"
+    | "customCSS"
+    | "customJS"
+    | "gridSettings"
+    | "inputSettings"
+    | "shortcuts"
   >
 > & {
   noteSettings?: Partial<NoteSettings>;
   customCSS?: Partial<CustomCss>;
   customJS?: Partial<CustomJs>;
   gridSettings?: Partial<GridSettings>;
+  inputSettings?: Partial<InputSettings>;
   shortcuts?: Partial<ShortcutsState>;
 };
 
 export type SettingsPatch = Partial<
   Omit<
     SettingsState,
-    "noteSettings" | "customCSS" | "customJS" | "gridSettings" | "shortcuts"
+    | "noteSettings"
+    | "customCSS"
+    | "customJS"
+    | "gridSettings"
+    | "inputSettings"
+    | "shortcuts"
   >
 > & {
   noteSettings?: NoteSettings;
   customCSS?: CustomCss;
   customJS?: CustomJs;
   gridSettings?: GridSettings;
+  inputSettings?: InputSettings;
   shortcuts?: ShortcutsState;
 };
 
@@ -142,6 +192,13 @@ export function normalizeSettingsPatch(
       } as GridSettings;
       continue;
     }
+    if (key === "inputSettings") {
+      next.inputSettings = {
+        ...current.inputSettings,
+        ...(value as Partial<InputSettings>),
+      } as InputSettings;
+      continue;
+    }
     if (key === "shortcuts") {
       next.shortcuts = {
         ...current.shortcuts,
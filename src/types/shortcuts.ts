@@ -4,6 +4,9 @@ export type ShortcutBinding = {
   shift?: boolean;
   alt?: boolean;
   meta?: boolean;
+  chord?: string[]; // key와 함께 눌러야 하는 다른 키 (예: F9 + F10)
+  leader?: ShortcutBinding; // 먼저 눌러야 하는 키 (시퀀스)
+  timeoutMs?: number; // leader 이후 입력 대기 시간
 };
 
 export type ShortcutsState = {
@@ -15,6 +18,11 @@ export type ShortcutsState = {
   zoomIn: ShortcutBinding;
   zoomOut: ShortcutBinding;
   resetZoom: ShortcutBinding;
+  resetModeCounters: ShortcutBinding;
+  toggleKeyCounter: ShortcutBinding;
+  toggleCapturePause: ShortcutBinding;
+  /** 키 모드 바로 전환 (모드 → 단축키, 전역) */
+  keyModeShortcuts?: Record<string, ShortcutBinding>;
 };
 
 export const DEFAULT_SHORTCUTS: ShortcutsState = {
@@ -62,4 +70,15 @@ export const DEFAULT_SHORTCUTS: ShortcutsState = {
     alt: false,
     meta: false,
   },
+  resetModeCounters: { key: "" },
+  toggleKeyCounter: { key: "" },
+  toggleCapturePause: { key: "" },
+  keyModeShortcuts: {},
 };
+
+/** 전역 단축키로 메인 창에 전달되는 캔버스 동작 */
+export type CanvasShortcutAction =
+  | "toggleSettingsPanel"
+  | "zoomIn"
+  | "zoomOut"
+  | "resetZoom";